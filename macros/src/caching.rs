@@ -1,72 +1,138 @@
 use proc_macro::TokenStream;
-use std::{
-    hash::{Hash, Hasher},
-    io::{BufRead, Write},
-    os::unix::fs::MetadataExt,
-    str::FromStr,
-};
 
+/// Expands `ts` through `f`, reusing a previous expansion of the same input by the
+/// same `macro_name` and crate version when the `unsafe_parser_gen_macro_cache_v1`
+/// feature is enabled.
+///
+/// The cache lives under `OUT_DIR` or `CARGO_TARGET_DIR`, and is skipped altogether
+/// when neither is set. Any io failure falls back to a plain expansion.
+/// Errors are never cached, as their spans would be lost by the round trip.
 pub(crate) fn cached(
+    macro_name: &str,
     attr: TokenStream,
     ts: TokenStream,
-    f: impl FnOnce(TokenStream, TokenStream) -> TokenStream,
-) -> std::io::Result<TokenStream> {
-    const CACHE_PATH: &str = "target/cache/lavan_proc_macro";
-    let ref latest = format!("{CACHE_PATH}/latest");
+    f: impl FnOnce(TokenStream, TokenStream) -> syn::Result<TokenStream>,
+) -> TokenStream {
+    #[cfg(feature = "unsafe_parser_gen_macro_cache_v1")]
+    let output = match disk::cache_dir() {
+        Some(dir) => disk::cached(&dir, macro_name, attr, ts, f),
+        None => f(attr, ts),
+    };
+    #[cfg(not(feature = "unsafe_parser_gen_macro_cache_v1"))]
+    let output = {
+        let _ = macro_name;
+        f(attr, ts)
+    };
 
-    let string = attr.to_string() + &ts.to_string();
-    let mut hasher = std::hash::DefaultHasher::new();
-    string.hash(&mut hasher);
-    let hash = hasher.finish();
-    let file_content = std::fs::read(format!("{CACHE_PATH}/{hash}"));
+    output.unwrap_or_else(|error| error.into_compile_error().into())
+}
+
+#[cfg(feature = "unsafe_parser_gen_macro_cache_v1")]
+mod disk {
+    use proc_macro::TokenStream;
+    use std::{
+        hash::{Hash, Hasher},
+        io::Write,
+        path::{Path, PathBuf},
+        str::FromStr,
+        time::{Duration, SystemTime},
+    };
+
+    /// How long an entry survives without being used, between two evictions
+    const EXPIRY: Duration = Duration::from_secs(60);
+
+    pub(super) fn cache_dir() -> Option<PathBuf> {
+        let (base, sub) = if let Some(out_dir) = std::env::var_os("OUT_DIR") {
+            (out_dir, "lavan_proc_macro")
+        } else {
+            (
+                std::env::var_os("CARGO_TARGET_DIR")?,
+                "cache/lavan_proc_macro",
+            )
+        };
+        Some(Path::new(&base).join(sub))
+    }
+
+    pub(super) fn cached(
+        dir: &Path,
+        macro_name: &str,
+        attr: TokenStream,
+        ts: TokenStream,
+        f: impl FnOnce(TokenStream, TokenStream) -> syn::Result<TokenStream>,
+    ) -> syn::Result<TokenStream> {
+        let key = (
+            env!("CARGO_PKG_VERSION"),
+            macro_name,
+            attr.to_string(),
+            ts.to_string(),
+        );
+        let mut hasher = std::hash::DefaultHasher::new();
+        key.hash(&mut hasher);
+        let hash = hasher.finish().to_string();
 
-    Ok(match file_content {
-        Ok(content) => {
-            TokenStream::from_str(&String::from_utf8(content).expect("erro0")).expect("error1")
+        if let Some(output) = load(dir, &hash) {
+            return Ok(output);
         }
-        Err(_) => {
-            let output = f(attr, ts);
-            std::fs::create_dir_all(CACHE_PATH)?;
 
-            let now = std::time::SystemTime::now()
-                .duration_since(std::time::SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
+        let output = f(attr, ts)?;
+        let _ = store(dir, &hash, &output);
+        Ok(output)
+    }
 
-            std::fs::File::create(latest)?;
-            let ctime = std::fs::metadata(latest)?.mtime();
+    fn load(dir: &Path, hash: &str) -> Option<TokenStream> {
+        let content = std::fs::read_to_string(dir.join(hash)).ok()?;
+        let output = TokenStream::from_str(&content).ok()?;
+        let _ = touch(dir, hash);
+        Some(output)
+    }
 
-            if now - ctime as u64 >= 60 {
-                if let Ok(mut file) = std::fs::File::open(latest) {
-                    let allowed = std::io::BufReader::new(&mut file)
-                        .lines()
-                        .map(|e| e.expect("error2"))
-                        .collect::<Vec<_>>();
-                    for file in std::fs::read_dir(CACHE_PATH)? {
-                        let file = file
-                            .expect("error3")
-                            .file_name()
-                            .to_string_lossy()
-                            .into_owned();
-                        if !allowed.contains(&file) {
-                            std::fs::remove_file(format!("{CACHE_PATH}/{file}"))?;
-                            std::fs::File::create(latest)?;
-                        }
-                    }
-                    drop(file);
-                }
-            }
+    fn store(dir: &Path, hash: &str, output: &TokenStream) -> std::io::Result<()> {
+        std::fs::create_dir_all(dir)?;
+        evict(dir)?;
+        // written aside then renamed, so that a parallel build never loads half of it
+        let partial = dir.join(format!("{hash}.{}.partial", std::process::id()));
+        std::fs::write(&partial, output.to_string())?;
+        std::fs::rename(partial, dir.join(hash))?;
+        touch(dir, hash)
+    }
+
+    /// Records the entry as recently used
+    fn touch(dir: &Path, hash: &str) -> std::io::Result<()> {
+        let mut latest = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join("latest"))?;
+        writeln!(latest, "{hash}")
+    }
 
-            let mut file = std::fs::OpenOptions::new()
-                .write(true)
-                .append(true)
-                .open(latest)?;
-            file.write(hash.to_string().as_bytes())?;
-            file.write(b"\n")?;
-            file.flush()?;
+    /// Removes the entries not used since the last eviction, once it has expired
+    ///
+    /// The expiry is kept by the `swept` file, rewritten on each eviction, as
+    /// `latest` is appended to on every use.
+    fn evict(dir: &Path) -> std::io::Result<()> {
+        let swept = dir.join("swept");
+        let Ok(metadata) = std::fs::metadata(&swept) else {
+            // the first entry starts the first period
+            return std::fs::write(swept, "");
+        };
+        let age = SystemTime::now()
+            .duration_since(metadata.modified()?)
+            .unwrap_or_default();
+        if age < EXPIRY {
+            return Ok(());
+        }
 
-            std::fs::write(format!("{CACHE_PATH}/{hash}"), output.to_string())?;
-            output
+        let latest = dir.join("latest");
+        let used = std::fs::read_to_string(&latest).unwrap_or_default();
+        let used: Vec<&str> = used.lines().collect();
+        for entry in std::fs::read_dir(dir)? {
+            let name = entry?.file_name();
+            let name = name.to_string_lossy();
+            if !["latest", "swept"].contains(&&*name) && !used.contains(&&*name) {
+                std::fs::remove_file(dir.join(&*name))?;
+            }
         }
-    })
+        std::fs::write(latest, "")?;
+        std::fs::write(swept, "")
+    }
 }
@@ -0,0 +1,363 @@
+use crate::prelude::*;
+use syn::{
+    spanned::Spanned, Attribute, Data, DeriveInput, Error, Fields, GenericArgument, Lit, Path,
+    PathArguments, Result, Type,
+};
+
+pub fn gen(target: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(target as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// The options given through `#[lavan(...)]` attributes
+#[derive(Default)]
+struct Options {
+    token: Option<Lit>,
+    sep_by: Option<Lit>,
+    skip_ws: bool,
+    with: Option<Path>,
+    input: Option<Type>,
+}
+
+/// Where the attributes have been found, as not every option applies everywhere
+#[derive(Clone, Copy, PartialEq)]
+enum Place {
+    Container,
+    Variant,
+    Field,
+}
+
+impl Options {
+    fn parse(attrs: &[Attribute], place: Place) -> Result<Self> {
+        let mut options = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("lavan")) {
+            attr.parse_nested_meta(|meta| {
+                let allowed = if meta.path.is_ident("token") {
+                    options.token = Some(text_literal(meta.value()?.parse()?)?);
+                    true
+                } else if meta.path.is_ident("skip_ws") {
+                    options.skip_ws = true;
+                    true
+                } else if meta.path.is_ident("sep_by") {
+                    options.sep_by = Some(text_literal(meta.value()?.parse()?)?);
+                    place == Place::Field
+                } else if meta.path.is_ident("with") {
+                    options.with = Some(meta.value()?.parse()?);
+                    place == Place::Field
+                } else if meta.path.is_ident("input") {
+                    options.input = Some(meta.value()?.parse()?);
+                    place == Place::Container
+                } else {
+                    return Err(meta.error("unknown lavan option"));
+                };
+
+                if !allowed {
+                    return Err(meta.error("this lavan option is not supported here"));
+                }
+                Ok(())
+            })?;
+        }
+
+        if let (Some(with), Some(_)) = (&options.with, &options.sep_by) {
+            return Err(Error::new(
+                with.span(),
+                "`with` and `sep_by` cannot be used together",
+            ));
+        }
+
+        Ok(options)
+    }
+
+    fn uses_text(&self) -> bool {
+        self.token.is_some() || self.sep_by.is_some() || self.skip_ws
+    }
+}
+
+fn text_literal(lit: Lit) -> Result<Lit> {
+    match lit {
+        Lit::Str(_) | Lit::Char(_) => Ok(lit),
+        _ => Err(Error::new(lit.span(), "expected a string or char literal")),
+    }
+}
+
+/// The state shared while generating the body of `FromParse::parse`
+struct Gen {
+    lavan: TokenStream2,
+    input: TokenStream2,
+    uses_text: bool,
+    uses_ws: bool,
+    bounds: Vec<TokenStream2>,
+}
+
+impl Gen {
+    fn skip_ws(&mut self, skip_ws: bool) -> TokenStream2 {
+        if skip_ws {
+            self.uses_ws = true;
+            quote! { skip_ws(input); }
+        } else {
+            quote! {}
+        }
+    }
+
+    fn token(&mut self, token: &Option<Lit>, skip_ws: bool, fail: &TokenStream2) -> TokenStream2 {
+        let Some(token) = token else {
+            return quote! {};
+        };
+        self.uses_text = true;
+        let skip_ws = self.skip_ws(skip_ws);
+        let (lavan, input) = (&self.lavan, &self.input);
+        quote! {
+            #skip_ws
+            if !#lavan::parser::traits::ParseOnce::<#input>::parse_once(#token, input) {
+                #fail;
+            }
+        }
+    }
+
+    fn value(&self, ty: &Type, fail: &TokenStream2) -> TokenStream2 {
+        let (lavan, input) = (&self.lavan, &self.input);
+        quote! {
+            match #lavan::__private::Response::control_flow(
+                <#ty as #lavan::parser::traits::FromParse<#input>>::parse(input)
+            ) {
+                ::core::ops::ControlFlow::Continue(value) => value,
+                ::core::ops::ControlFlow::Break(_) => #fail,
+            }
+        }
+    }
+
+    fn fields(
+        &mut self,
+        fields: &Fields,
+        skip_ws: bool,
+        fail: &TokenStream2,
+    ) -> Result<(TokenStream2, TokenStream2)> {
+        let (lavan, input) = (self.lavan.clone(), self.input.clone());
+        let mut parsing = vec![];
+        let mut idents = vec![];
+
+        for (idx, field) in fields.iter().enumerate() {
+            let options = Options::parse(&field.attrs, Place::Field)?;
+            let skip_ws = skip_ws || options.skip_ws;
+            let ident = quote::format_ident!("__field{idx}");
+            let ty = &field.ty;
+
+            parsing.push(self.token(&options.token, skip_ws, fail));
+            let skip = self.skip_ws(skip_ws);
+
+            let value = if let Some(with) = &options.with {
+                quote! {
+                    match #lavan::__private::Response::control_flow(
+                        #lavan::parser::traits::ParseOnce::<#input>::parse_once(#with, input)
+                    ) {
+                        ::core::ops::ControlFlow::Continue(value) => value,
+                        ::core::ops::ControlFlow::Break(_) => #fail,
+                    }
+                }
+            } else if let Some(sep) = &options.sep_by {
+                self.uses_text = true;
+                let item = vec_item(ty)?;
+                self.bounds
+                    .push(quote! { #item: #lavan::parser::traits::FromParse<#input> });
+                let first = self.value(item, &quote! { break 'items });
+                let next = self.value(
+                    item,
+                    &quote! { {
+                        #lavan::input::traits::Stream::backtrack(input, state);
+                        break 'items;
+                    } },
+                );
+                quote! {{
+                    let mut items = ::std::vec::Vec::new();
+                    let state = #lavan::input::traits::Stream::savestate(input);
+                    'items: {
+                        items.push(#first);
+                        loop {
+                            let state = #lavan::input::traits::Stream::savestate(input);
+                            #skip
+                            if !#lavan::parser::traits::ParseOnce::<#input>::parse_once(#sep, input) {
+                                #lavan::input::traits::Stream::backtrack(input, state);
+                                break 'items;
+                            }
+                            #skip
+                            items.push(#next);
+                        }
+                    }
+                    if items.is_empty() {
+                        #lavan::input::traits::Stream::backtrack(input, state);
+                    }
+                    items
+                }}
+            } else {
+                self.bounds
+                    .push(quote! { #ty: #lavan::parser::traits::FromParse<#input> });
+                self.value(ty, fail)
+            };
+
+            parsing.push(quote! {
+                #skip
+                let #ident = #value;
+            });
+            idents.push((field.ident.clone(), ident));
+        }
+
+        let construct = match fields {
+            Fields::Named(_) => {
+                let (names, idents): (Vec<_>, Vec<_>) = idents.into_iter().unzip();
+                quote! { { #(#names: #idents),* } }
+            }
+            Fields::Unnamed(_) => {
+                let idents = idents.into_iter().map(|(_, ident)| ident);
+                quote! { ( #(#idents),* ) }
+            }
+            Fields::Unit => quote! {},
+        };
+
+        Ok((quote! { #(#parsing)* }, construct))
+    }
+}
+
+/// Extracts `T` out of `Vec<T>`, as required by `sep_by`
+fn vec_item(ty: &Type) -> Result<&Type> {
+    if let Type::Path(path) = ty {
+        if let Some(segment) = path.path.segments.last() {
+            if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                if let (true, Some(GenericArgument::Type(item)), 1) =
+                    (segment.ident == "Vec", args.args.first(), args.args.len())
+                {
+                    return Ok(item);
+                }
+            }
+        }
+    }
+    Err(Error::new(
+        ty.span(),
+        "`sep_by` can only be used on `Vec` fields",
+    ))
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let lavan = lavan_crate();
+    let options = Options::parse(&input.attrs, Place::Container)?;
+    let input_ty = match &options.input {
+        Some(ty) => quote! { #ty },
+        None => quote! { INPUT },
+    };
+    let mut gen = Gen {
+        lavan: lavan.clone(),
+        input: input_ty.clone(),
+        uses_text: options.uses_text(),
+        uses_ws: false,
+        bounds: vec![],
+    };
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let fail = quote! { return ::core::option::Option::None };
+            let token = gen.token(&options.token, options.skip_ws, &fail);
+            let (fields, construct) = gen.fields(&data.fields, options.skip_ws, &fail)?;
+            quote! {
+                #token
+                #fields
+                ::core::option::Option::Some(Self #construct)
+            }
+        }
+        Data::Enum(data) => {
+            let fail = quote! { break 'variant };
+            let mut variants = vec![];
+
+            for variant in &data.variants {
+                let variant_options = Options::parse(&variant.attrs, Place::Variant)?;
+                let skip_ws = options.skip_ws || variant_options.skip_ws;
+                let token = gen.token(&variant_options.token, skip_ws, &fail);
+                let (fields, construct) = gen.fields(&variant.fields, skip_ws, &fail)?;
+                let ident = &variant.ident;
+                variants.push(quote! {
+                    let state = #lavan::input::traits::Stream::savestate(input);
+                    'variant: {
+                        #token
+                        #fields
+                        return ::core::option::Option::Some(Self::#ident #construct);
+                    }
+                    #lavan::input::traits::Stream::backtrack(input, state);
+                });
+            }
+
+            let token = gen.token(
+                &options.token,
+                options.skip_ws,
+                &quote! {
+                    return ::core::option::Option::None
+                },
+            );
+            quote! {
+                #token
+                #(#variants)*
+                ::core::option::Option::None
+            }
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span,
+                "FromParse cannot be derived for unions",
+            ))
+        }
+    };
+
+    let ident = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for bound in &gen.bounds {
+        where_clause.predicates.push(parse_quote! { #bound });
+    }
+
+    if options.input.is_none() {
+        generics
+            .params
+            .push(parse_quote! { INPUT: #lavan::input::traits::Stream });
+    }
+    if gen.uses_text || options.input.is_some() {
+        generics.params.insert(0, parse_quote! { 'lavan });
+    }
+    if gen.uses_text && options.input.is_none() {
+        generics.make_where_clause().predicates.push(parse_quote! {
+            INPUT: #lavan::input::traits::StreamSlice<Item = char, Slice = &'lavan str>
+        });
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let skip_ws = if gen.uses_ws {
+        quote! {
+            let skip_ws = |input: &mut #input_ty| loop {
+                let state = #lavan::input::traits::Stream::savestate(input);
+                match ::core::iter::Iterator::next(input) {
+                    ::core::option::Option::Some(c) if char::is_whitespace(c) => {}
+                    _ => {
+                        #lavan::input::traits::Stream::backtrack(input, state);
+                        break;
+                    }
+                }
+            };
+        }
+    } else {
+        quote! {}
+    };
+
+    Ok(quote! {
+        impl #impl_generics #lavan::parser::traits::FromParse<#input_ty> for #ident #ty_generics
+        #where_clause
+        {
+            type Output = ::core::option::Option<Self>;
+
+            #[allow(unreachable_code, clippy::never_loop)]
+            fn parse(input: &mut #input_ty) -> Self::Output {
+                #skip_ws
+                #body
+            }
+        }
+    })
+}
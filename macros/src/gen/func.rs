@@ -1,18 +1,33 @@
 use crate::prelude::*;
 use syn::{
-    AttrStyle, Generics, Ident, ItemFn, PatType, Path, ReturnType, Signature, Type, TypeParamBound,
-    WherePredicate,
+    spanned::Spanned, AttrStyle, Error, Generics, Ident, ItemFn, PatType, Path, Result, ReturnType,
+    Signature, Type, TypeParamBound, WherePredicate,
 };
 
-pub fn gen(attr: TokenStream, target: TokenStream) -> TokenStream {
+pub fn gen(attr: TokenStream, target: TokenStream) -> Result<TokenStream> {
+    let item = syn::parse::<ItemFn>(target)?;
+    expand(attr.into(), item).map(Into::into)
+}
+
+fn expand(attr: TokenStream2, item: ItemFn) -> Result<TokenStream2> {
     let ItemFn {
         attrs, sig, block, ..
-    } = parse_macro_input!(target as ItemFn);
+    } = item;
 
     let bound: TypeParamBound = trait_bound(lavan_path! { input::traits::Stream }).into();
 
-    let is_mut_in_move = "mut in move" == attr.to_string();
+    let is_mut_in_move = match attr.to_string().as_str() {
+        "" => false,
+        "mut in move" => true,
+        _ => {
+            return Err(Error::new_spanned(
+                attr,
+                "expected either no arguments or `mut in move`",
+            ))
+        }
+    };
 
+    let sig_span = sig.span();
     let Signature {
         //ident,
         mut generics,
@@ -25,29 +40,46 @@ pub fn gen(attr: TokenStream, target: TokenStream) -> TokenStream {
         generics.make_where_clause();
     }
 
-    let mut input_ty = input_ty_setup(&mut generics, &bound)
-        .expect("You can only have one generic input (full capitalized generic)");
+    let mut input_ty = input_ty_setup(&mut generics, &bound)?;
     let mut receiver = None;
 
     for ele in inputs {
         match ele {
-            syn::FnArg::Typed(pat) => input_ty = input_arg_setup(input_ty, pat),
+            syn::FnArg::Typed(pat) => input_ty = input_arg_setup(input_ty, pat)?,
             syn::FnArg::Receiver(r) => receiver = Some(r),
         }
     }
 
     let input_ty = input_ty.unwrap_or_else(|| default_input_ty(&mut generics, &bound));
-    let receiver = receiver.expect("Provide a Self type through a receiver argument");
+    let receiver = receiver.ok_or_else(|| {
+        Error::new(
+            sig_span,
+            "expected a receiver naming the parser type, as in `self: &MyParser`",
+        )
+    })?;
+
+    if receiver.colon_token.is_none() {
+        return Err(Error::new_spanned(
+            &receiver,
+            "the receiver must name the parser type, as in `self: &MyParser`",
+        ));
+    }
+    if is_mut_in_move && receiver.mutability.is_none() {
+        return Err(Error::new_spanned(
+            &receiver,
+            "`mut in move` requires a `mut self` receiver",
+        ));
+    }
 
     let receiver_mut_token = receiver.mutability.map(|_| quote![mut]).unwrap_or_default();
 
-    let mut self_ty = receiver
-        .colon_token
-        .map(|_| receiver.ty)
-        .expect("Self type not provided");
+    let mut self_ty = receiver.ty;
 
     let ReturnType::Type(_, output) = output else {
-        panic!("Provide a return type");
+        return Err(Error::new(
+            sig_span,
+            "expected a return type, being the output of the parser",
+        ));
     };
 
     let mut func_attrs = vec![];
@@ -74,6 +106,7 @@ pub fn gen(attr: TokenStream, target: TokenStream) -> TokenStream {
         }
     }
 
+    let lavan = lavan_crate();
     let traits = external::Traits::associated(input_ty.clone());
     let parser_trait_path_once: Path = traits.once_parser;
     let parser_trait_path_mut: Path = traits.mut_parser;
@@ -109,6 +142,11 @@ pub fn gen(attr: TokenStream, target: TokenStream) -> TokenStream {
             };
 
             let common_macros = quote! {
+                #[allow(unused_imports)]
+                use #lavan::{
+                    input::traits::Stream as _,
+                    parser::traits::{Parse as _, ParseMut as _, ParseOnce as _},
+                };
                 macro_rules! input {
                     () => (input)
                 }
@@ -172,11 +210,19 @@ pub fn gen(attr: TokenStream, target: TokenStream) -> TokenStream {
         }
     }
 
-    quote! { #once_impl #mut_impl #const_impl }.into()
+    Ok(quote! { #once_impl #mut_impl #const_impl })
 }
 
-fn input_ty_setup(generics: &mut Generics, bound: &TypeParamBound) -> Result<Option<Type>, ()> {
-    Ok(unique_upper_param(generics)?.map(|param| {
+fn input_ty_setup(generics: &mut Generics, bound: &TypeParamBound) -> Result<Option<Type>> {
+    let span = generics.span();
+    let param = unique_upper_param(generics).map_err(|()| {
+        Error::new(
+            span,
+            "expected at most one input type, being the only fully capitalized generic",
+        )
+    })?;
+
+    Ok(param.map(|param| {
         if param.bounds.is_empty() {
             param.bounds.push(bound.clone());
         }
@@ -195,17 +241,23 @@ fn default_input_ty(generics: &mut Generics, bound: &TypeParamBound) -> Type {
 }
 
 // TODO: PatType::attr
-fn input_arg_setup(input_ty: Option<Type>, PatType { pat, ty, .. }: PatType) -> Option<Type> {
-    if let syn::Pat::Ident(pat) = *pat {
-        if pat.ident.to_string() != "input" {
-            panic!("Function arguments are not supported with full capitalized generics.");
-        }
-        if input_ty.is_some() {
-            panic!("Function arguments are not supported with full capitalized generics.");
-        }
-        return Some(ty.as_ref().clone());
+fn input_arg_setup(input_ty: Option<Type>, arg: PatType) -> Result<Option<Type>> {
+    let syn::Pat::Ident(pat) = &*arg.pat else {
+        return Err(Error::new_spanned(arg.pat, "expected the `input` argument"));
+    };
+    if pat.ident != "input" {
+        return Err(Error::new_spanned(
+            &pat.ident,
+            "the only argument must be `input`, naming the input type",
+        ));
+    }
+    if input_ty.is_some() {
+        return Err(Error::new_spanned(
+            arg,
+            "the input type is already given by a fully capitalized generic",
+        ));
     }
-    input_ty
+    Ok(Some(*arg.ty))
 }
 
 fn fun_name2(mutability: &str, mim_suffix: TokenStream2) -> TokenStream2 {
@@ -0,0 +1,224 @@
+use crate::prelude::*;
+use syn::{
+    braced, parenthesized,
+    parse::{Parse, ParseStream},
+    Expr, Ident, LitChar, LitStr, Path, Result, Token, Type,
+};
+
+pub fn gen(target: TokenStream) -> TokenStream {
+    let grammar = parse_macro_input!(target as Grammar);
+    let lavan = lavan_crate();
+
+    // rules referred to before their own definition, including from within it,
+    // are declared upfront and defined in place, as are the ones with an output type
+    let mut declared: Vec<Ident> = grammar
+        .rules
+        .iter()
+        .filter(|rule| rule.ty.is_some())
+        .map(|rule| rule.name.clone())
+        .collect();
+    for (index, rule) in grammar.rules.iter().enumerate() {
+        for later in &grammar.rules[index..] {
+            if rule.body.refers_to(&later.name) && !declared.contains(&later.name) {
+                declared.push(later.name.clone());
+            }
+        }
+    }
+
+    let declarations = grammar
+        .rules
+        .iter()
+        .filter(|rule| declared.contains(&rule.name))
+        .map(|Rule { name, ty, .. }| match ty {
+            Some(ty) => quote! { let #name = #lavan::parser::sources::declare::<#ty, _>(); },
+            None => quote! { let #name = #lavan::parser::sources::declare(); },
+        });
+    let rules = grammar.rules.iter().map(|Rule { name, body, .. }| {
+        let body = body.expand(&lavan, &declared);
+        match declared.contains(name) {
+            true => quote! { #name.define(#body); },
+            false => quote! { let #name = #body; },
+        }
+    });
+
+    quote! { #(#declarations)* #(#rules)* }.into()
+}
+
+/// A sequence of `name = expression;` rules
+struct Grammar {
+    rules: Vec<Rule>,
+}
+
+/// A `name = expression;` rule, or `name: Output = expression;`
+struct Rule {
+    name: Ident,
+    ty: Option<Type>,
+    body: Node,
+}
+
+/// An expression of a rule, from the loosest binding to the tightest
+enum Node {
+    /// `a | b`
+    Or(Vec<Node>),
+    /// `a b`
+    And(Vec<Node>),
+    /// `$a`
+    Slice(Box<Node>),
+    /// `a*`
+    Repeat(Box<Node>),
+    /// `a+`
+    RepeatMin(Box<Node>),
+    /// `a?`
+    Opt(Box<Node>),
+    /// `"abc"`
+    Str(LitStr),
+    /// `'a'`
+    Char(LitChar),
+    /// `name`, either a rule or any parser in scope
+    Ref(Path),
+    /// `{ expr }`, any parser expression
+    Expr(Expr),
+}
+
+impl Parse for Grammar {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut rules = vec![];
+        while !input.is_empty() {
+            let name = input.parse()?;
+            let ty = match input.parse::<Option<Token![:]>>()? {
+                Some(_) => Some(input.parse()?),
+                None => None,
+            };
+            input.parse::<Token![=]>()?;
+            let body = Node::parse_or(input)?;
+            input.parse::<Token![;]>()?;
+            rules.push(Rule { name, ty, body });
+        }
+        Ok(Self { rules })
+    }
+}
+
+impl Node {
+    fn parse_or(input: ParseStream) -> Result<Self> {
+        let mut nodes = vec![Self::parse_and(input)?];
+        while input.parse::<Option<Token![|]>>()?.is_some() {
+            nodes.push(Self::parse_and(input)?);
+        }
+        Ok(Self::flatten(nodes, Self::Or))
+    }
+
+    fn parse_and(input: ParseStream) -> Result<Self> {
+        let mut nodes = vec![Self::parse_prefix(input)?];
+        while !(input.is_empty() || input.peek(Token![|]) || input.peek(Token![;])) {
+            nodes.push(Self::parse_prefix(input)?);
+        }
+        Ok(Self::flatten(nodes, Self::And))
+    }
+
+    fn parse_prefix(input: ParseStream) -> Result<Self> {
+        if input.parse::<Option<Token![$]>>()?.is_some() {
+            return Ok(Self::Slice(Box::new(Self::parse_prefix(input)?)));
+        }
+        Self::parse_postfix(input)
+    }
+
+    fn parse_postfix(input: ParseStream) -> Result<Self> {
+        let mut node = Self::parse_atom(input)?;
+        loop {
+            node = if input.parse::<Option<Token![*]>>()?.is_some() {
+                Self::Repeat(Box::new(node))
+            } else if input.parse::<Option<Token![+]>>()?.is_some() {
+                Self::RepeatMin(Box::new(node))
+            } else if input.parse::<Option<Token![?]>>()?.is_some() {
+                Self::Opt(Box::new(node))
+            } else {
+                return Ok(node);
+            };
+        }
+    }
+
+    fn parse_atom(input: ParseStream) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(LitStr) {
+            Ok(Self::Str(input.parse()?))
+        } else if lookahead.peek(LitChar) {
+            Ok(Self::Char(input.parse()?))
+        } else if lookahead.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            Self::parse_or(&content)
+        } else if lookahead.peek(syn::token::Brace) {
+            let content;
+            braced!(content in input);
+            Ok(Self::Expr(content.parse()?))
+        } else if lookahead.peek(Ident) || lookahead.peek(Token![::]) {
+            Ok(Self::Ref(input.parse()?))
+        } else {
+            Err(lookahead.error())
+        }
+    }
+
+    fn flatten(mut nodes: Vec<Self>, f: impl FnOnce(Vec<Self>) -> Self) -> Self {
+        if nodes.len() == 1 {
+            nodes.pop().unwrap()
+        } else {
+            f(nodes)
+        }
+    }
+
+    /// Whether this node refers to the rule `name`
+    fn refers_to(&self, name: &Ident) -> bool {
+        match self {
+            Self::Or(nodes) | Self::And(nodes) => nodes.iter().any(|node| node.refers_to(name)),
+            Self::Slice(node) | Self::Repeat(node) | Self::RepeatMin(node) | Self::Opt(node) => {
+                node.refers_to(name)
+            }
+            Self::Ref(path) => path.is_ident(name),
+            Self::Str(_) | Self::Char(_) | Self::Expr(_) => false,
+        }
+    }
+
+    /// Expands into combinators, referring to the `declared` rules by weak handles
+    fn expand(&self, lavan: &TokenStream2, declared: &[Ident]) -> TokenStream2 {
+        let traits = quote! { #lavan::parser::traits::ParseOnce };
+        let void = |node: &Node| {
+            let node = node.expand(lavan, declared);
+            quote! { #lavan::__private::void(#node) }
+        };
+
+        match self {
+            Self::Or(nodes) | Self::And(nodes) => {
+                let method = match self {
+                    Self::Or(_) => quote! { or },
+                    _ => quote! { and },
+                };
+                let mut nodes = nodes.iter().map(|node| node.expand(lavan, declared));
+                let first = nodes.next().unwrap();
+                nodes.fold(first, |acc, node| quote! { #traits::#method(#acc, #node) })
+            }
+            Self::Slice(node) => {
+                let node = void(node);
+                quote! { #traits::slice(#node) }
+            }
+            Self::Repeat(node) => {
+                let node = void(node);
+                quote! { #traits::repeat(#node) }
+            }
+            Self::RepeatMin(node) => {
+                let node = void(node);
+                quote! { #traits::repeat_min(#node, 1) }
+            }
+            Self::Opt(node) => {
+                let node = node.expand(lavan, declared);
+                quote! { #traits::opt(#node) }
+            }
+            Self::Str(lit) => quote! { #lit },
+            Self::Char(lit) => quote! { #lit },
+            Self::Ref(path) => match path.get_ident() {
+                Some(name) if declared.contains(name) => quote! { #name.weak() },
+                _ => quote! { #lavan::__private::by_ref(&#path) },
+            },
+            Self::Expr(expr) => quote! { (#expr) },
+        }
+    }
+}
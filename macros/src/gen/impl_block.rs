@@ -0,0 +1,173 @@
+use crate::prelude::*;
+use proc_macro2::{Delimiter, Group, TokenTree};
+use syn::{spanned::Spanned, Error, FnArg, ImplItem, ItemFn, ItemImpl, Pat, Result, Type};
+
+pub fn gen(attr: TokenStream, target: TokenStream) -> Result<TokenStream> {
+    let item = syn::parse::<ItemImpl>(target)?;
+    let is_mut_in_move = "mut in move" == attr.to_string();
+    let func = lower(item, is_mut_in_move)?;
+    crate::gen::func::gen(attr, func.into_token_stream().into())
+}
+
+/// Lowers the impl block into the function expected by `#[parser_fn]`
+fn lower(item: ItemImpl, is_mut_in_move: bool) -> Result<ItemFn> {
+    if let Some((_, path, _)) = &item.trait_ {
+        return Err(Error::new_spanned(
+            path,
+            "expected an inherent impl block, the parser traits are implemented for you",
+        ));
+    }
+
+    let mut input_ty = None;
+    let mut output_ty = None;
+    let mut func = None;
+
+    for impl_item in item.items {
+        match impl_item {
+            ImplItem::Type(ty) if ty.ident == "Input" && input_ty.is_none() => {
+                input_ty = Some(ty.ty)
+            }
+            ImplItem::Type(ty) if ty.ident == "Output" && output_ty.is_none() => {
+                output_ty = Some(ty.ty)
+            }
+            ImplItem::Fn(f) if func.is_none() => func = Some(f),
+            other => {
+                return Err(Error::new_spanned(
+                    other,
+                    "expected only `type Input`, `type Output` and a single parsing function",
+                ))
+            }
+        }
+    }
+
+    let span = item.self_ty.span();
+    let output_ty = output_ty.ok_or_else(|| Error::new(span, "missing `type Output`"))?;
+    let func = func.ok_or_else(|| Error::new(span, "missing the parsing function"))?;
+
+    if !func.sig.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &func.sig.generics,
+            "generics belong to the impl block",
+        ));
+    }
+
+    let mut args = func.sig.inputs.iter();
+    let receiver = match args.next() {
+        Some(FnArg::Receiver(receiver)) if receiver.colon_token.is_none() => receiver,
+        _ => {
+            return Err(Error::new_spanned(
+                &func.sig,
+                "the parsing function must take `self`, `&mut self` or `&self`",
+            ))
+        }
+    };
+    match args.next() {
+        Some(FnArg::Typed(arg)) if matches!(&*arg.pat, Pat::Ident(pat) if pat.ident == "input") => {
+        }
+        Some(arg) => {
+            return Err(Error::new_spanned(
+                arg,
+                "the input argument must be named `input`",
+            ))
+        }
+        None => {}
+    }
+    if let Some(arg) = args.next() {
+        return Err(Error::new_spanned(arg, "unexpected argument"));
+    }
+
+    let self_ty = &item.self_ty;
+    // the by-value parser is moved into a mutable binding to be parsed with by `&mut`
+    let receiver = match (&receiver.reference, &receiver.mutability) {
+        (Some(_), Some(_)) if is_mut_in_move => quote! { mut self: &mut #self_ty },
+        (Some(_), Some(_)) => quote! { self: &mut #self_ty },
+        (Some(_), None) => quote! { self: &#self_ty },
+        (None, Some(_)) => quote! { mut self: #self_ty },
+        (None, None) => quote! { self: #self_ty },
+    };
+
+    // an uppercase generic input is picked up by `#[parser_fn]` on its own
+    let generic_input = input_ty.as_ref().is_some_and(|ty| {
+        item.generics.type_params().any(|param| {
+            let ident = param.ident.to_string();
+            ident.len() > 2
+                && ident.chars().all(char::is_uppercase)
+                && matches!(ty, Type::Path(path) if path.path.is_ident(&param.ident))
+        })
+    });
+    let input_arg = match &input_ty {
+        Some(ty) if !generic_input => quote! { input: #ty },
+        _ => quote! {},
+    };
+    let input_ty = match input_ty {
+        Some(ty) => ty.into_token_stream(),
+        None => unique_input_ident(&item.generics),
+    };
+
+    let generics = &item.generics;
+    let where_clause = &item.generics.where_clause;
+    let attrs = item.attrs.iter().chain(
+        func.attrs
+            .iter()
+            .filter(|attr| !attr.path().is_ident("doc")),
+    );
+    let output_ty = replace_self_input(output_ty.into_token_stream(), &input_ty);
+    let block = replace_self_input(func.block.into_token_stream(), &input_ty);
+
+    syn::parse2(quote! {
+        #(#attrs)*
+        fn parser_impl #generics (#receiver, #input_arg) -> #output_ty
+        #where_clause
+        #block
+    })
+}
+
+/// The input type `#[parser_fn]` will settle upon without an explicit one
+fn unique_input_ident(generics: &syn::Generics) -> TokenStream2 {
+    generics
+        .type_params()
+        .map(|param| &param.ident)
+        .find(|ident| {
+            let ident = ident.to_string();
+            ident.len() > 2 && ident.chars().all(char::is_uppercase)
+        })
+        .map(ToTokens::into_token_stream)
+        .unwrap_or_else(|| quote! { INPUT })
+}
+
+/// Replaces `Self::Input`, which only exists within the impl block
+fn replace_self_input(ts: TokenStream2, input_ty: &TokenStream2) -> TokenStream2 {
+    let tokens: Vec<TokenTree> = ts.into_iter().collect();
+    let mut output = TokenStream2::new();
+    let mut idx = 0;
+
+    while idx < tokens.len() {
+        if let [TokenTree::Ident(this), TokenTree::Punct(p0), TokenTree::Punct(p1), TokenTree::Ident(name), ..] =
+            &tokens[idx..]
+        {
+            if this == "Self" && p0.as_char() == ':' && p1.as_char() == ':' && name == "Input" {
+                output.extend([TokenTree::Group(Group::new(
+                    Delimiter::None,
+                    input_ty.clone(),
+                ))]);
+                idx += 4;
+                continue;
+            }
+        }
+
+        output.extend([match &tokens[idx] {
+            TokenTree::Group(group) => {
+                let mut new = Group::new(
+                    group.delimiter(),
+                    replace_self_input(group.stream(), input_ty),
+                );
+                new.set_span(group.span());
+                TokenTree::Group(new)
+            }
+            tt => tt.clone(),
+        }]);
+        idx += 1;
+    }
+
+    output
+}
@@ -1,14 +1,19 @@
 use crate::prelude::*;
-use syn::{Generics, Ident, ItemFn, Type, TypeParamBound};
+use syn::{spanned::Spanned, Error, Generics, Ident, ItemFn, Result, Type, TypeParamBound};
 
-pub fn gen(_attr: TokenStream, target: TokenStream) -> TokenStream {
+pub fn gen(_attr: TokenStream, target: TokenStream) -> Result<TokenStream> {
+    let item = syn::parse::<ItemFn>(target)?;
+    expand(item).map(Into::into)
+}
+
+fn expand(item: ItemFn) -> Result<TokenStream2> {
     let ItemFn {
         attrs,
         vis,
         mut sig,
         block,
         ..
-    } = parse_macro_input!(target as ItemFn);
+    } = item;
 
     let mut inner_sig = sig.clone();
 
@@ -35,24 +40,37 @@ pub fn gen(_attr: TokenStream, target: TokenStream) -> TokenStream {
         _ => false,
     });
 
-    let receiver = receiver.expect("Provide a Self type through a receiver argument");
-
-    let mut self_ty = receiver
-        .colon_token
-        .map(|_| receiver.ty)
-        .expect("Self type not provided");
+    let receiver = receiver.ok_or_else(|| {
+        Error::new(
+            sig.span(),
+            "expected a receiver naming the parser type, as in `self: &MyParser`",
+        )
+    })?;
+
+    if receiver.colon_token.is_none() {
+        return Err(Error::new_spanned(
+            &receiver,
+            "the receiver must name the parser type, as in `self: &MyParser`",
+        ));
+    }
+    let mut self_ty = receiver.ty;
 
     if let Type::Reference(ty) = *self_ty.clone() {
         self_ty = ty.elem.clone();
     }
 
-    let input_ty = input_ty_setup(&mut sig.generics, &bound)
-        .expect("You can only have one generic input (full capitalized generic)");
+    let span = sig.generics.span();
+    let input_ty = input_ty_setup(&mut sig.generics, &bound).map_err(|()| {
+        Error::new(
+            span,
+            "expected at most one input type, being the only fully capitalized generic",
+        )
+    })?;
     let _ = input_ty.unwrap_or_else(|| default_input_ty(&mut sig.generics, &bound));
 
     sig.output = parse_quote!(-> #lavan_crate::parser::sources::adapters::Src<#self_ty, INPUT>);
 
-    quote! {
+    Ok(quote! {
 
         #vis #sig
         {
@@ -70,11 +88,13 @@ pub fn gen(_attr: TokenStream, target: TokenStream) -> TokenStream {
            #lavan_crate::parser::sources::functions::src(#block)
         }
 
-    }
-    .into()
+    })
 }
 
-fn input_ty_setup(generics: &mut Generics, bound: &TypeParamBound) -> Result<Option<Type>, ()> {
+fn input_ty_setup(
+    generics: &mut Generics,
+    bound: &TypeParamBound,
+) -> std::result::Result<Option<Type>, ()> {
     Ok(unique_upper_param(generics)?.map(|param| {
         if param.bounds.is_empty() {
             param.bounds.push(bound.clone());
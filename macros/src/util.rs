@@ -1,18 +1,10 @@
 use crate::prelude::*;
-use std::{result::Result, sync::OnceLock};
+use std::result::Result;
 use syn::{punctuated::*, *};
 
-const PRIMARY_CRATE_NAME: &str = "lavan";
-static IS_PRIMARY_CRATE: OnceLock<bool> = OnceLock::new();
-
+/// The path to `lavan`, which also names itself so within its own crate
 pub(crate) fn lavan_crate() -> TokenStream2 {
-    if *IS_PRIMARY_CRATE
-        .get_or_init(|| std::env::var("CARGO_PKG_NAME").unwrap() == PRIMARY_CRATE_NAME)
-    {
-        quote! { crate }
-    } else {
-        quote! { lavan }
-    }
+    quote! { ::lavan }
 }
 
 pub(crate) fn __private_lavan_path(ts: TokenStream2) -> TokenStream2 {
@@ -0,0 +1,184 @@
+//! Lossless concrete syntax trees
+//!
+//! Parsers wrapped by [`node`](crate::parser::traits::ParseOnce::node) record their
+//! spans into a [`CstBuilder`](crate::input::adapters::cst_builder::CstBuilder) stream,
+//! which then assembles them into a tree. The text between the nodes, such as
+//! whitespace and comments, is kept as tokens, so that the tree reproduces the
+//! original text exactly.
+//!
+//! The tree comes in two layers: [`GreenNode`]s only know their kind, length
+//! and children, so that they can be shared and compared regardless of where they
+//! occur, while [`SyntaxNode`]s add the offsets in the text on top of them.
+
+use std::fmt::{self, Display};
+use std::ops::Range;
+use std::rc::Rc;
+
+/// A node of the tree, independent from its location in the text
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GreenNode<Kind> {
+    kind: Kind,
+    len: usize,
+    children: Vec<GreenElement<Kind>>,
+}
+
+/// A child of a [`GreenNode`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GreenElement<Kind> {
+    Node(Rc<GreenNode<Kind>>),
+    /// Text outside of any node
+    Token(Rc<str>),
+}
+
+impl<Kind> GreenNode<Kind> {
+    pub fn new(kind: Kind, children: Vec<GreenElement<Kind>>) -> Self {
+        let len = children.iter().map(GreenElement::len).sum();
+        Self {
+            kind,
+            len,
+            children,
+        }
+    }
+
+    pub fn kind(&self) -> &Kind {
+        &self.kind
+    }
+
+    /// The length of the text in bytes
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn children(&self) -> &[GreenElement<Kind>] {
+        &self.children
+    }
+}
+
+impl<Kind> GreenElement<Kind> {
+    /// The length of the text in bytes
+    pub fn len(&self) -> usize {
+        match self {
+            GreenElement::Node(node) => node.len(),
+            GreenElement::Token(text) => text.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Writes the text of the node, as it was parsed
+impl<Kind> Display for GreenNode<Kind> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.children.iter().try_for_each(|child| child.fmt(f))
+    }
+}
+
+impl<Kind> Display for GreenElement<Kind> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GreenElement::Node(node) => node.fmt(f),
+            GreenElement::Token(text) => f.write_str(text),
+        }
+    }
+}
+
+/// A [`GreenNode`] located at a byte offset of the text
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SyntaxNode<Kind> {
+    green: Rc<GreenNode<Kind>>,
+    offset: usize,
+}
+
+/// A token located at a byte offset of the text
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SyntaxToken {
+    text: Rc<str>,
+    offset: usize,
+}
+
+/// A child of a [`SyntaxNode`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SyntaxElement<Kind> {
+    Node(SyntaxNode<Kind>),
+    Token(SyntaxToken),
+}
+
+impl<Kind> SyntaxNode<Kind> {
+    /// The root of a tree, starting the text
+    pub fn new_root(green: Rc<GreenNode<Kind>>) -> Self {
+        Self { green, offset: 0 }
+    }
+
+    pub fn green(&self) -> &Rc<GreenNode<Kind>> {
+        &self.green
+    }
+
+    pub fn kind(&self) -> &Kind {
+        self.green.kind()
+    }
+
+    /// The byte range of the node in the text
+    pub fn text_range(&self) -> Range<usize> {
+        self.offset..self.offset + self.green.len()
+    }
+
+    /// The text of the node, as it was parsed
+    pub fn text(&self) -> String {
+        self.green.to_string()
+    }
+
+    pub fn children(&self) -> impl Iterator<Item = SyntaxElement<Kind>> + '_ {
+        let mut offset = self.offset;
+        self.green.children().iter().map(move |child| {
+            let element = match child {
+                GreenElement::Node(node) => SyntaxElement::Node(SyntaxNode {
+                    green: node.clone(),
+                    offset,
+                }),
+                GreenElement::Token(text) => SyntaxElement::Token(SyntaxToken {
+                    text: text.clone(),
+                    offset,
+                }),
+            };
+            offset += child.len();
+            element
+        })
+    }
+
+    /// The child nodes, skipping tokens
+    pub fn child_nodes(&self) -> impl Iterator<Item = SyntaxNode<Kind>> + '_ {
+        self.children().filter_map(|child| match child {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        })
+    }
+}
+
+impl SyntaxToken {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The byte range of the token in the text
+    pub fn text_range(&self) -> Range<usize> {
+        self.offset..self.offset + self.text.len()
+    }
+}
+
+impl<Kind> Display for SyntaxNode<Kind> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.green.fmt(f)
+    }
+}
+
+impl Display for SyntaxToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
@@ -0,0 +1,194 @@
+use std::fmt::{Debug, Display};
+
+/// Something a parser was looking for when it failed
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Expected<Item> {
+    /// A specific token
+    Token(Item),
+    /// A human readable name, such as `"identifier"`
+    Label(&'static str),
+    /// The end of the input
+    EndOfInput,
+}
+
+impl<Item> Expected<Item> {
+    pub fn map<Fun, NewItem>(self, f: Fun) -> Expected<NewItem>
+    where
+        Fun: FnOnce(Item) -> NewItem,
+    {
+        match self {
+            Expected::Token(token) => Expected::Token(f(token)),
+            Expected::Label(label) => Expected::Label(label),
+            Expected::EndOfInput => Expected::EndOfInput,
+        }
+    }
+}
+
+impl<Item: Debug> Display for Expected<Item> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expected::Token(token) => write!(f, "{token:?}"),
+            Expected::Label(label) => write!(f, "{label}"),
+            Expected::EndOfInput => write!(f, "end of input"),
+        }
+    }
+}
+
+/// A structured parsing error, carrying where the parser failed,
+/// what it was expecting, and what it found instead.
+///
+/// A `found` of [None] means the parser hit the end of the input.
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// use lavan::prelude::*;
+///
+/// let error = ParseError::new((3, 4), Some('!'))
+///     .with_expected(Expected::Token(';'))
+///     .with_expected(Expected::Label("operator"));
+///
+/// assert_eq!(error.span(), &(3, 4));
+/// assert_eq!(error.to_string(), "expected ';' or operator, found '!'");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError<Item, Span> {
+    span: Span,
+    expected: Vec<Expected<Item>>,
+    found: Option<Item>,
+}
+
+impl<Item, Span> ParseError<Item, Span> {
+    pub fn new(span: Span, found: Option<Item>) -> Self {
+        Self {
+            span,
+            expected: Vec::new(),
+            found,
+        }
+    }
+
+    /// Adds `expected` to the expected-set, ignoring duplicates
+    pub fn with_expected(mut self, expected: Expected<Item>) -> Self
+    where
+        Item: PartialEq,
+    {
+        if !self.expected.contains(&expected) {
+            self.expected.push(expected);
+        }
+        self
+    }
+
+    /// Replaces the expected-set by a single human readable name
+    ///
+    /// # Examples
+    /// Basic usage:
+    /// ```
+    /// use lavan::prelude::*;
+    ///
+    /// let error = ParseError::new((0, 1), Some('?'))
+    ///     .with_expected(Expected::Token('_'))
+    ///     .with_expected(Expected::Label("letter"))
+    ///     .relabel("identifier");
+    ///
+    /// assert_eq!(error.to_string(), "expected identifier, found '?'");
+    /// ```
+    pub fn relabel(mut self, label: &'static str) -> Self {
+        self.expected = vec![Expected::Label(label)];
+        self
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    pub fn expected(&self) -> &[Expected<Item>] {
+        &self.expected
+    }
+
+    pub fn found(&self) -> Option<&Item> {
+        self.found.as_ref()
+    }
+
+    pub fn into_parts(self) -> (Span, Vec<Expected<Item>>, Option<Item>) {
+        (self.span, self.expected, self.found)
+    }
+
+    pub fn map_span<Fun, NewSpan>(self, f: Fun) -> ParseError<Item, NewSpan>
+    where
+        Fun: FnOnce(Span) -> NewSpan,
+    {
+        ParseError {
+            span: f(self.span),
+            expected: self.expected,
+            found: self.found,
+        }
+    }
+
+    /// Maps the expected and found items, as from the tokens of a stream to their kinds
+    pub fn map_items<Fun, NewItem>(self, mut f: Fun) -> ParseError<NewItem, Span>
+    where
+        Fun: FnMut(Item) -> NewItem,
+    {
+        ParseError {
+            span: self.span,
+            expected: self
+                .expected
+                .into_iter()
+                .map(|expected| expected.map(&mut f))
+                .collect(),
+            found: self.found.map(f),
+        }
+    }
+
+    /// Merges two errors of alternative branches.
+    ///
+    /// If both failed at the same span, the expected-sets are united.
+    /// Otherwise, the error that got further into the input is kept.
+    pub fn merge(self, other: Self) -> Self
+    where
+        Item: PartialEq,
+        Span: PartialOrd,
+    {
+        use std::cmp::Ordering::*;
+        match self.span.partial_cmp(&other.span) {
+            Some(Equal) => other.expected.into_iter().fold(self, Self::with_expected),
+            Some(Greater) => self,
+            Some(Less) | None => other,
+        }
+    }
+}
+
+/// An error expecting nothing and found at the end of the input, at the default span,
+/// such as the failure [left_rec](crate::parser::sources::left_rec) starts growing from.
+/// Merging it with the error of another branch at a further span keeps the latter.
+impl<Item, Span: Default> Default for ParseError<Item, Span> {
+    fn default() -> Self {
+        Self::new(Span::default(), None)
+    }
+}
+
+impl<Item: Debug, Span> Display for ParseError<Item, Span> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some((last, init)) = self.expected.split_last() {
+            write!(f, "expected ")?;
+            for (idx, expected) in init.iter().enumerate() {
+                if idx > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{expected}")?;
+            }
+            if !init.is_empty() {
+                write!(f, " or ")?;
+            }
+            write!(f, "{last}, found ")?;
+        } else {
+            write!(f, "unexpected ")?;
+        }
+        match &self.found {
+            Some(found) => write!(f, "{found:?}"),
+            None => write!(f, "end of input"),
+        }
+    }
+}
+
+impl<Item: Debug, Span: Debug> std::error::Error for ParseError<Item, Span> {}
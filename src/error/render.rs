@@ -0,0 +1,168 @@
+use super::parse_error::ParseError;
+use crate::input::adapters::line_cursor;
+use std::fmt::{Debug, Display};
+use std::ops::Range;
+
+/// A span that can be located in the source text, as a range of `char` offsets
+pub trait SourceSpan {
+    fn char_range(&self) -> Range<usize>;
+}
+
+impl SourceSpan for (usize, usize) {
+    fn char_range(&self) -> Range<usize> {
+        self.0..self.1
+    }
+}
+
+impl SourceSpan for Range<usize> {
+    fn char_range(&self) -> Range<usize> {
+        self.clone()
+    }
+}
+
+impl SourceSpan for line_cursor::Span {
+    fn char_range(&self) -> Range<usize> {
+        self.start.char..self.end.char
+    }
+}
+
+/// A line/column location in the source text, both starting from 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineCol {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl LineCol {
+    /// Locates the `char` offset `offset` in `source`
+    ///
+    /// # Examples
+    /// Basic usage:
+    /// ```
+    /// use lavan::error::render::LineCol;
+    ///
+    /// let source = "first\nsecond";
+    /// assert_eq!(LineCol::locate(source, 8), LineCol { line: 2, column: 3 });
+    /// ```
+    pub fn locate(source: &str, offset: usize) -> Self {
+        let mut location = LineCol { line: 1, column: 1 };
+        for c in source.chars().take(offset) {
+            if c == '\n' {
+                location.line += 1;
+                location.column = 1;
+            } else {
+                location.column += 1;
+            }
+        }
+        location
+    }
+}
+
+impl Display for LineCol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// A rustc-style report of a [ParseError] against the source text it came from
+///
+/// This `struct` is created by the [`ParseError::render`] method on [`ParseError`].
+/// See its documentation for more.
+#[derive(Debug, Clone, Copy)]
+pub struct Diagnostic<'a, Item, Span> {
+    error: &'a ParseError<Item, Span>,
+    source: &'a str,
+    name: Option<&'a str>,
+}
+
+impl<'a, Item, Span> Diagnostic<'a, Item, Span> {
+    /// Names the source in the report, usually after its file path
+    pub fn with_name(self, name: &'a str) -> Self {
+        Self {
+            name: Some(name),
+            ..self
+        }
+    }
+}
+
+impl<Item, Span> ParseError<Item, Span>
+where
+    Span: SourceSpan,
+{
+    /// Renders this error against the `source` it was produced from,
+    /// showing the offending line and underlining the span.
+    ///
+    /// # Examples
+    /// Basic usage:
+    /// ```
+    /// use lavan::prelude::*;
+    ///
+    /// let source = "width = 80\nheight 24";
+    /// let mut input = source.chars().cursor();
+    ///
+    /// // skip the first line
+    /// any_ne('\n').del().repeat().and('\n').parse_once(&mut input);
+    ///
+    /// let error = utf::identifier()
+    ///     .label("key")
+    ///     .and(any_eq(' ').expect(Expected::Token(' ')))
+    ///     .and(any_eq('=').expect(Expected::Token('=')))
+    ///     .parse_once(&mut input)
+    ///     .into_result()
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     error.render(source).with_name("main.conf").to_string(),
+    ///     "error: expected '=', found '2'\n \
+    ///      --> main.conf:2:8\n  \
+    ///       |\n\
+    ///      2 | height 24\n  \
+    ///       |        ^\n"
+    /// );
+    /// ```
+    pub fn render<'a>(&'a self, source: &'a str) -> Diagnostic<'a, Item, Span> {
+        Diagnostic {
+            error: self,
+            source,
+            name: None,
+        }
+    }
+}
+
+impl<Item, Span> Display for Diagnostic<'_, Item, Span>
+where
+    Item: Debug,
+    Span: SourceSpan,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let range = self.error.span().char_range();
+        let start = LineCol::locate(self.source, range.start);
+        let end = LineCol::locate(self.source, range.end.max(range.start));
+        let line = self.source.lines().nth(start.line - 1).unwrap_or_default();
+
+        // underline up to the end of the span, or of the line in case it spans many
+        let line_len = line.chars().count();
+        let underline_end = match end.line == start.line {
+            true => end.column,
+            false => line_len + 1,
+        };
+        let underline_len = underline_end.saturating_sub(start.column).max(1);
+
+        let number = start.line.to_string();
+        let gutter = " ".repeat(number.len());
+
+        writeln!(f, "error: {}", self.error)?;
+        match self.name {
+            Some(name) => writeln!(f, "{gutter}--> {name}:{start}")?,
+            None => writeln!(f, "{gutter}--> {start}")?,
+        }
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{number} | {line}")?;
+        writeln!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(start.column - 1),
+            "^".repeat(underline_len)
+        )
+    }
+}
@@ -0,0 +1,98 @@
+use super::{parse_error::ParseError, response::ParseResult};
+use crate::response::prelude::internal::*;
+
+/// A response that can be reported as a [ParseError]
+///
+/// Responses that already carry a [ParseError] hand it over,
+/// while the others only tell that they have failed.
+pub trait Report<Item, Span>: Response {
+    fn report(self) -> Result<Self::Value, Option<ParseError<Item, Span>>>;
+}
+
+/// An error that can be reported as a [ParseError], if it is one
+///
+/// Implementing it lets the parsers failing with this error, such as the ones
+/// built through [or_else](crate::parser::adapters::filter::Filter::or_else),
+/// be [labelled](crate::parser::traits::ParseOnce::label).
+/// Errors carrying no position are reported as `None`.
+///
+/// # Examples
+/// Basic usage:
+///```
+/// use lavan::prelude::*;
+///
+/// struct NotAKeyword;
+///
+/// impl<Item, Span> ReportError<Item, Span> for NotAKeyword {
+///     fn report_error(self) -> Option<ParseError<Item, Span>> {
+///         None
+///     }
+/// }
+///
+/// let mut input = "lit".chars().cursor();
+/// let error = take(3)
+///     .eq("let")
+///     .or_else(|| NotAKeyword)
+///     .label("keyword")
+///     .parse_once(&mut input)
+///     .into_result()
+///     .unwrap_err();
+/// assert_eq!(error.to_string(), "expected keyword, found 'l'");
+/// ```
+pub trait ReportError<Item, Span> {
+    fn report_error(self) -> Option<ParseError<Item, Span>>;
+}
+
+impl<Item, Span> ReportError<Item, Span> for ParseError<Item, Span> {
+    fn report_error(self) -> Option<ParseError<Item, Span>> {
+        Some(self)
+    }
+}
+
+impl<Item, Span> ReportError<Item, Span> for () {
+    fn report_error(self) -> Option<ParseError<Item, Span>> {
+        None
+    }
+}
+
+impl<Item, Span> ReportError<Item, Span> for std::convert::Infallible {
+    fn report_error(self) -> Option<ParseError<Item, Span>> {
+        match self {}
+    }
+}
+
+impl<Val, Item, Span> Report<Item, Span> for Option<Val> {
+    fn report(self) -> Result<Self::Value, Option<ParseError<Item, Span>>> {
+        self.ok_or(None)
+    }
+}
+
+impl<Item, Span> Report<Item, Span> for bool {
+    fn report(self) -> Result<Self::Value, Option<ParseError<Item, Span>>> {
+        self.then_some(()).ok_or(None)
+    }
+}
+
+impl<Val, Err, Item, Span> Report<Item, Span> for Result<Val, Err>
+where
+    Err: ReportError<Item, Span>,
+{
+    fn report(self) -> Result<Self::Value, Option<ParseError<Item, Span>>> {
+        self.map_err(Err::report_error)
+    }
+}
+
+impl<Err, Item, Span> Report<Item, Span> for Unsure<Err>
+where
+    Err: ReportError<Item, Span>,
+{
+    fn report(self) -> Result<Self::Value, Option<ParseError<Item, Span>>> {
+        self.into_result().map_err(Err::report_error)
+    }
+}
+
+impl<Val, Item, Span> Report<Item, Span> for ParseResult<Val, Item, Span> {
+    fn report(self) -> Result<Self::Value, Option<ParseError<Item, Span>>> {
+        self.into_result().map_err(Some)
+    }
+}
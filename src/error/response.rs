@@ -0,0 +1,244 @@
+use super::parse_error::ParseError;
+use crate::response::prelude::internal::*;
+
+/// A response carrying a [ParseError] in case of failure
+///
+/// Unlike [Result], alternating two failing [ParseResult]s through
+/// [or](crate::parser::traits::ParseOnce::or) merges both errors,
+/// as defined by [ParseError::merge].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseResult<T, Item, Span>(pub Result<T, ParseError<Item, Span>>);
+
+impl<T, Item, Span> ParseResult<T, Item, Span> {
+    pub fn ok(value: T) -> Self {
+        Self(Ok(value))
+    }
+
+    pub fn err(error: ParseError<Item, Span>) -> Self {
+        Self(Err(error))
+    }
+
+    pub fn into_result(self) -> Result<T, ParseError<Item, Span>> {
+        self.0
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.0.is_ok()
+    }
+
+    pub fn is_err(&self) -> bool {
+        self.0.is_err()
+    }
+}
+
+impl<T, Item, Span> From<Result<T, ParseError<Item, Span>>> for ParseResult<T, Item, Span> {
+    fn from(value: Result<T, ParseError<Item, Span>>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T, Item, Span> From<ParseResult<T, Item, Span>> for Result<T, ParseError<Item, Span>> {
+    fn from(value: ParseResult<T, Item, Span>) -> Self {
+        value.0
+    }
+}
+
+impl<T, Item, Span> Response for ParseResult<T, Item, Span> {
+    type Value = T;
+    type Error = ParseError<Item, Span>;
+    type Residual = Exception<ParseError<Item, Span>>;
+    type WithVal<Val> = ParseResult<Val, Item, Span>;
+    type WithErr<Err> = Result<T, Err>;
+
+    fn from_value(value: Self::Value) -> Self {
+        Self::ok(value)
+    }
+
+    fn from_error(error: Self::Error) -> Self {
+        Self::err(error)
+    }
+
+    fn control_flow(self) -> ControlFlow<Self::Error, Self::Value> {
+        self.into_result().control_flow()
+    }
+
+    fn map<Fun, Val>(self, f: Fun) -> Self::WithVal<Val>
+    where
+        Fun: FnOnce(Self::Value) -> Val,
+    {
+        ParseResult(self.into_result().map(f))
+    }
+
+    fn map_err<Fun, Err>(self, f: Fun) -> Self::WithErr<Err>
+    where
+        Fun: FnOnce(Self::Error) -> Err,
+    {
+        self.into_result().map_err(f)
+    }
+
+    fn flat_map<Fun, Val>(self, f: Fun) -> Self::WithVal<Val>
+    where
+        Fun: FnOnce(Self::Value) -> Self::WithVal<Val>,
+    {
+        match self.into_result() {
+            Ok(value) => f(value),
+            Err(error) => ParseResult::err(error),
+        }
+    }
+}
+
+impl<T, Item, Span> ValueResponse for ParseResult<T, Item, Span> {
+    type VoidVal = ParseResult<(), Item, Span>;
+
+    fn void_val(self) -> Self::VoidVal {
+        self.map(|_| ())
+    }
+
+    fn unwrap(self) -> Self::Value
+    where
+        Self::Error: std::fmt::Debug,
+    {
+        self.into_result().unwrap()
+    }
+}
+
+impl<T, Item, Span> ErrorResponse for ParseResult<T, Item, Span> {
+    type VoidErr = Option<T>;
+
+    fn void_err(self) -> Self::VoidErr {
+        self.into_result().ok()
+    }
+
+    fn unwrap_err(self) -> Self::Error
+    where
+        Self::Value: std::fmt::Debug,
+    {
+        self.into_result().unwrap_err()
+    }
+}
+
+impl<T, Item, Span> Attach for ParseResult<T, Item, Span> {
+    type Output<V> = ParseResult<V, Item, Span>;
+
+    fn attach_to_response<V>(self, value: impl FnOnce() -> V) -> Self::Output<V> {
+        self.map(|_| value())
+    }
+}
+
+impl<Val, Item, Span> Fallible for ParseResult<Val, Item, Span> {
+    type Infallible = Sure<Val>;
+    type Optional = Sure<Option<Val>>;
+
+    fn optional(self) -> Self::Optional {
+        Sure(self.into_result().ok())
+    }
+}
+
+impl<Val0, Val1, Item, Span, Fun> Apply<Fun> for ParseResult<Val0, Item, Span>
+where
+    Fun: Fn(Val0) -> ParseResult<Val1, Item, Span>,
+{
+    type Output = ParseResult<Val1, Item, Span>;
+
+    fn apply(self, f: &Fun) -> Self::Output {
+        self.flat_map(f)
+    }
+}
+
+impl<Val, Item, Span> Combine<()> for ParseResult<Val, Item, Span> {
+    type Output = Self;
+
+    fn combine<F>(self, f: F) -> Self::Output
+    where
+        F: FnOnce(),
+    {
+        self.map(|value| {
+            f();
+            value
+        })
+    }
+}
+
+impl<Val0, Val1, Item, Span> Combine<Sure<Val1>> for ParseResult<Val0, Item, Span> {
+    type Output = ParseResult<(Val0, Val1), Item, Span>;
+
+    fn combine<F>(self, f: F) -> Self::Output
+    where
+        F: FnOnce() -> Sure<Val1>,
+    {
+        self.map(|value| (value, f().value()))
+    }
+}
+
+impl<Val0, Val1, Item, Span> Combine<ParseResult<Val1, Item, Span>>
+    for ParseResult<Val0, Item, Span>
+{
+    type Output = ParseResult<(Val0, Val1), Item, Span>;
+
+    fn combine<F>(self, f: F) -> Self::Output
+    where
+        F: FnOnce() -> ParseResult<Val1, Item, Span>,
+    {
+        self.flat_map(|value0| f().map(|value1| (value0, value1)))
+    }
+}
+
+impl<Val0, Val1, Item, Span> Combine<ParseResult<Val1, Item, Span>> for Sure<Val0> {
+    type Output = ParseResult<(Val0, Val1), Item, Span>;
+
+    fn combine<F>(self, f: F) -> Self::Output
+    where
+        F: FnOnce() -> ParseResult<Val1, Item, Span>,
+    {
+        f().map(|value| (self.value(), value))
+    }
+}
+
+impl<Val, Item, Span> Switch<()> for ParseResult<Val, Item, Span> {
+    type Output = Sure<Option<Val>>;
+
+    fn switch<F>(self, f: F) -> Self::Output
+    where
+        F: FnOnce(),
+    {
+        match self.into_result() {
+            Ok(value) => Sure(Some(value)),
+            Err(_error) => {
+                f();
+                Sure(None)
+            }
+        }
+    }
+}
+
+impl<Val, Item, Span> Switch<Sure<Val>> for ParseResult<Val, Item, Span> {
+    type Output = Sure<Val>;
+
+    fn switch<F>(self, f: F) -> Self::Output
+    where
+        F: FnOnce() -> Sure<Val>,
+    {
+        match self.into_result() {
+            Ok(value) => Sure(value),
+            Err(_error) => f(),
+        }
+    }
+}
+
+impl<Val, Item, Span> Switch<ParseResult<Val, Item, Span>> for ParseResult<Val, Item, Span>
+where
+    Item: PartialEq,
+    Span: PartialOrd,
+{
+    type Output = Self;
+
+    fn switch<F>(self, f: F) -> Self::Output
+    where
+        F: FnOnce() -> Self,
+    {
+        match self.into_result() {
+            Ok(value) => ParseResult::ok(value),
+            Err(error0) => ParseResult(f().into_result().map_err(|error1| error0.merge(error1))),
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use crate::cst::{GreenElement, GreenNode, SyntaxNode};
+use crate::input::prelude::internal::*;
+use std::ops::Range;
+use std::rc::Rc;
+
+/// A `char` stream recording the nodes of a [concrete syntax tree](crate::cst)
+///
+/// Backtracking also discards the nodes recorded since the savestate,
+/// so only the nodes of the branches that were actually taken remain.
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// use lavan::prelude::*;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Kind {
+///     Let,
+///     Ident,
+///     Number,
+///     File,
+/// }
+///
+/// let ws = any_if(char::is_ascii_whitespace).del().repeat();
+/// let ident = any_if(char::is_ascii_alphabetic).del().repeat_min(1).node(Kind::Ident);
+/// let number = any_if(char::is_ascii_digit).del().repeat_min(1).node(Kind::Number);
+/// let binding = "let"
+///     .and(ws.as_ref())
+///     .and(ident)
+///     .and(ws.as_ref())
+///     .and('=')
+///     .and(ws.as_ref())
+///     .and(number)
+///     .and(';')
+///     .node(Kind::Let);
+/// let file = ws.as_ref().and(binding.as_ref()).and(ws.as_ref()).repeat();
+///
+/// let source = "let x = 1;\n  let yy  =  22;";
+/// let mut input = source.chars().cst_builder();
+/// file.parse(&mut input);
+/// let root = input.finish(Kind::File);
+///
+/// // the tree is lossless
+/// assert_eq!(root.text(), source);
+///
+/// let bindings: Vec<_> = root.child_nodes().collect();
+/// assert_eq!(bindings.len(), 2);
+/// assert_eq!(bindings[1].text(), "let yy  =  22;");
+/// assert_eq!(bindings[1].text_range(), 13..27);
+///
+/// let children: Vec<_> = bindings[1]
+///     .child_nodes()
+///     .map(|node| (*node.kind(), node.text()))
+///     .collect();
+/// assert_eq!(
+///     children,
+///     [(Kind::Ident, "yy".to_owned()), (Kind::Number, "22".to_owned())]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct CstBuilder<S, Kind>
+where
+    S: StreamSlice,
+{
+    pub scanner: S,
+    /// The byte offset, from where the builder started
+    pub offset: usize,
+    origin: S::SliceOffset,
+    depth: usize,
+    nodes: Vec<Recorded<Kind>>,
+}
+
+/// A node that has been parsed, before being assembled into the tree
+#[derive(Debug, Clone)]
+struct Recorded<Kind> {
+    kind: Kind,
+    range: Range<usize>,
+    depth: usize,
+}
+
+/// Where a node started, as returned by [`StreamNode::start_node`]
+#[derive(Debug, Clone, Copy)]
+pub struct NodeStart {
+    offset: usize,
+    len: usize,
+}
+
+impl<'a, S, Kind> CstBuilder<S, Kind>
+where
+    S: StreamSlice<Item = char, Slice = &'a str>,
+{
+    pub fn new(scanner: S) -> Self {
+        Self {
+            origin: scanner.slice_offset(),
+            scanner,
+            offset: 0,
+            depth: 0,
+            nodes: Vec::new(),
+        }
+    }
+
+    /// Assembles the nodes recorded so far into a tree, under a root node of `kind`
+    /// spanning all the text consumed
+    pub fn finish(self, kind: Kind) -> SyntaxNode<Kind> {
+        let text = self.scanner.slice_since(self.origin);
+        let mut stack: Vec<(usize, Range<usize>, GreenElement<Kind>)> = vec![];
+
+        for node in self.nodes {
+            let start = stack
+                .iter()
+                .rposition(|(depth, ..)| *depth <= node.depth)
+                .map_or(0, |idx| idx + 1);
+            let children = stack.drain(start..).map(|(_, range, child)| (range, child));
+            let green = assemble(text, node.kind, node.range.clone(), children);
+            stack.push((node.depth, node.range, GreenElement::Node(Rc::new(green))));
+        }
+
+        let children = stack.into_iter().map(|(_, range, child)| (range, child));
+        let root = assemble(text, kind, 0..text.len(), children);
+        SyntaxNode::new_root(Rc::new(root))
+    }
+}
+
+/// Builds a node out of its child nodes, filling the gaps between them with tokens
+fn assemble<Kind>(
+    text: &str,
+    kind: Kind,
+    range: Range<usize>,
+    children: impl Iterator<Item = (Range<usize>, GreenElement<Kind>)>,
+) -> GreenNode<Kind> {
+    let mut elements = vec![];
+    let mut offset = range.start;
+    let mut token = |elements: &mut Vec<_>, start: usize, end: usize| {
+        if start < end {
+            elements.push(GreenElement::Token(text[start..end].into()));
+        }
+    };
+
+    for (child_range, child) in children {
+        token(&mut elements, offset, child_range.start);
+        elements.push(child);
+        offset = child_range.end;
+    }
+    token(&mut elements, offset, range.end);
+
+    GreenNode::new(kind, elements)
+}
+
+impl<S, Kind> Iterator for CstBuilder<S, Kind>
+where
+    S: StreamSlice<Item = char>,
+{
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = self.scanner.next()?;
+        self.offset += c.len_utf8();
+        Some(c)
+    }
+}
+
+impl<S, Kind> Stream for CstBuilder<S, Kind>
+where
+    S: StreamSlice<Item = char>,
+{
+    type SaveState = (S::SaveState, usize, usize);
+
+    fn savestate(&mut self) -> Self::SaveState {
+        (self.scanner.savestate(), self.offset, self.nodes.len())
+    }
+
+    fn backtrack(&mut self, (state, offset, len): Self::SaveState) {
+        self.scanner.backtrack(state);
+        self.offset = offset;
+        self.nodes.truncate(len);
+    }
+
+    fn demand(&mut self, count: usize) {
+        self.scanner.demand(count)
+    }
+}
+
+impl<S, Kind> StreamNode for CstBuilder<S, Kind>
+where
+    S: StreamSlice<Item = char>,
+{
+    type Kind = Kind;
+    type NodeStart = NodeStart;
+
+    fn start_node(&mut self) -> Self::NodeStart {
+        self.depth += 1;
+        NodeStart {
+            offset: self.offset,
+            len: self.nodes.len(),
+        }
+    }
+
+    fn finish_node(&mut self, start: Self::NodeStart, kind: Option<Self::Kind>) {
+        self.depth -= 1;
+        match kind {
+            Some(kind) => self.nodes.push(Recorded {
+                kind,
+                range: start.offset..self.offset,
+                depth: self.depth,
+            }),
+            None => self.nodes.truncate(start.len),
+        }
+    }
+}
+
+impl<S, Kind> StreamCut for CstBuilder<S, Kind>
+where
+    S: StreamSlice<Item = char> + StreamCut,
+{
+    fn cut(&mut self) {
+        self.scanner.cut()
+    }
+}
+
+impl<S, Kind> StreamOffset for CstBuilder<S, Kind>
+where
+    S: StreamSlice<Item = char>,
+{
+    fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<S, Kind> StreamSlice for CstBuilder<S, Kind>
+where
+    S: StreamSlice<Item = char>,
+{
+    type Slice = S::Slice;
+    type SliceOffset = S::SliceOffset;
+
+    fn slice_offset(&self) -> Self::SliceOffset {
+        self.scanner.slice_offset()
+    }
+
+    fn slice_since(&self, start: Self::SliceOffset) -> Self::Slice {
+        self.scanner.slice_since(start)
+    }
+}
+
+impl<S, Kind> StreamSpan for CstBuilder<S, Kind>
+where
+    S: StreamSlice<Item = char>,
+{
+    type Span = Range<usize>;
+    type SpanOffset = usize;
+
+    fn span_offset(&self) -> Self::SpanOffset {
+        self.offset
+    }
+
+    fn span_since(&self, start: Self::SpanOffset) -> Self::Span {
+        start..self.offset
+    }
+}
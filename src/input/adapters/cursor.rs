@@ -20,8 +20,9 @@ where
     type Item = S::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
+        let item = self.scanner.next()?;
         self.count += 1;
-        self.scanner.next()
+        Some(item)
     }
 }
 
@@ -29,14 +30,37 @@ impl<S> Stream for Cursor<S>
 where
     S: Stream,
 {
-    type SaveState = S::SaveState;
+    type SaveState = (S::SaveState, usize);
 
     fn savestate(&mut self) -> Self::SaveState {
-        self.scanner.savestate()
+        (self.scanner.savestate(), self.count)
     }
 
-    fn backtrack(&mut self, state: Self::SaveState) {
-        self.scanner.backtrack(state)
+    fn backtrack(&mut self, (state, count): Self::SaveState) {
+        self.scanner.backtrack(state);
+        self.count = count;
+    }
+
+    fn demand(&mut self, count: usize) {
+        self.scanner.demand(count)
+    }
+}
+
+impl<S> StreamCut for Cursor<S>
+where
+    S: StreamCut,
+{
+    fn cut(&mut self) {
+        self.scanner.cut()
+    }
+}
+
+impl<S> StreamOffset for Cursor<S>
+where
+    S: Stream,
+{
+    fn offset(&self) -> usize {
+        self.count
     }
 }
 
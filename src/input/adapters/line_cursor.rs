@@ -0,0 +1,174 @@
+use crate::input::prelude::internal::*;
+
+/// A location in a `char` stream, with line and column starting from 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub byte: usize,
+    pub char: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    /// The position of the first `char` of a stream
+    pub const START: Self = Self {
+        byte: 0,
+        char: 0,
+        line: 1,
+        column: 1,
+    };
+
+    /// Moves past `c`, going to the next line after a `'\n'`
+    pub fn advance(&mut self, c: char) {
+        self.byte += c.len_utf8();
+        self.char += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+    }
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Self::START
+    }
+}
+
+/// A span between two [Position]s of a `char` stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// A `char` stream tracking the byte offset, `char` offset, line and column
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// use lavan::input::adapters::line_cursor::Position;
+/// use lavan::prelude::*;
+///
+/// let mut input = "let\n  x".chars().line_cursor();
+/// let (_, (_, span)) = utf::identifier()
+///     .and(any_eq('\n').del())
+///     .and(any_eq(' ').del().repeat())
+///     .and(utf::identifier().spanned())
+///     .parse_once(&mut input)
+///     .unwrap();
+///
+/// assert_eq!((span.start.line, span.start.column), (2, 3));
+/// assert_eq!((span.start.byte, span.end.byte), (6, 7));
+///
+/// // the position is restored when backtracking
+/// let mut input = "a\nb".chars().line_cursor();
+/// let state = input.savestate();
+/// assert_eq!(input.nth(2), Some('b'));
+/// assert_eq!(input.position.line, 2);
+/// input.backtrack(state);
+/// assert_eq!(input.position, Position::START);
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct LineCursor<S> {
+    pub scanner: S,
+    pub position: Position,
+}
+
+impl<S> LineCursor<S> {
+    pub fn new(scanner: S) -> Self {
+        Self {
+            scanner,
+            position: Position::START,
+        }
+    }
+}
+
+impl<S> Iterator for LineCursor<S>
+where
+    S: Iterator<Item = char>,
+{
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.scanner.next()?;
+        self.position.advance(item);
+        Some(item)
+    }
+}
+
+impl<S> Stream for LineCursor<S>
+where
+    S: Stream<Item = char>,
+{
+    type SaveState = (S::SaveState, Position);
+
+    fn savestate(&mut self) -> Self::SaveState {
+        (self.scanner.savestate(), self.position)
+    }
+
+    fn backtrack(&mut self, (state, position): Self::SaveState) {
+        self.scanner.backtrack(state);
+        self.position = position;
+    }
+
+    fn demand(&mut self, count: usize) {
+        self.scanner.demand(count)
+    }
+}
+
+impl<S> StreamCut for LineCursor<S>
+where
+    S: StreamCut<Item = char>,
+{
+    fn cut(&mut self) {
+        self.scanner.cut()
+    }
+}
+
+impl<S> StreamOffset for LineCursor<S>
+where
+    S: Stream<Item = char>,
+{
+    fn offset(&self) -> usize {
+        self.position.char
+    }
+}
+
+impl<S> StreamSlice for LineCursor<S>
+where
+    S: StreamSlice<Item = char>,
+{
+    type Slice = S::Slice;
+    type SliceOffset = S::SliceOffset;
+
+    fn slice_offset(&self) -> Self::SliceOffset {
+        self.scanner.slice_offset()
+    }
+
+    fn slice_since(&self, start: Self::SliceOffset) -> Self::Slice {
+        self.scanner.slice_since(start)
+    }
+}
+
+impl<S> StreamSpan for LineCursor<S>
+where
+    S: Stream<Item = char>,
+{
+    type Span = Span;
+    type SpanOffset = Position;
+
+    fn span_offset(&self) -> Self::SpanOffset {
+        self.position
+    }
+
+    fn span_since(&self, start: Self::SpanOffset) -> Self::Span {
+        Span {
+            start,
+            end: self.position,
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::input::prelude::internal::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A stream telling apart the runs of [memoized](crate::parser::traits::ParseOnce::memoize)
+/// parsers, so that their outputs are only reused within the same stream
+///
+/// Every stream gets its own identity, clones included.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Memoizing<S> {
+    pub scanner: S,
+    id: usize,
+}
+
+impl<S> Memoizing<S> {
+    pub fn new(scanner: S) -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        Self {
+            scanner,
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+}
+
+impl<S> Clone for Memoizing<S>
+where
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self::new(self.scanner.clone())
+    }
+}
+
+impl<S> Iterator for Memoizing<S>
+where
+    S: Iterator,
+{
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.scanner.next()
+    }
+}
+
+impl<S> Stream for Memoizing<S>
+where
+    S: Stream,
+{
+    type SaveState = S::SaveState;
+
+    fn savestate(&mut self) -> Self::SaveState {
+        self.scanner.savestate()
+    }
+
+    fn backtrack(&mut self, state: Self::SaveState) {
+        self.scanner.backtrack(state)
+    }
+
+    fn demand(&mut self, count: usize) {
+        self.scanner.demand(count)
+    }
+}
+
+impl<S> StreamMemo for Memoizing<S>
+where
+    S: StreamOffset,
+{
+    fn memo_id(&self) -> usize {
+        self.id
+    }
+}
+
+impl<S> StreamCut for Memoizing<S>
+where
+    S: StreamCut,
+{
+    fn cut(&mut self) {
+        self.scanner.cut()
+    }
+}
+
+impl<S> StreamOffset for Memoizing<S>
+where
+    S: StreamOffset,
+{
+    fn offset(&self) -> usize {
+        self.scanner.offset()
+    }
+}
+
+impl<S> StreamRecover for Memoizing<S>
+where
+    S: StreamRecover,
+{
+    type Error = S::Error;
+
+    fn push_error(&mut self, error: Self::Error) {
+        self.scanner.push_error(error)
+    }
+}
+
+impl<S> StreamSlice for Memoizing<S>
+where
+    S: StreamSlice,
+{
+    type Slice = S::Slice;
+    type SliceOffset = S::SliceOffset;
+
+    fn slice_offset(&self) -> Self::SliceOffset {
+        self.scanner.slice_offset()
+    }
+
+    fn slice_since(&self, start: Self::SliceOffset) -> Self::Slice {
+        self.scanner.slice_since(start)
+    }
+}
+
+impl<S> StreamSpan for Memoizing<S>
+where
+    S: StreamSpan,
+{
+    type Span = S::Span;
+    type SpanOffset = S::SpanOffset;
+
+    fn span_offset(&self) -> Self::SpanOffset {
+        self.scanner.span_offset()
+    }
+
+    fn span_since(&self, start: Self::SpanOffset) -> Self::Span {
+        self.scanner.span_since(start)
+    }
+}
+
+impl<S> StreamTrim for Memoizing<S>
+where
+    S: StreamTrim,
+{
+    fn trim(&mut self) {
+        self.scanner.trim()
+    }
+}
@@ -0,0 +1,121 @@
+use crate::input::prelude::internal::*;
+
+/// A stream accumulating the errors recovered while parsing
+///
+/// Backtracking also discards the errors recovered since the savestate,
+/// so only the errors of the branches that were actually taken remain.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Recovering<S, E> {
+    pub scanner: S,
+    pub errors: Vec<E>,
+}
+
+impl<S, E> Recovering<S, E> {
+    pub fn new(scanner: S) -> Self {
+        Self {
+            scanner,
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn errors(&self) -> &[E] {
+        &self.errors
+    }
+
+    pub fn into_errors(self) -> Vec<E> {
+        self.errors
+    }
+}
+
+impl<S, E> Iterator for Recovering<S, E>
+where
+    S: Iterator,
+{
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.scanner.next()
+    }
+}
+
+impl<S, E> Stream for Recovering<S, E>
+where
+    S: Stream,
+{
+    type SaveState = (S::SaveState, usize);
+
+    fn savestate(&mut self) -> Self::SaveState {
+        (self.scanner.savestate(), self.errors.len())
+    }
+
+    fn backtrack(&mut self, (state, len): Self::SaveState) {
+        self.scanner.backtrack(state);
+        self.errors.truncate(len);
+    }
+
+    fn demand(&mut self, count: usize) {
+        self.scanner.demand(count)
+    }
+}
+
+impl<S, E> StreamRecover for Recovering<S, E>
+where
+    S: Stream,
+{
+    type Error = E;
+
+    fn push_error(&mut self, error: Self::Error) {
+        self.errors.push(error)
+    }
+}
+
+impl<S, E> StreamCut for Recovering<S, E>
+where
+    S: StreamCut,
+{
+    fn cut(&mut self) {
+        self.scanner.cut()
+    }
+}
+
+impl<S, E> StreamOffset for Recovering<S, E>
+where
+    S: StreamOffset,
+{
+    fn offset(&self) -> usize {
+        self.scanner.offset()
+    }
+}
+
+impl<S, E> StreamSlice for Recovering<S, E>
+where
+    S: StreamSlice,
+{
+    type Slice = S::Slice;
+    type SliceOffset = S::SliceOffset;
+
+    fn slice_offset(&self) -> Self::SliceOffset {
+        self.scanner.slice_offset()
+    }
+
+    fn slice_since(&self, start: Self::SliceOffset) -> Self::Slice {
+        self.scanner.slice_since(start)
+    }
+}
+
+impl<S, E> StreamSpan for Recovering<S, E>
+where
+    S: StreamSpan,
+{
+    type Span = S::Span;
+    type SpanOffset = S::SpanOffset;
+
+    fn span_offset(&self) -> Self::SpanOffset {
+        self.scanner.span_offset()
+    }
+
+    fn span_since(&self, start: Self::SpanOffset) -> Self::Span {
+        self.scanner.span_since(start)
+    }
+}
@@ -0,0 +1,162 @@
+use crate::input::prelude::internal::*;
+
+/// A stream logging its backtracks to the [trace](crate::trace) being recorded
+///
+/// Backtracks are located by the [offsets](StreamOffset) of the wrapped stream,
+/// whose spans and other capabilities are kept as they are.
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// use lavan::prelude::*;
+/// use lavan::trace::{self, Event};
+///
+/// let word = any_if(char::is_ascii_alphabetic).del().repeat_min(1).slice().trace("word");
+/// let mut input = "\nhi".chars().line_cursor().traced();
+/// assert_eq!(any_eq('\n').parse(&mut input), Some('\n'));
+///
+/// let (output, trace) = trace::record(|| word.parse(&mut input));
+/// assert_eq!(output, Some("hi"));
+/// let Event::Invocation(word) = &trace.events[0] else {
+///     panic!("expected an invocation");
+/// };
+/// assert!(word.span.contains("line: 2, column: 1"));
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Traced<S> {
+    pub scanner: S,
+}
+
+impl<S> Traced<S> {
+    pub fn new(scanner: S) -> Self {
+        Self { scanner }
+    }
+}
+
+impl<S> Iterator for Traced<S>
+where
+    S: Iterator,
+{
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.scanner.next()
+    }
+}
+
+impl<S> Stream for Traced<S>
+where
+    S: StreamOffset,
+{
+    type SaveState = S::SaveState;
+
+    fn savestate(&mut self) -> Self::SaveState {
+        self.scanner.savestate()
+    }
+
+    fn backtrack(&mut self, state: Self::SaveState) {
+        let from = self.scanner.offset();
+        self.scanner.backtrack(state);
+        crate::trace::backtrack(from, self.scanner.offset());
+    }
+
+    fn demand(&mut self, count: usize) {
+        self.scanner.demand(count)
+    }
+}
+
+impl<S> StreamCut for Traced<S>
+where
+    S: StreamCut + StreamOffset,
+{
+    fn cut(&mut self) {
+        self.scanner.cut()
+    }
+}
+
+impl<S> StreamMemo for Traced<S>
+where
+    S: StreamMemo,
+{
+    fn memo_id(&self) -> usize {
+        self.scanner.memo_id()
+    }
+}
+
+impl<S> StreamNode for Traced<S>
+where
+    S: StreamNode + StreamOffset,
+{
+    type Kind = S::Kind;
+    type NodeStart = S::NodeStart;
+
+    fn start_node(&mut self) -> Self::NodeStart {
+        self.scanner.start_node()
+    }
+
+    fn finish_node(&mut self, start: Self::NodeStart, kind: Option<Self::Kind>) {
+        self.scanner.finish_node(start, kind)
+    }
+}
+
+impl<S> StreamOffset for Traced<S>
+where
+    S: StreamOffset,
+{
+    fn offset(&self) -> usize {
+        self.scanner.offset()
+    }
+}
+
+impl<S> StreamRecover for Traced<S>
+where
+    S: StreamRecover + StreamOffset,
+{
+    type Error = S::Error;
+
+    fn push_error(&mut self, error: Self::Error) {
+        self.scanner.push_error(error)
+    }
+}
+
+impl<S> StreamSlice for Traced<S>
+where
+    S: StreamSlice + StreamOffset,
+{
+    type Slice = S::Slice;
+    type SliceOffset = S::SliceOffset;
+
+    fn slice_offset(&self) -> Self::SliceOffset {
+        self.scanner.slice_offset()
+    }
+
+    fn slice_since(&self, start: Self::SliceOffset) -> Self::Slice {
+        self.scanner.slice_since(start)
+    }
+}
+
+impl<S> StreamSpan for Traced<S>
+where
+    S: StreamSpan + StreamOffset,
+{
+    type Span = S::Span;
+    type SpanOffset = S::SpanOffset;
+
+    fn span_offset(&self) -> Self::SpanOffset {
+        self.scanner.span_offset()
+    }
+
+    fn span_since(&self, start: Self::SpanOffset) -> Self::Span {
+        self.scanner.span_since(start)
+    }
+}
+
+impl<S> StreamTrim for Traced<S>
+where
+    S: StreamTrim + StreamOffset,
+{
+    fn trim(&mut self) {
+        self.scanner.trim()
+    }
+}
@@ -0,0 +1,277 @@
+use crate::input::prelude::internal::*;
+
+/// What is skipped by [`StreamTrim::trim`] on a [`Trimming`] stream
+///
+/// The default skips whitespace, `//` line comments and nested `/* */` block comments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Trivia {
+    pub whitespace: bool,
+    pub line_comment: Option<&'static str>,
+    pub block_comment: Option<(&'static str, &'static str)>,
+    pub nested: bool,
+}
+
+impl Trivia {
+    /// Skips nothing
+    pub const NONE: Self = Self {
+        whitespace: false,
+        line_comment: None,
+        block_comment: None,
+        nested: false,
+    };
+
+    /// Skips whitespace only
+    pub const fn whitespace() -> Self {
+        Self {
+            whitespace: true,
+            ..Self::NONE
+        }
+    }
+
+    /// Also skips from `start` to the end of the line
+    pub const fn line_comments(self, start: &'static str) -> Self {
+        Self {
+            line_comment: Some(start),
+            ..self
+        }
+    }
+
+    /// Also skips from `open` to the first `close`
+    pub const fn block_comments(self, open: &'static str, close: &'static str) -> Self {
+        Self {
+            block_comment: Some((open, close)),
+            nested: false,
+            ..self
+        }
+    }
+
+    /// Also skips from `open` to the matching `close`, so that block comments can nest
+    pub const fn nested_block_comments(self, open: &'static str, close: &'static str) -> Self {
+        Self {
+            block_comment: Some((open, close)),
+            nested: true,
+            ..self
+        }
+    }
+}
+
+impl Default for Trivia {
+    fn default() -> Self {
+        Self::whitespace()
+            .line_comments("//")
+            .nested_block_comments("/*", "*/")
+    }
+}
+
+/// A `char` stream skipping [`Trivia`] whenever it is [trimmed](StreamTrim::trim)
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// use lavan::input::adapters::trimming::Trivia;
+/// use lavan::prelude::*;
+///
+/// let mut input = "  // comment\n /* outer /* inner */ */ x".chars().trimming(Trivia::default());
+/// input.trim();
+/// assert_eq!(input.next(), Some('x'));
+///
+/// // an unterminated block comment is left for the parser to fail upon
+/// let mut input = "/* x".chars().trimming(Trivia::default());
+/// input.trim();
+/// assert_eq!(input.next(), Some('/'));
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Trimming<S> {
+    pub scanner: S,
+    pub trivia: Trivia,
+}
+
+impl<S> Trimming<S> {
+    pub fn new(scanner: S, trivia: Trivia) -> Self {
+        Self { scanner, trivia }
+    }
+}
+
+impl<S> Trimming<S>
+where
+    S: Stream<Item = char>,
+{
+    /// Consumes `word` if the stream starts with it
+    fn eat(&mut self, word: &str) -> bool {
+        let state = self.scanner.savestate();
+        if word.chars().all(|c| self.scanner.next() == Some(c)) {
+            return true;
+        }
+        self.scanner.backtrack(state);
+        false
+    }
+
+    fn eat_whitespace(&mut self) -> bool {
+        let state = self.scanner.savestate();
+        if self.scanner.next().is_some_and(char::is_whitespace) {
+            return true;
+        }
+        self.scanner.backtrack(state);
+        false
+    }
+
+    fn eat_line_comment(&mut self, start: &str) -> bool {
+        if !self.eat(start) {
+            return false;
+        }
+        while self.scanner.next().is_some_and(|c| c != '\n') {}
+        true
+    }
+
+    fn eat_block_comment(&mut self, open: &str, close: &str) -> bool {
+        let state = self.scanner.savestate();
+        if !self.eat(open) {
+            return false;
+        }
+
+        let mut depth = 1usize;
+        while depth > 0 {
+            if self.trivia.nested && self.eat(open) {
+                depth += 1;
+            } else if self.eat(close) {
+                depth -= 1;
+            } else if self.scanner.next().is_none() {
+                self.scanner.backtrack(state);
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl<S> Iterator for Trimming<S>
+where
+    S: Iterator,
+{
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.scanner.next()
+    }
+}
+
+impl<S> Stream for Trimming<S>
+where
+    S: Stream,
+{
+    type SaveState = S::SaveState;
+
+    fn savestate(&mut self) -> Self::SaveState {
+        self.scanner.savestate()
+    }
+
+    fn backtrack(&mut self, state: Self::SaveState) {
+        self.scanner.backtrack(state)
+    }
+
+    fn demand(&mut self, count: usize) {
+        self.scanner.demand(count)
+    }
+}
+
+impl<S> StreamTrim for Trimming<S>
+where
+    S: Stream<Item = char>,
+{
+    fn trim(&mut self) {
+        let Trivia {
+            whitespace,
+            line_comment,
+            block_comment,
+            ..
+        } = self.trivia;
+
+        loop {
+            let skipped = (whitespace && self.eat_whitespace())
+                || line_comment.is_some_and(|start| self.eat_line_comment(start))
+                || block_comment.is_some_and(|(open, close)| self.eat_block_comment(open, close));
+            if !skipped {
+                break;
+            }
+        }
+    }
+}
+
+impl<S> StreamCut for Trimming<S>
+where
+    S: StreamCut,
+{
+    fn cut(&mut self) {
+        self.scanner.cut()
+    }
+}
+
+impl<S> StreamNode for Trimming<S>
+where
+    S: StreamNode,
+{
+    type Kind = S::Kind;
+    type NodeStart = S::NodeStart;
+
+    fn start_node(&mut self) -> Self::NodeStart {
+        self.scanner.start_node()
+    }
+
+    fn finish_node(&mut self, start: Self::NodeStart, kind: Option<Self::Kind>) {
+        self.scanner.finish_node(start, kind)
+    }
+}
+
+impl<S> StreamOffset for Trimming<S>
+where
+    S: StreamOffset,
+{
+    fn offset(&self) -> usize {
+        self.scanner.offset()
+    }
+}
+
+impl<S> StreamRecover for Trimming<S>
+where
+    S: StreamRecover,
+{
+    type Error = S::Error;
+
+    fn push_error(&mut self, error: Self::Error) {
+        self.scanner.push_error(error)
+    }
+}
+
+impl<S> StreamSlice for Trimming<S>
+where
+    S: StreamSlice,
+{
+    type Slice = S::Slice;
+    type SliceOffset = S::SliceOffset;
+
+    fn slice_offset(&self) -> Self::SliceOffset {
+        self.scanner.slice_offset()
+    }
+
+    fn slice_since(&self, start: Self::SliceOffset) -> Self::Slice {
+        self.scanner.slice_since(start)
+    }
+}
+
+impl<S> StreamSpan for Trimming<S>
+where
+    S: StreamSpan,
+{
+    type Span = S::Span;
+    type SpanOffset = S::SpanOffset;
+
+    fn span_offset(&self) -> Self::SpanOffset {
+        self.scanner.span_offset()
+    }
+
+    fn span_since(&self, start: Self::SpanOffset) -> Self::Span {
+        self.scanner.span_since(start)
+    }
+}
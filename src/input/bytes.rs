@@ -0,0 +1,87 @@
+use super::traits::*;
+
+/// A stream of bytes, yielding each [u8] by value
+///
+/// Unlike [std::slice::Iter], whose items are `&u8`, it allows matching
+/// bytes directly, as in `any_eq(b'x')`.
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// use lavan::prelude::*;
+///
+/// let mut input = Bytes::new(b"GET /");
+/// let Sure(method) = any_ne(b' ').del().repeat().slice().parse_once(&mut input);
+/// assert_eq!(method, b"GET");
+/// assert_eq!(input.as_slice(), b" /");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Bytes<'a> {
+    slice: &'a [u8],
+}
+
+impl<'a> Bytes<'a> {
+    pub fn new(slice: &'a [u8]) -> Self {
+        Self { slice }
+    }
+
+    /// The bytes that are yet to be consumed
+    pub fn as_slice(&self) -> &'a [u8] {
+        self.slice
+    }
+}
+
+impl<'a> From<&'a [u8]> for Bytes<'a> {
+    fn from(slice: &'a [u8]) -> Self {
+        Self::new(slice)
+    }
+}
+
+impl<'a> Iterator for Bytes<'a> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&first, rest) = self.slice.split_first()?;
+        self.slice = rest;
+        Some(first)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.slice.len(), Some(self.slice.len()))
+    }
+}
+
+impl<'a> Stream for Bytes<'a> {
+    type SaveState = Self;
+
+    fn savestate(&mut self) -> Self::SaveState {
+        *self
+    }
+
+    fn backtrack(&mut self, state: Self::SaveState) {
+        *self = state;
+    }
+
+    fn is_exhausted(&mut self) -> bool {
+        self.slice.is_empty()
+    }
+}
+
+impl<'a> StreamOffset for Bytes<'a> {
+    fn offset(&self) -> usize {
+        usize::MAX - self.slice.len()
+    }
+}
+
+impl<'a> StreamSlice for Bytes<'a> {
+    type Slice = &'a [u8];
+    type SliceOffset = &'a [u8];
+
+    fn slice_since(&self, builder: Self::SliceOffset) -> Self::Slice {
+        &builder[..builder.len() - self.slice.len()]
+    }
+
+    fn slice_offset(&self) -> Self::SliceOffset {
+        self.slice
+    }
+}
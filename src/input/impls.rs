@@ -1,3 +1,4 @@
+use super::tokens::{tokens_span, JoinSpan};
 use super::traits::*;
 
 impl<T> IntoStream for T
@@ -24,6 +25,12 @@ impl<'a> Stream for std::str::Chars<'a> {
     }
 }
 
+impl<'a> StreamOffset for std::str::Chars<'a> {
+    fn offset(&self) -> usize {
+        usize::MAX - self.as_str().len()
+    }
+}
+
 impl<'a> StreamSlice for std::str::Chars<'a> {
     type Slice = &'a str;
     type SliceOffset = &'a str;
@@ -49,6 +56,12 @@ impl<'a, T> Stream for std::slice::Iter<'a, T> {
     }
 }
 
+impl<'a, T> StreamOffset for std::slice::Iter<'a, T> {
+    fn offset(&self) -> usize {
+        usize::MAX - self.len()
+    }
+}
+
 impl<'a, T> StreamSlice for std::slice::Iter<'a, T> {
     type Slice = &'a [T];
     type SliceOffset = &'a [T];
@@ -61,3 +74,24 @@ impl<'a, T> StreamSlice for std::slice::Iter<'a, T> {
         self.as_slice()
     }
 }
+
+/// The spans of a slice of tokens paired with their span, as lexed by a
+/// [Lexer](crate::parser::lexer::Lexer), joined together as by [Tokens](super::tokens::Tokens).
+/// Unlike it, nothing is known before the remaining tokens, so the empty span at
+/// the end of the input is the default one.
+impl<'a, Tok, Span> StreamSpan for std::slice::Iter<'a, (Tok, Span)>
+where
+    Span: JoinSpan,
+{
+    type Span = Span;
+    type SpanOffset = &'a [(Tok, Span)];
+
+    fn span_offset(&self) -> Self::SpanOffset {
+        self.as_slice()
+    }
+
+    fn span_since(&self, start: Self::SpanOffset) -> Self::Span {
+        let consumed = &start[..start.len() - self.len()];
+        tokens_span(consumed, self.as_slice(), None)
+    }
+}
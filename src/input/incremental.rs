@@ -0,0 +1,234 @@
+use super::traits::*;
+use crate::parser::prelude::*;
+
+/// The outcome of parsing an [Incremental] stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Status<T> {
+    /// The parser has finished without reaching the end of the buffer
+    Complete(T),
+    /// The parser has reached the end of the buffer before the input was
+    /// finished, and needs at least `needed` more items to go further,
+    /// or at least one if the parser could not tell
+    Incomplete { needed: Option<usize> },
+}
+
+impl<T> Status<T> {
+    pub fn is_complete(&self) -> bool {
+        matches!(self, Status::Complete(_))
+    }
+
+    pub fn is_incomplete(&self) -> bool {
+        matches!(self, Status::Incomplete { .. })
+    }
+
+    pub fn complete(self) -> Option<T> {
+        match self {
+            Status::Complete(value) => Some(value),
+            Status::Incomplete { .. } => None,
+        }
+    }
+}
+
+/// A stream over a growable buffer, for input arriving in chunks
+///
+/// Until [finish](Incremental::finish) is called, reaching the end of the
+/// buffer does not mean reaching the end of the input: the stream is then
+/// starved, and [parse_partial](Incremental::parse_partial) rewinds it and
+/// returns [Status::Incomplete], so that parsing can be resumed once more
+/// data is [fed](Incremental::feed). The amount of items missing is known
+/// when the parser has [demanded](Stream::demand) them, as the parsers of a
+/// fixed size such as [binary::tag](crate::util::binary::tag) do.
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// use lavan::prelude::*;
+///
+/// let mut input = Incremental::new();
+/// let frame = || binary::length_prefixed(binary::u16_be());
+///
+/// input.feed(*b"\x00\x05hel");
+/// assert_eq!(input.parse_partial(frame()), Status::Incomplete { needed: Some(2) });
+/// // the partial frame is kept, to be parsed again
+/// assert_eq!(input.remaining(), b"\x00\x05hel");
+///
+/// input.feed(*b"lo\x00");
+/// assert_eq!(input.parse_partial(frame()), Status::Complete(Some(b"hello".to_vec())));
+/// assert_eq!(input.parse_partial(frame()), Status::Incomplete { needed: Some(1) });
+///
+/// // a parser of unknown size can only tell that it needs more
+/// let word = || any_ne(b' ').del().repeat().slice();
+/// assert!(matches!(input.parse_partial(word()), Status::Incomplete { needed: None }));
+///
+/// input.finish();
+/// assert_eq!(input.parse_partial(frame()), Status::Complete(None));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Incremental<T> {
+    buffer: Vec<T>,
+    position: usize,
+    drained: usize,
+    is_final: bool,
+    starved: bool,
+    /// the missing items reported by the starved parsers, if all could tell
+    needed: Option<usize>,
+    /// the end of the items demanded by the parser, as an index in the buffer
+    demand: Option<usize>,
+}
+
+impl<T> Incremental<T> {
+    pub fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            position: 0,
+            drained: 0,
+            is_final: false,
+            starved: false,
+            needed: None,
+            demand: None,
+        }
+    }
+
+    /// Appends more items to the buffer
+    pub fn feed(&mut self, items: impl IntoIterator<Item = T>) {
+        self.buffer.extend(items)
+    }
+
+    /// Marks the end of the buffer as the end of the input
+    pub fn finish(&mut self) {
+        self.is_final = true
+    }
+
+    pub fn is_final(&self) -> bool {
+        self.is_final
+    }
+
+    /// Whether the end of a non-final buffer has been reached.
+    /// Unlike the offset, it is kept when backtracking.
+    pub fn is_starved(&self) -> bool {
+        self.starved
+    }
+
+    /// The items that are yet to be consumed
+    pub fn remaining(&self) -> &[T] {
+        &self.buffer[self.position..]
+    }
+
+    /// Runs `parser`, returning [Status::Incomplete] and rewinding the stream
+    /// if it has reached the end of the buffer before the input was finished.
+    /// Otherwise, the consumed items are dropped from the buffer.
+    pub fn parse_partial<Par>(&mut self, parser: Par) -> Status<Par::Output>
+    where
+        Par: ParseOnce<Self>,
+        T: Clone,
+    {
+        let save_state = self.savestate();
+        self.starved = false;
+        let output = parser.parse_once(self);
+        self.demand = None;
+        if std::mem::take(&mut self.starved) {
+            self.backtrack(save_state);
+            let needed = self.needed.take();
+            return Status::Incomplete { needed };
+        }
+        self.buffer.drain(..self.position);
+        self.drained += self.position;
+        self.position = 0;
+        Status::Complete(output)
+    }
+}
+
+impl<T> Default for Incremental<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Iterator for Incremental<T>
+where
+    T: Clone,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.buffer.get(self.position) {
+            Some(item) => {
+                self.position += 1;
+                Some(item.clone())
+            }
+            None if self.is_final => None,
+            None => {
+                let needed = self.demand.map(|end| end.saturating_sub(self.buffer.len()));
+                let needed = needed.filter(|&needed| needed > 0);
+                // any of the starved parsers may be the one to resume
+                self.needed = match self.starved {
+                    true => self.needed.zip(needed).map(|(a, b)| a.min(b)),
+                    false => needed,
+                };
+                self.starved = true;
+                None
+            }
+        }
+    }
+}
+
+impl<T> Stream for Incremental<T>
+where
+    T: Clone,
+{
+    type SaveState = usize;
+
+    fn savestate(&mut self) -> Self::SaveState {
+        self.position
+    }
+
+    fn backtrack(&mut self, state: Self::SaveState) {
+        self.position = state;
+        self.demand = None;
+    }
+
+    fn demand(&mut self, count: usize) {
+        self.demand = Some(self.position + count);
+    }
+}
+
+impl<T> StreamOffset for Incremental<T>
+where
+    T: Clone,
+{
+    fn offset(&self) -> usize {
+        self.drained + self.position
+    }
+}
+
+impl<T> StreamSlice for Incremental<T>
+where
+    T: Clone,
+{
+    type Slice = Vec<T>;
+    type SliceOffset = usize;
+
+    fn slice_offset(&self) -> Self::SliceOffset {
+        self.position
+    }
+
+    fn slice_since(&self, start: Self::SliceOffset) -> Self::Slice {
+        self.buffer[start..self.position].to_vec()
+    }
+}
+
+impl<T> StreamSpan for Incremental<T>
+where
+    T: Clone,
+{
+    type Span = (usize, usize);
+    type SpanOffset = usize;
+
+    fn span_offset(&self) -> Self::SpanOffset {
+        self.drained + self.position
+    }
+
+    fn span_since(&self, start: Self::SpanOffset) -> Self::Span {
+        (start, self.drained + self.position)
+    }
+}
@@ -1,4 +1,11 @@
-pub use super::traits::{IntoStream, StrStream, Stream, StreamSlice, StreamSpan, StreamTrim};
+pub use super::bytes::Bytes;
+pub use super::incremental::{Incremental, Status};
+pub use super::reader::ReaderStream;
+pub use super::tokens::{tokens, Tokens};
+pub use super::traits::{
+    IntoStream, StrStream, Stream, StreamCut, StreamMemo, StreamNode, StreamOffset, StreamRecover,
+    StreamSlice, StreamSpan, StreamTrim,
+};
 
 pub(crate) mod internal {
     pub(crate) use super::*;
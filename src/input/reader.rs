@@ -0,0 +1,322 @@
+use super::traits::*;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::io::{self, BufRead};
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+/// The count of live [Checkpoint]s at each offset
+type Pins = Rc<RefCell<BTreeMap<usize, usize>>>;
+
+/// A position of a [ReaderStream], pinning the data after it until dropped
+#[derive(Debug)]
+pub struct Checkpoint {
+    offset: usize,
+    pins: Pins,
+}
+
+impl Checkpoint {
+    fn new(offset: usize, pins: &Pins) -> Self {
+        *pins.borrow_mut().entry(offset).or_default() += 1;
+        Self {
+            offset,
+            pins: pins.clone(),
+        }
+    }
+
+    /// The offset in bytes from the start of the reader
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl Clone for Checkpoint {
+    fn clone(&self) -> Self {
+        Self::new(self.offset, &self.pins)
+    }
+}
+
+impl Drop for Checkpoint {
+    fn drop(&mut self) {
+        let mut pins = self.pins.borrow_mut();
+        if let Some(count) = pins.get_mut(&self.offset) {
+            *count -= 1;
+            if *count == 0 {
+                pins.remove(&self.offset);
+            }
+        }
+    }
+}
+
+/// A stream reading from a [BufRead], either by bytes or by UTF-8 decoded `char`s
+///
+/// Only the data that can still be backtracked to is kept in memory:
+/// a [Checkpoint], taken as a savestate or a slice offset, pins the data
+/// after it until dropped, and everything before both the current offset and
+/// the earliest pin is freed when reading more. A [cut](StreamCut::cut) commits
+/// to the current offset, also freeing the data pinned by older checkpoints:
+/// backtracking or slicing from one of them starts at the cut instead.
+///
+/// Reading stops on the first io error, which can be retrieved afterwards.
+/// Invalid UTF-8 is reported as an [io::ErrorKind::InvalidData] error.
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// use lavan::prelude::*;
+/// use std::io::BufReader;
+///
+/// let log = "INFO ok\n".repeat(1000) + "WARN disk almost full\n";
+/// let mut input = ReaderStream::chars(BufReader::with_capacity(8, log.as_bytes()));
+/// let line = any_ne('\n').del().repeat().slice().and(any_eq('\n').del());
+///
+/// let mut warnings = vec![];
+/// while let Some(line) = line.parse(&mut input) {
+///     if line.starts_with("WARN") {
+///         warnings.push(line);
+///     }
+///     assert!(input.buffered() < 32);
+/// }
+///
+/// assert_eq!(warnings, ["WARN disk almost full"]);
+/// assert!(input.error().is_none());
+/// ```
+#[derive(Debug)]
+pub struct ReaderStream<R, T = u8> {
+    reader: R,
+    buffer: Vec<u8>,
+    /// offset of the first byte of `buffer`
+    base: usize,
+    offset: usize,
+    cut: usize,
+    pins: Pins,
+    error: Option<io::Error>,
+    item: PhantomData<T>,
+}
+
+impl<R> ReaderStream<R, u8>
+where
+    R: BufRead,
+{
+    /// Creates a stream yielding the bytes of `reader`
+    pub fn bytes(reader: R) -> Self {
+        Self::new(reader)
+    }
+}
+
+impl<R> ReaderStream<R, char>
+where
+    R: BufRead,
+{
+    /// Creates a stream yielding the UTF-8 decoded `char`s of `reader`
+    pub fn chars(reader: R) -> Self {
+        Self::new(reader)
+    }
+}
+
+impl<R, T> ReaderStream<R, T>
+where
+    R: BufRead,
+{
+    fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: Vec::new(),
+            base: 0,
+            offset: 0,
+            cut: 0,
+            pins: Pins::default(),
+            error: None,
+            item: PhantomData,
+        }
+    }
+
+    /// The offset in bytes from the start of the reader
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The amount of bytes currently held in memory
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// The io error which stopped the reading, if any
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Frees the data that can no longer be backtracked to
+    fn compact(&mut self) {
+        // checkpoints from before the cut rewind to the cut itself
+        let pinned = self
+            .pins
+            .borrow()
+            .first_key_value()
+            .map(|(&o, _)| o.max(self.cut));
+        let keep = pinned.map_or(self.offset, |pinned| pinned.min(self.offset));
+        self.buffer.drain(..keep - self.base);
+        self.base = keep;
+    }
+
+    /// Makes sure that `len` bytes are buffered after the current offset,
+    /// returning false if the reader has ended or failed before
+    fn fill(&mut self, len: usize) -> bool {
+        while self.base + self.buffer.len() < self.offset + len {
+            if self.error.is_some() {
+                return false;
+            }
+            self.compact();
+            let chunk = match self.reader.fill_buf() {
+                Ok([]) => return false,
+                Ok(chunk) => chunk,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => {
+                    self.error = Some(error);
+                    return false;
+                }
+            };
+            let read = chunk.len();
+            self.buffer.extend_from_slice(chunk);
+            self.reader.consume(read);
+        }
+        true
+    }
+
+    fn bytes_since(&self, start: &Checkpoint) -> &[u8] {
+        let start = start.offset.max(self.cut);
+        &self.buffer[start - self.base..self.offset - self.base]
+    }
+}
+
+impl<R> Iterator for ReaderStream<R, u8>
+where
+    R: BufRead,
+{
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.fill(1) {
+            return None;
+        }
+        let byte = self.buffer[self.offset - self.base];
+        self.offset += 1;
+        Some(byte)
+    }
+}
+
+impl<R> Iterator for ReaderStream<R, char>
+where
+    R: BufRead,
+{
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.fill(1) {
+            return None;
+        }
+        let len = match self.buffer[self.offset - self.base] {
+            0x00..=0x7f => 1,
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            _ => 4,
+        };
+        let start = self.offset - self.base;
+        let decoded = match self.fill(len) {
+            true => std::str::from_utf8(&self.buffer[start..start + len]).ok(),
+            false if self.error.is_some() => return None,
+            false => None,
+        };
+        match decoded.and_then(|s| s.chars().next()) {
+            Some(c) => {
+                self.offset += len;
+                Some(c)
+            }
+            None => {
+                self.error = Some(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "stream did not contain valid UTF-8",
+                ));
+                None
+            }
+        }
+    }
+}
+
+impl<R, T> Stream for ReaderStream<R, T>
+where
+    R: BufRead,
+    Self: Iterator,
+{
+    type SaveState = Checkpoint;
+
+    fn savestate(&mut self) -> Self::SaveState {
+        Checkpoint::new(self.offset, &self.pins)
+    }
+
+    fn backtrack(&mut self, state: Self::SaveState) {
+        self.offset = state.offset.max(self.cut);
+    }
+}
+
+impl<R, T> StreamOffset for ReaderStream<R, T>
+where
+    R: BufRead,
+    Self: Iterator,
+{
+    fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<R, T> StreamCut for ReaderStream<R, T>
+where
+    R: BufRead,
+    Self: Iterator,
+{
+    fn cut(&mut self) {
+        self.cut = self.offset;
+        self.compact();
+    }
+}
+
+impl<R> StreamSlice for ReaderStream<R, u8>
+where
+    R: BufRead,
+{
+    type Slice = Vec<u8>;
+    type SliceOffset = Checkpoint;
+
+    fn slice_offset(&self) -> Self::SliceOffset {
+        Checkpoint::new(self.offset, &self.pins)
+    }
+
+    fn slice_since(&self, start: Self::SliceOffset) -> Self::Slice {
+        self.bytes_since(&start).to_vec()
+    }
+}
+
+impl<R> StreamSlice for ReaderStream<R, char>
+where
+    R: BufRead,
+{
+    type Slice = String;
+    type SliceOffset = Checkpoint;
+
+    fn slice_offset(&self) -> Self::SliceOffset {
+        Checkpoint::new(self.offset, &self.pins)
+    }
+
+    fn slice_since(&self, start: Self::SliceOffset) -> Self::Slice {
+        // only whole `char`s are consumed, so the bytes are valid UTF-8
+        String::from_utf8_lossy(self.bytes_since(&start)).into_owned()
+    }
+}
@@ -0,0 +1,230 @@
+use super::adapters::line_cursor;
+use super::traits::*;
+use std::ops::Range;
+
+/// A span of source text that can be joined with the ones following it,
+/// for the span of a sequence of tokens
+pub trait JoinSpan: Clone + Default {
+    /// Spans from the start of this span to the end of `end`
+    fn join(&self, end: &Self) -> Self;
+    /// An empty span at the start of this span
+    fn start(&self) -> Self;
+    /// An empty span at the end of this span
+    fn end(&self) -> Self;
+}
+
+impl JoinSpan for (usize, usize) {
+    fn join(&self, end: &Self) -> Self {
+        (self.0, end.1)
+    }
+
+    fn start(&self) -> Self {
+        (self.0, self.0)
+    }
+
+    fn end(&self) -> Self {
+        (self.1, self.1)
+    }
+}
+
+impl JoinSpan for Range<usize> {
+    fn join(&self, end: &Self) -> Self {
+        self.start..end.end
+    }
+
+    fn start(&self) -> Self {
+        self.start..self.start
+    }
+
+    fn end(&self) -> Self {
+        self.end..self.end
+    }
+}
+
+impl JoinSpan for line_cursor::Span {
+    fn join(&self, end: &Self) -> Self {
+        Self {
+            start: self.start,
+            end: end.end,
+        }
+    }
+
+    fn start(&self) -> Self {
+        Self {
+            start: self.start,
+            end: self.start,
+        }
+    }
+
+    fn end(&self) -> Self {
+        Self {
+            start: self.end,
+            end: self.end,
+        }
+    }
+}
+
+/// The span of the `consumed` tokens joined together. When nothing is consumed,
+/// an empty span where the `rest` starts, or where the `previous` token ends.
+pub(crate) fn tokens_span<Tok, Span>(
+    consumed: &[(Tok, Span)],
+    rest: &[(Tok, Span)],
+    previous: Option<&(Tok, Span)>,
+) -> Span
+where
+    Span: JoinSpan,
+{
+    match consumed {
+        [(_, first), .., (_, last)] => first.join(last),
+        [(_, only)] => only.clone(),
+        [] => match (rest.first(), previous) {
+            (Some((_, next)), _) => next.start(),
+            (None, Some((_, last))) => last.end(),
+            (None, None) => Span::default(),
+        },
+    }
+}
+
+/// Creates a [Tokens] stream over the output of a lexer
+pub fn tokens<Tok, Span>(tokens: &[(Tok, Span)]) -> Tokens<'_, Tok, Span> {
+    Tokens::new(tokens)
+}
+
+/// A stream of tokens, each paired with its span in the source text
+///
+/// The tokens are yielded by value, and the spans it reports are the
+/// source-text spans of the consumed tokens, joined together.
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// use lavan::prelude::*;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// enum Tok {
+///     Ident(&'static str),
+///     Comma,
+/// }
+///
+/// // "x, y"
+/// let lexed = [(Tok::Ident("x"), (0, 1)), (Tok::Comma, (1, 2)), (Tok::Ident("y"), (3, 4))];
+/// let mut input = tokens(&lexed);
+///
+/// let ident = select! { Tok::Ident(name) => name };
+/// let (names, span) = ident
+///     .and(just(Tok::Comma).del())
+///     .and(select! { Tok::Ident(name) => name })
+///     .spanned()
+///     .parse_once(&mut input)
+///     .unwrap();
+///
+/// assert_eq!(names, ("x", "y"));
+/// assert_eq!(span, (0, 4));
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Tokens<'a, Tok, Span> {
+    tokens: &'a [(Tok, Span)],
+    position: usize,
+}
+
+impl<'a, Tok, Span> Tokens<'a, Tok, Span> {
+    pub fn new(tokens: &'a [(Tok, Span)]) -> Self {
+        Self {
+            tokens,
+            position: 0,
+        }
+    }
+
+    /// The tokens that are yet to be consumed
+    pub fn as_slice(&self) -> &'a [(Tok, Span)] {
+        &self.tokens[self.position..]
+    }
+
+    /// The index of the next token
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl<Tok, Span> Clone for Tokens<'_, Tok, Span> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Tok, Span> Copy for Tokens<'_, Tok, Span> {}
+
+impl<'a, Tok, Span> Iterator for Tokens<'a, Tok, Span>
+where
+    Tok: Clone,
+{
+    type Item = Tok;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (token, _) = self.tokens.get(self.position)?;
+        self.position += 1;
+        Some(token.clone())
+    }
+}
+
+impl<'a, Tok, Span> Stream for Tokens<'a, Tok, Span>
+where
+    Tok: Clone,
+{
+    type SaveState = usize;
+
+    fn savestate(&mut self) -> Self::SaveState {
+        self.position
+    }
+
+    fn backtrack(&mut self, state: Self::SaveState) {
+        self.position = state;
+    }
+
+    fn is_exhausted(&mut self) -> bool {
+        self.position >= self.tokens.len()
+    }
+}
+
+impl<'a, Tok, Span> StreamOffset for Tokens<'a, Tok, Span>
+where
+    Tok: Clone,
+{
+    fn offset(&self) -> usize {
+        self.position
+    }
+}
+
+impl<'a, Tok, Span> StreamSlice for Tokens<'a, Tok, Span>
+where
+    Tok: Clone,
+{
+    type Slice = &'a [(Tok, Span)];
+    type SliceOffset = usize;
+
+    fn slice_offset(&self) -> Self::SliceOffset {
+        self.position
+    }
+
+    fn slice_since(&self, start: Self::SliceOffset) -> Self::Slice {
+        &self.tokens[start..self.position]
+    }
+}
+
+impl<'a, Tok, Span> StreamSpan for Tokens<'a, Tok, Span>
+where
+    Tok: Clone,
+    Span: JoinSpan,
+{
+    type Span = Span;
+    type SpanOffset = usize;
+
+    fn span_offset(&self) -> Self::SpanOffset {
+        self.position
+    }
+
+    fn span_since(&self, start: Self::SpanOffset) -> Self::Span {
+        let (before, rest) = self.tokens.split_at(self.position);
+        tokens_span(&before[start.min(self.position)..], rest, before.last())
+    }
+}
@@ -1,4 +1,13 @@
-use super::adapters::cursor::Cursor;
+#[cfg(feature = "unstable-trace-2021-v1")]
+use super::adapters::traced::Traced;
+use super::adapters::{
+    cst_builder::CstBuilder,
+    cursor::Cursor,
+    line_cursor::LineCursor,
+    memoizing::Memoizing,
+    recovering::Recovering,
+    trimming::{Trimming, Trivia},
+};
 use crate::parser::{adapters::slice::Slice, prelude::*};
 
 pub trait Stream: Iterator {
@@ -7,6 +16,13 @@ pub trait Stream: Iterator {
     fn savestate(&mut self) -> Self::SaveState;
     fn backtrack(&mut self, state: Self::SaveState);
 
+    /// Announces that the next `count` items are about to be consumed as a whole,
+    /// for a stream running out of items to tell how many are missing.
+    /// Does nothing by default.
+    fn demand(&mut self, count: usize) {
+        let _ = count;
+    }
+
     fn is_exhausted(&mut self) -> bool {
         let s = self.savestate();
         let a = self.next().is_none();
@@ -21,6 +37,50 @@ pub trait Stream: Iterator {
         Cursor::new(self)
     }
 
+    fn line_cursor(self) -> LineCursor<Self>
+    where
+        Self: Sized + Iterator<Item = char>,
+    {
+        LineCursor::new(self)
+    }
+
+    fn recovering<E>(self) -> Recovering<Self, E>
+    where
+        Self: Sized,
+    {
+        Recovering::new(self)
+    }
+
+    fn memoizing(self) -> Memoizing<Self>
+    where
+        Self: Sized,
+    {
+        Memoizing::new(self)
+    }
+
+    fn trimming(self, trivia: Trivia) -> Trimming<Self>
+    where
+        Self: Sized + Iterator<Item = char>,
+    {
+        Trimming::new(self, trivia)
+    }
+
+    fn cst_builder<'a, Kind>(self) -> CstBuilder<Self, Kind>
+    where
+        Self: Sized + StreamSlice<Item = char, Slice = &'a str>,
+    {
+        CstBuilder::new(self)
+    }
+
+    /// Logs the backtracks of this stream to the [trace](crate::trace) being recorded
+    #[cfg(feature = "unstable-trace-2021-v1")]
+    fn traced(self) -> Traced<Self>
+    where
+        Self: Sized + StreamOffset,
+    {
+        Traced::new(self)
+    }
+
     fn from<T>(source: T) -> Self
     where
         Self: Sized,
@@ -54,6 +114,43 @@ pub trait StreamSpan: Stream {
     fn span_since(&self, start: Self::SpanOffset) -> Self::Span;
 }
 
+/// A stream whose position can be compared, increasing as it is consumed.
+/// Only positions of the same stream are meaningful to each other.
+pub trait StreamOffset: Stream {
+    fn offset(&self) -> usize;
+}
+
+/// A stream with an identity, for [memoized](crate::parser::traits::ParseOnce::memoize)
+/// parsers to only reuse their outputs within the same stream
+pub trait StreamMemo: StreamOffset {
+    fn memo_id(&self) -> usize;
+}
+
+pub trait StreamRecover: Stream {
+    type Error;
+
+    fn push_error(&mut self, error: Self::Error);
+}
+
+/// A stream recording the nodes of a [concrete syntax tree](crate::cst)
+pub trait StreamNode: Stream {
+    type Kind;
+    type NodeStart;
+
+    fn start_node(&mut self) -> Self::NodeStart;
+    /// Ends the node started at `start`, discarding it along with
+    /// its children when `kind` is `None`
+    fn finish_node(&mut self, start: Self::NodeStart, kind: Option<Self::Kind>);
+}
+
+/// A stream which can commit to its current position, letting go of
+/// the input before it. Backtracking to a savestate taken before the cut
+/// rewinds to the cut instead, so the parser holding it fails from there
+/// rather than reparsing the committed input.
+pub trait StreamCut: Stream {
+    fn cut(&mut self);
+}
+
 pub trait StreamTrim: Stream {
     fn trim(&mut self);
 }
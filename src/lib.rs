@@ -1,6 +1,8 @@
 #![allow(unused)]
 #![cfg(feature = "unstable")]
 
+extern crate self as lavan;
+
 #[macro_export]
 macro_rules! or {
     ($first:expr $(,$tail:expr)+ $(,)?) => {
@@ -8,6 +10,150 @@ macro_rules! or {
     }
 }
 
+/// Defines parsers through PEG-like rules, expanding into the usual combinators.
+///
+/// Each `name = expression;` rule is bound to a local variable, usable in any
+/// rule and as any other parser. Rules referred to before their definition, or
+/// from within it, are [declared](parser::sources::declare) upfront, so that rules
+/// can be recursive. They are then [Parse](parser::traits::Parse) trait objects,
+/// whose output type may have to be given, as in a cycle of rules: a rule written
+/// as `name: Output = expression;` is declared as well, with that output type.
+/// Within expressions:
+/// - `"abc"` and `'a'` expect a string or a char
+/// - `name` refers to a rule, or to any parser in scope, by reference
+/// - `{ expr }` embeds a parser expression
+/// - `a b` parses in sequence, as [`and`](parser::traits::ParseOnce::and)
+/// - `a | b` tries alternatives, as [`or`](parser::traits::ParseOnce::or)
+/// - `a?` is optional, as [`opt`](parser::traits::ParseOnce::opt)
+/// - `a*` and `a+` repeat, discarding the values
+/// - `$a` yields the input slice matched by `a`
+///
+/// # Examples
+/// Basic usage:
+///```
+/// use lavan::prelude::*;
+///
+/// let alpha = any_if(char::is_ascii_alphabetic);
+/// let alnum = any_if(char::is_ascii_alphanumeric);
+/// let digit = any_if(char::is_ascii_digit);
+///
+/// grammar! {
+///     ident = $(alpha (alnum | '_')*);
+///     number = $digit+;
+///     call = ident '(' number? ')' { eoi() };
+/// }
+///
+/// assert_eq!(ident.parse(&mut "snake_case2 x".chars()), Some("snake_case2"));
+/// assert_eq!(ident.parse(&mut "2x".chars()), None);
+/// assert_eq!(call.parse(&mut "sqrt(16)".chars()), Some(("sqrt", Some("16"))));
+/// assert_eq!(call.parse(&mut "pi()".chars()), Some(("pi", None)));
+/// assert_eq!(call.parse(&mut "pi() + 1".chars()), None);
+/// ```
+///
+/// Recursive rules:
+///```
+/// use lavan::prelude::*;
+///
+/// let digit = any_if(char::is_ascii_digit);
+///
+/// grammar! {
+///     value: Option<&str> = $digit+ | $list;
+///     list = '[' items? ']';
+///     items = value (',' value)*;
+/// }
+///
+/// assert_eq!(value.parse(&mut "[1,[2,3],[]]".chars()), Some("[1,[2,3],[]]"));
+/// assert_eq!(value.parse(&mut "[1,[2]".chars()), None);
+/// ```
+pub use lavan_proc_macros::grammar;
+
+/// Implements [`ParseOnce`](parser::traits::ParseOnce), [`ParseMut`](parser::traits::ParseMut)
+/// and [`Parse`](parser::traits::Parse) for the type of the receiver, out of the function body.
+///
+/// The receiver names the parser type, as in `self: &MyParser`, and the return
+/// type is the output of the parser. A `self: MyParser` receiver only implements
+/// `ParseOnce`, `self: &mut MyParser` adds `ParseMut`, and `self: &MyParser`
+/// implements all three. The input type is either given as an `input: Type`
+/// argument, or as the only fully capitalized generic, or else a generic `INPUT`
+/// stream. Bounds of the form `for<'impl_move>`, `for<'impl_mut>` and `for<'impl_ref>`
+/// only apply to the matching implementation.
+///
+/// Lowercase generics are bounded to be parsers of the same kind as the one
+/// being implemented, and within the body:
+/// - `parse![p]` parses with `p` through the method matching the implementation
+/// - `parser![p]` takes `p` so that it can be parsed with as such
+/// - `when! { move => a, mut => b, ref => c }` picks an expression by implementation
+///
+/// With `#[parser_fn(mut in move)]`, the `ParseOnce` implementation parses with
+/// its lowercase generics through `ParseMut`, so that they can be parsed with
+/// repeatedly. It requires a `mut self` receiver.
+///
+/// # Examples
+/// Basic usage:
+///```
+/// use lavan::prelude::*;
+///
+/// /// Parses with `parser` up to `max` times, counting the successes
+/// struct Count<Par> {
+///     parser: Par,
+///     max: usize,
+/// }
+///
+/// #[parser_fn(mut in move)]
+/// fn count<par>(mut self: &Count<par>) -> Sure<usize> {
+///     let mut count = 0;
+///     while count < self.max && parse![self.parser].control_flow().is_continue() {
+///         count += 1;
+///     }
+///     Sure(count)
+/// }
+///
+/// let a = Count {
+///     parser: any_eq('a'),
+///     max: 3,
+/// };
+/// let Sure(count) = a.parse(&mut "aab".chars());
+/// assert_eq!(count, 2);
+/// let Sure(count) = a.parse(&mut "aaaa".chars());
+/// assert_eq!(count, 3);
+/// ```
+pub use lavan_proc_macros::parser_fn;
+
+/// Implements [`ParseOnce`](parser::traits::ParseOnce), [`ParseMut`](parser::traits::ParseMut)
+/// and [`Parse`](parser::traits::Parse) out of a single parsing function.
+///
+/// The impl block holds the `Output` type, the parsing function and optionally
+/// the `Input` type, otherwise generic over any [`Stream`](input::traits::Stream).
+/// Which traits are implemented depends on the receiver: `self` only implements
+/// `ParseOnce`, `&mut self` adds `ParseMut`, and `&self` implements all three.
+///
+/// The function body is that of a [`parser_fn`], along with its generics
+/// and helper macros, and `Self::Input` names the input type.
+///
+/// # Examples
+/// Basic usage:
+///```
+/// use lavan::prelude::*;
+///
+/// struct Number;
+///
+/// #[parser_impl]
+/// impl<'a> Number {
+///     type Input = std::str::Chars<'a>;
+///     type Output = Option<u32>;
+///
+///     fn parse(&self, input: &mut Self::Input) -> Self::Output {
+///         let digits = parse![any_if(char::is_ascii_digit).del().repeat_min(1).slice()]?;
+///         digits.parse().ok()
+///     }
+/// }
+///
+/// let sum = Number.as_ref().and(any_eq('+').del()).and(Number);
+/// assert_eq!(sum.parse(&mut "12+30".chars()), Some((12, 30)));
+/// assert_eq!(Number.parse(&mut "x".chars()), None);
+/// ```
+pub use lavan_proc_macros::parser_impl;
+
 pub mod parser {
     pub mod adapters {
         pub mod and;
@@ -17,17 +163,22 @@ pub mod parser {
         pub mod del;
         pub mod delimited;
         pub mod eq;
+        pub mod expect;
         pub mod filter;
         pub mod lift;
         pub mod map;
         pub mod marker;
+        pub mod memoize;
         pub(crate) mod never_fails;
+        pub mod node;
         pub mod ok;
         pub mod opt;
         pub mod or;
         pub mod owned;
+        pub mod padded;
         pub mod parse_str;
         pub mod persist;
+        pub mod recover;
 
         #[cfg(feature = "unstable-repeat-api-2021-v1")]
         pub mod repeat;
@@ -37,14 +188,29 @@ pub mod parser {
         pub mod slice;
         pub mod spanned;
         pub mod then;
+        #[cfg(feature = "unstable-trace-2021-v1")]
+        pub mod trace;
         pub mod try_with;
         pub mod unwrapped;
     }
+    pub mod lexer;
+    pub mod pratt;
     pub(crate) mod prelude;
     pub mod sources;
     pub mod traits;
     pub(crate) mod util;
 }
+pub mod error {
+    pub mod parse_error;
+    pub mod render;
+    pub mod report;
+    pub mod response;
+
+    pub use parse_error::{Expected, ParseError};
+    pub use render::{Diagnostic, SourceSpan};
+    pub use report::{Report, ReportError};
+    pub use response::ParseResult;
+}
 pub mod response {
     pub(crate) mod adapters {
         pub mod bool;
@@ -61,19 +227,47 @@ pub mod response {
 }
 pub mod input {
     pub mod adapters {
+        pub mod cst_builder;
         pub mod cursor;
+        pub mod line_cursor;
+        pub mod memoizing;
+        pub mod recovering;
+        #[cfg(feature = "unstable-trace-2021-v1")]
+        pub mod traced;
+        pub mod trimming;
     }
+    pub mod bytes;
     pub mod impls;
+    pub mod incremental;
     pub mod prelude;
+    pub mod reader;
+    pub mod tokens;
     pub mod traits;
 }
+pub mod cst;
 pub mod util {
+    pub mod char_class;
+    mod general_category;
+
     #[cfg(feature = "unstable-text-util-2021-v1")]
     pub mod text;
     #[cfg(not(feature = "unstable-text-util-2021-v1"))]
     pub(crate) mod text;
+
+    #[cfg(feature = "unstable-binary-util-2021-v1")]
+    pub mod binary;
+    #[cfg(not(feature = "unstable-binary-util-2021-v1"))]
+    pub(crate) mod binary;
 }
 #[cfg(feature = "unstable-prelude-2021-v1")]
 pub mod prelude;
 #[cfg(not(feature = "unstable-prelude-2021-v1"))]
 pub(crate) mod prelude;
+#[cfg(feature = "unstable-trace-2021-v1")]
+pub mod trace;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::parser::util::{by_ref, void};
+    pub use crate::response::traits::Response;
+}
@@ -0,0 +1,46 @@
+use crate::error::{Expected, ParseError, ParseResult, Report};
+use crate::input::tokens::JoinSpan;
+use crate::parser::prelude::internal::*;
+
+/// A parser for reporting a [ParseError] in case of failure
+///
+/// This `struct` is created by the [`ParseOnce::expect`] and
+/// [`ParseOnce::label`] methods on [`ParseOnce`].
+/// See their documentation for more.
+#[must_use = "Parsers are lazy and do nothing unless consumed"]
+#[derive(Debug, Clone)]
+pub struct Expect<Par, Item> {
+    pub(in crate::parser) parser: Par,
+    pub(in crate::parser) expected: Expected<Item>,
+}
+
+#[parser_fn]
+fn expect<par, Item, Val>(self: &Expect<par, Item>) -> ParseResult<Val, Item, INPUT::Span>
+where
+    INPUT: StreamSpan<Item = Item>,
+    par::Output: Report<Item, INPUT::Span, Value = Val>,
+    INPUT::Span: JoinSpan + PartialOrd,
+    Item: PartialEq,
+    for<'impl_mut, 'impl_ref> Item: Clone,
+{
+    let start = input.span_offset();
+    let save_state = input.savestate();
+    match parse![self.parser].report() {
+        Ok(value) => ParseResult::ok(value),
+        Err(inner) => {
+            input.backtrack(save_state);
+            let rewind_state = input.savestate();
+            let found = input.next();
+            let error = ParseError::new(input.span_since(start), found);
+            input.backtrack(rewind_state);
+            ParseResult::err(match inner {
+                // the inner parser failed further in, so its error is more precise
+                Some(inner) if inner.span().start() > error.span().start() => inner,
+                _ => error.with_expected(when! {
+                    move => self.expected,
+                    _ => self.expected.clone(),
+                }),
+            })
+        }
+    }
+}
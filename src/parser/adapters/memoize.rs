@@ -0,0 +1,75 @@
+use crate::parser::prelude::internal::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// A parser for caching the outputs of another parser by stream offset
+///
+/// This `struct` is created by the [`ParseOnce::memoize`] method on [`ParseOnce`].
+/// See its documentation for more.
+#[must_use = "Parsers are lazy and do nothing unless consumed"]
+#[derive(Debug, Clone)]
+pub struct Memoize<Par, Out, State> {
+    pub(in crate::parser) parser: Par,
+    pub(in crate::parser) memo: RefCell<Memo<Out, State>>,
+}
+
+/// The cached outputs of a single stream, told apart by [`StreamMemo::memo_id`]
+#[derive(Debug, Clone)]
+pub(in crate::parser) struct Memo<Out, State> {
+    pub(in crate::parser) id: Option<usize>,
+    pub(in crate::parser) outputs: HashMap<usize, (Out, State)>,
+}
+
+impl<Out, State> Default for Memo<Out, State> {
+    fn default() -> Self {
+        Self {
+            id: None,
+            outputs: HashMap::new(),
+        }
+    }
+}
+
+impl<Par, Out, State> Memoize<Par, Out, State> {
+    /// Forgets every cached output
+    pub fn clear(&self) {
+        self.memo.borrow_mut().outputs.clear()
+    }
+
+    /// The amount of cached outputs
+    pub fn len(&self) -> usize {
+        self.memo.borrow().outputs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.memo.borrow().outputs.is_empty()
+    }
+}
+
+#[parser_fn]
+fn memoize<par>(self: &Memoize<par, par::Output, INPUT::SaveState>) -> par::Output
+where
+    INPUT: StreamMemo<SaveState: Clone>,
+    par::Output: Clone,
+{
+    let id = input.memo_id();
+    let offset = input.offset();
+    {
+        let mut memo = self.memo.borrow_mut();
+        // the outputs of another stream are meaningless to this one
+        if memo.id != Some(id) {
+            memo.id = Some(id);
+            memo.outputs.clear();
+        }
+        if let Some((output, state)) = memo.outputs.get(&offset) {
+            input.backtrack(state.clone());
+            return output.clone();
+        }
+    }
+    let output = parse![self.parser];
+    let state = input.savestate();
+    self.memo
+        .borrow_mut()
+        .outputs
+        .insert(offset, (output.clone(), state));
+    output
+}
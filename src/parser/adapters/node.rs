@@ -0,0 +1,25 @@
+use crate::parser::prelude::internal::*;
+
+/// A parser recording its span as a node of a [concrete syntax tree](crate::cst)
+///
+/// This `struct` is created by the [`ParseOnce::node`] method on [`ParseOnce`].
+/// See its documentation for more.
+#[must_use = "Parsers are lazy and do nothing unless consumed"]
+#[derive(Debug, Clone, Copy)]
+pub struct Node<Par, Kind> {
+    pub(in crate::parser) parser: Par,
+    pub(in crate::parser) kind: Kind,
+}
+
+#[parser_fn]
+fn node<par, Kind>(self: &Node<par, Kind>) -> par::Output
+where
+    INPUT: StreamNode<Kind = Kind>,
+    Kind: Clone,
+{
+    let start = input.start_node();
+    let mut kind = None;
+    let output = parse![self.parser].on_ok(|| kind = Some(self.kind.clone()));
+    input.finish_node(start, kind);
+    output
+}
@@ -0,0 +1,41 @@
+use crate::parser::prelude::internal::*;
+
+/// A parser skipping trivia before and after itself, through [`StreamTrim`]
+///
+/// This `struct` is created by the [`ParseOnce::padded`] method on [`ParseOnce`].
+/// See its documentation for more.
+#[must_use = "Parsers are lazy and do nothing unless consumed"]
+#[derive(Debug, Clone, Copy)]
+pub struct Padded<Par> {
+    pub(in crate::parser) parser: Par,
+}
+
+/// A parser skipping trivia after itself, through [`StreamTrim`]
+///
+/// This `struct` is created by the [`ParseOnce::lexeme`] method on [`ParseOnce`].
+/// See its documentation for more.
+#[must_use = "Parsers are lazy and do nothing unless consumed"]
+#[derive(Debug, Clone, Copy)]
+pub struct Lexeme<Par> {
+    pub(in crate::parser) parser: Par,
+}
+
+#[parser_fn]
+fn padded<par>(self: &Padded<par>) -> par::Output
+where
+    INPUT: StreamTrim,
+{
+    let state = input.savestate();
+    input.trim();
+    parse![self.parser]
+        .on_ok(|| input.trim())
+        .on_err(|| input.backtrack(state))
+}
+
+#[parser_fn]
+fn lexeme<par>(self: &Lexeme<par>) -> par::Output
+where
+    INPUT: StreamTrim,
+{
+    parse![self.parser].on_ok(|| input.trim())
+}
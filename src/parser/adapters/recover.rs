@@ -0,0 +1,38 @@
+use crate::parser::prelude::internal::*;
+
+/// A parser for recovering from failures with a fallback strategy
+///
+/// This `struct` is created by the [`ParseOnce::recover_with`] method on [`ParseOnce`].
+/// See its documentation for more.
+#[must_use = "Parsers are lazy and do nothing unless consumed"]
+#[derive(Debug, Clone, Copy)]
+pub struct RecoverWith<Par, Strat> {
+    pub(in crate::parser) parser: Par,
+    pub(in crate::parser) strategy: Strat,
+}
+
+#[parser_fn]
+fn recover_with<par, strat>(self: &RecoverWith<par, strat>) -> par::Output
+where
+    INPUT: StreamRecover<Error = err![par]>,
+    strat::Output: Response<Value = val![par]>,
+{
+    let save_state = input.savestate();
+    match parse![self.parser].control_flow() {
+        Continue(value) => par::Output::from_value(value),
+        Break(error) => {
+            input.backtrack(save_state);
+            let rewind_state = input.savestate();
+            match parse![self.strategy].control_flow() {
+                Continue(placeholder) => {
+                    input.push_error(error);
+                    par::Output::from_value(placeholder)
+                }
+                Break(_) => {
+                    input.backtrack(rewind_state);
+                    par::Output::from_error(error)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::parser::prelude::internal::*;
+use crate::trace::{self, Outcome};
+
+/// A parser logging its invocations to the [trace](crate::trace) being recorded
+///
+/// This `struct` is created by the [`ParseOnce::trace`] method on [`ParseOnce`].
+/// See its documentation for more.
+#[must_use = "Parsers are lazy and do nothing unless consumed"]
+#[derive(Debug, Clone, Copy)]
+pub struct Trace<Par> {
+    pub(in crate::parser) parser: Par,
+    pub(in crate::parser) name: &'static str,
+}
+
+#[parser_fn]
+fn trace<par>(self: &Trace<par>) -> par::Output
+where
+    INPUT: StreamSpan,
+    <INPUT as StreamSpan>::Span: std::fmt::Debug,
+{
+    if !trace::enter() {
+        return parse![self.parser];
+    }
+
+    let start = input.span_offset();
+    let mut outcome = Outcome::Failure;
+    let output = parse![self.parser].on_ok(|| outcome = Outcome::Success);
+    let span = format!("{:?}", input.span_since(start));
+    trace::exit(self.name, span, outcome);
+    output
+}
@@ -0,0 +1,263 @@
+use crate::error::{Expected, ParseError, Report};
+use crate::input::adapters::cursor::Cursor;
+use crate::input::tokens::{JoinSpan, Tokens};
+use crate::parser::{prelude::internal::*, sources::adapters::NOP};
+use std::fmt::{Debug, Display};
+use std::str::Chars;
+
+/// The stream a [Lexer] runs its parsers on
+pub type Source<'s> = Cursor<Chars<'s>>;
+
+/// The span of a token, as `char` offsets in the source text
+pub type Span = (usize, usize);
+
+/// A tokenizer built from a parser of single tokens, producing the
+/// tokens of a source text paired with their [Span]
+///
+/// It is itself a parser, repeating the token parser up to the end of the input,
+/// so it runs on any stream with spans, while [Lexer::lex] runs it on a source text.
+///
+/// # Examples
+/// Basic usage:
+///```
+/// use lavan::prelude::*;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// enum Tok {
+///     Num(u32),
+///     Plus,
+/// }
+///
+/// let num = |digits: &str| Tok::Num(digits.parse().unwrap());
+/// let plus = || Tok::Plus;
+/// let token = any_if(char::is_ascii_digit)
+///     .del()
+///     .repeat_min(1)
+///     .slice()
+///     .map(num)
+///     .or(any_eq('+').del().sel(plus));
+///
+/// let mut lexer = Lexer::new(token).skip(any_eq(' ').del().repeat());
+/// assert_eq!(
+///     lexer.lex("1 + 23"),
+///     Ok(vec![(Tok::Num(1), (0, 1)), (Tok::Plus, (2, 3)), (Tok::Num(23), (4, 6))])
+/// );
+/// assert_eq!(lexer.lex("1 - 2").unwrap_err().to_string(), "unexpected '-'");
+///
+/// let tokens = lexer.parse(&mut "1+2 ".chars().cursor()).unwrap();
+/// assert_eq!(tokens[2], (Tok::Num(2), (2, 3)));
+/// ```
+#[must_use = "Parsers are lazy and do nothing unless consumed"]
+#[derive(Debug, Clone, Copy)]
+pub struct Lexer<Par, Skip = NOP> {
+    token: Par,
+    skip: Skip,
+}
+
+/// A parser for a single token of a [Lexer], along with what is skipped before it.
+/// Fails at the end of the input, or on a token which could not be lexed.
+#[derive(Debug, Clone, Copy)]
+struct LexToken<Par, Skip> {
+    token: Par,
+    skip: Skip,
+}
+
+impl<Par> Lexer<Par> {
+    pub fn new(token: Par) -> Self {
+        Self { token, skip: NOP }
+    }
+}
+
+impl<Par, Skip> Lexer<Par, Skip> {
+    /// Runs `skip` before each token, discarding its output,
+    /// as for whitespace and comments
+    pub fn skip<Skip1>(self, skip: Skip1) -> Lexer<Par, Skip1> {
+        Lexer {
+            token: self.token,
+            skip,
+        }
+    }
+
+    /// Splits `source` into tokens, failing on the first one which
+    /// could not be parsed, or which was parsed without consuming anything
+    pub fn lex<'s, Tok>(
+        &mut self,
+        source: &'s str,
+    ) -> Result<Vec<(Tok, Span)>, ParseError<char, Span>>
+    where
+        Self: ParseMut<Source<'s>, Output = Result<Vec<(Tok, Span)>, ParseError<char, Span>>>,
+    {
+        self.parse_mut(&mut source.chars().cursor())
+    }
+}
+
+#[parser_fn]
+fn lex_token<par, skip, Tok>(self: &LexToken<par, skip>) -> Option<(Tok, INPUT::Span)>
+where
+    INPUT: StreamOffset + StreamSpan,
+    par::Output: Report<INPUT::Item, INPUT::Span, Value = Tok>,
+{
+    parse![self.skip];
+    if input.is_exhausted() {
+        return None;
+    }
+    let offset = input.offset();
+    let start = input.span_offset();
+    let token = parse![self.token].report().ok()?;
+    (input.offset() > offset).then(|| (token, input.span_since(start)))
+}
+
+#[parser_fn(mut in move)]
+fn lexer<par, skip, Tok>(
+    mut self: &Lexer<par, skip>,
+) -> Result<Vec<(Tok, INPUT::Span)>, ParseError<INPUT::Item, INPUT::Span>>
+where
+    INPUT: StreamOffset + StreamSpan,
+    par::Output: Report<INPUT::Item, INPUT::Span, Value = Tok>,
+{
+    let tokens = {
+        let token = LexToken {
+            token: parser![self.token],
+            skip: parser![self.skip],
+        };
+        token.repeat().to_vec().parse_once(input).value()
+    };
+
+    parse![self.skip];
+    if input.is_exhausted() {
+        return Ok(tokens);
+    }
+    // the tokens stopped early, so the next one is lexed again for its error
+    let start = input.span_offset();
+    let save_state = input.savestate();
+    Err(match parse![self.token].report() {
+        Err(Some(error)) => error,
+        _ => {
+            input.backtrack(save_state);
+            let found = input.next();
+            ParseError::new(input.span_since(start), found)
+        }
+    })
+}
+
+/// An error of either phase of [lex_then_parse], located in the source text
+#[derive(Debug, Clone, PartialEq)]
+pub enum PipelineError<Tok> {
+    Lex(ParseError<char, Span>),
+    Parse(ParseError<Tok, Span>),
+}
+
+impl<Tok> PipelineError<Tok> {
+    pub fn span(&self) -> &Span {
+        match self {
+            PipelineError::Lex(error) => error.span(),
+            PipelineError::Parse(error) => error.span(),
+        }
+    }
+}
+
+impl<Tok: Debug> Display for PipelineError<Tok> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PipelineError::Lex(error) => Display::fmt(error, f),
+            PipelineError::Parse(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl<Tok: Debug> std::error::Error for PipelineError<Tok> {}
+
+/// Splits `source` into `tokens` through `lexer`, then runs `parser` on them,
+/// expecting it to consume every token.
+///
+/// The parser is given the tokens as a [Tokens] stream, whose spans are the
+/// ones of the tokens, so the errors it reports are located in the source text,
+/// as the ones of the lexer, the end of the input being where the last token ends.
+/// The tokens are kept in `tokens`, for the output to borrow from them.
+/// If the parser fails without reporting an error, the token it stopped at is reported.
+///
+/// # Examples
+/// Basic usage:
+///```
+/// use lavan::parser::lexer::PipelineError;
+/// use lavan::prelude::*;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// enum Tok {
+///     Num(u32),
+///     Plus,
+/// }
+///
+/// fn sum(source: &str) -> Result<u32, PipelineError<Tok>> {
+///     let num = |digits: &str| Tok::Num(digits.parse().unwrap());
+///     let plus = || Tok::Plus;
+///     let token = any_if(char::is_ascii_digit)
+///         .del()
+///         .repeat_min(1)
+///         .slice()
+///         .map(num)
+///         .or(any_eq('+').del().sel(plus));
+///     let mut lexer = Lexer::new(token).skip(any_eq(' ').del().repeat());
+///
+///     let number = || select! { Tok::Num(n) => n }.label("number");
+///     let sum = number()
+///         .and(just(Tok::Plus).label("'+'"))
+///         .and(number())
+///         .map(|((lhs, _), rhs)| lhs + rhs);
+///
+///     lex_then_parse(source, &mut lexer, &mut vec![], sum)
+/// }
+///
+/// assert_eq!(sum("1 + 23"), Ok(24));
+///
+/// let error = sum("1 + + 2").unwrap_err();
+/// assert_eq!(error.to_string(), "expected number, found Plus");
+/// assert_eq!(error.span(), &(4, 5));
+///
+/// let error = sum("1 + 2 3").unwrap_err();
+/// assert_eq!(error.to_string(), "expected end of input, found Num(3)");
+/// assert_eq!(error.span(), &(6, 7));
+///
+/// let error = sum("1 +").unwrap_err();
+/// assert_eq!(error.to_string(), "expected number, found end of input");
+/// assert_eq!(error.span(), &(3, 3));
+///
+/// let error = sum("1 - 2").unwrap_err();
+/// assert_eq!(error.to_string(), "unexpected '-'");
+/// assert_eq!(error.span(), &(2, 3));
+/// ```
+pub fn lex_then_parse<'s, 't, Lex, Skip, Tok, Par, Val>(
+    source: &'s str,
+    lexer: &mut Lexer<Lex, Skip>,
+    tokens: &'t mut Vec<(Tok, Span)>,
+    parser: Par,
+) -> Result<Val, PipelineError<Tok>>
+where
+    Lexer<Lex, Skip>:
+        ParseMut<Source<'s>, Output = Result<Vec<(Tok, Span)>, ParseError<char, Span>>>,
+    Tok: Clone + PartialEq,
+    Par: ParseOnce<Tokens<'t, Tok, Span>>,
+    Par::Output: Report<Tok, Span, Value = Val>,
+{
+    *tokens = lexer.lex(source).map_err(PipelineError::Lex)?;
+    let mut input = Tokens::new(tokens);
+
+    let error = match parser.parse_once(&mut input).report() {
+        Ok(value) if input.is_exhausted() => return Ok(value),
+        Ok(_) => unexpected(&mut input).with_expected(Expected::EndOfInput),
+        Err(Some(error)) => error,
+        Err(None) => unexpected(&mut input),
+    };
+    Err(PipelineError::Parse(error))
+}
+
+/// An error at the next token of `input`
+fn unexpected<Tok, Span>(input: &mut Tokens<'_, Tok, Span>) -> ParseError<Tok, Span>
+where
+    Tok: Clone,
+    Span: JoinSpan,
+{
+    let start = input.span_offset();
+    let found = input.next();
+    ParseError::new(input.span_since(start), found)
+}
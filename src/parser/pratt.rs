@@ -0,0 +1,290 @@
+use crate::parser::prelude::internal::*;
+
+/// Parses an operand, with the binding power of the operator before it
+type Operand<'r, I, Expr, Err> = &'r mut dyn FnMut(&mut I) -> Result<Expr, Err>;
+
+/// Parses a prefix operator and its operand,
+/// returning [None] without consuming anything if the operator is not found
+type PrefixFn<'a, I, Expr, Err> =
+    Box<dyn 'a + Fn(&mut I, Operand<'_, I, Expr, Err>) -> Option<Result<Expr, Err>>>;
+
+/// Parses an infix operator and its right operand,
+/// returning the left operand back without consuming anything if the operator is not found
+type InfixFn<'a, I, Expr, Err> =
+    Box<dyn 'a + Fn(&mut I, Expr, Operand<'_, I, Expr, Err>) -> Result<Result<Expr, Err>, Expr>>;
+
+/// Parses a postfix operator,
+/// returning the operand back without consuming anything if the operator is not found
+type PostfixFn<'a, I, Expr> = Box<dyn 'a + Fn(&mut I, Expr) -> Result<Expr, Expr>>;
+
+/// Creates an expression parser, through precedence climbing over `atom`
+///
+/// Operators are added with their precedence, higher ones binding tighter.
+/// The output is the one of `atom`, so a failing operand after an operator
+/// fails the whole expression with the error of `atom`.
+/// Operators which are not found are backtracked, ending the expression.
+///
+/// # Examples
+/// Basic usage:
+///```
+/// use lavan::prelude::*;
+///
+/// fn digit(c: char) -> i64 {
+///     c.to_digit(10).unwrap().into()
+/// }
+///
+/// let expr = pratt(any_if(char::is_ascii_digit).map(digit))
+///     .infix_left(any_eq('+'), 1, |lhs, _, rhs| lhs + rhs)
+///     .infix_left(any_eq('-'), 1, |lhs, _, rhs| lhs - rhs)
+///     .infix_left(any_eq('*'), 2, |lhs, _, rhs| lhs * rhs)
+///     .infix_right(any_eq('^'), 3, |lhs, _, rhs| lhs.pow(rhs as u32))
+///     .prefix(any_eq('-'), 4, |_, operand| -operand)
+///     .postfix(any_eq('!'), 5, |operand, _| (1..=operand).product());
+///
+/// assert_eq!(expr.parse(&mut "1+2*3-4".chars()), Some(3));
+/// assert_eq!(expr.parse(&mut "2^3^2".chars()), Some(512));
+/// assert_eq!(expr.parse(&mut "-3!-1".chars()), Some(-7));
+/// assert_eq!(expr.parse(&mut "1+".chars()), None);
+/// ```
+///
+/// Errors of the operands propagate as they are:
+///```
+/// use lavan::prelude::*;
+///
+/// fn digit(c: char) -> i64 {
+///     c.to_digit(10).unwrap().into()
+/// }
+///
+/// let number = any_if(char::is_ascii_digit).map(digit).label("number");
+/// let expr = pratt(number).infix_left(any_eq('+'), 1, |lhs, _, rhs| lhs + rhs);
+///
+/// let error = expr.parse(&mut "1+*".chars().cursor()).into_result().unwrap_err();
+/// assert_eq!(error.to_string(), "expected number, found '*'");
+/// assert_eq!(error.span(), &(2, 3));
+/// ```
+pub fn pratt<'a, Atom, I, Expr, Err>(atom: Atom) -> Pratt<'a, Atom, I, Expr, Err>
+where
+    I: Stream,
+    Atom: ParseOnce<I>,
+    Atom::Output: Response<Value = Expr, Error = Err>,
+{
+    Pratt {
+        atom,
+        operators: Operators {
+            prefix: Vec::new(),
+            infix: Vec::new(),
+            postfix: Vec::new(),
+        },
+    }
+}
+
+/// A parser for expressions of prefix, infix and postfix operators
+///
+/// This `struct` is created by the [`pratt`] method on [`pratt`](crate::parser::pratt).
+/// See its documentation for more.
+#[must_use = "Parsers are lazy and do nothing unless consumed"]
+pub struct Pratt<'a, Atom, I, Expr, Err> {
+    atom: Atom,
+    operators: Operators<'a, I, Expr, Err>,
+}
+
+struct Operators<'a, I, Expr, Err> {
+    /// with their right binding power
+    prefix: Vec<(u32, PrefixFn<'a, I, Expr, Err>)>,
+    /// with their left and right binding powers
+    infix: Vec<(u32, u32, InfixFn<'a, I, Expr, Err>)>,
+    /// with their left binding power
+    postfix: Vec<(u32, PostfixFn<'a, I, Expr>)>,
+}
+
+/// Shows the binding powers of the operators, as their parsers are closures
+impl<Atom, I, Expr, Err> std::fmt::Debug for Pratt<'_, Atom, I, Expr, Err>
+where
+    Atom: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Operators {
+            prefix,
+            infix,
+            postfix,
+        } = &self.operators;
+        f.debug_struct("Pratt")
+            .field("atom", &self.atom)
+            .field(
+                "prefix",
+                &prefix.iter().map(|(right, _)| right).collect::<Vec<_>>(),
+            )
+            .field(
+                "infix",
+                &infix
+                    .iter()
+                    .map(|(left, right, _)| (left, right))
+                    .collect::<Vec<_>>(),
+            )
+            .field(
+                "postfix",
+                &postfix.iter().map(|(left, _)| left).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl<'a, Atom, I, Expr, Err> Pratt<'a, Atom, I, Expr, Err>
+where
+    I: Stream,
+{
+    /// Adds a left-associative infix operator, folding `lhs op rhs` into `fold(lhs, op, rhs)`
+    pub fn infix_left<Op, Fun>(self, op: Op, precedence: u16, fold: Fun) -> Self
+    where
+        Op: 'a + Parse<I>,
+        Fun: 'a + Fn(Expr, <Op::Output as Response>::Value, Expr) -> Expr,
+    {
+        self.infix(op, power(precedence), power(precedence) + 1, fold)
+    }
+
+    /// Adds a right-associative infix operator, folding `lhs op rhs` into `fold(lhs, op, rhs)`
+    pub fn infix_right<Op, Fun>(self, op: Op, precedence: u16, fold: Fun) -> Self
+    where
+        Op: 'a + Parse<I>,
+        Fun: 'a + Fn(Expr, <Op::Output as Response>::Value, Expr) -> Expr,
+    {
+        self.infix(op, power(precedence) + 1, power(precedence), fold)
+    }
+
+    /// Adds a prefix operator, folding `op operand` into `fold(op, operand)`
+    pub fn prefix<Op, Fun>(mut self, op: Op, precedence: u16, fold: Fun) -> Self
+    where
+        Op: 'a + Parse<I>,
+        Fun: 'a + Fn(<Op::Output as Response>::Value, Expr) -> Expr,
+    {
+        let step = move |input: &mut I, operand: Operand<'_, I, Expr, Err>| {
+            let save_state = input.savestate();
+            match op.parse(input).control_flow() {
+                Continue(op) => Some(operand(input).map(|operand| fold(op, operand))),
+                Break(_) => {
+                    input.backtrack(save_state);
+                    None
+                }
+            }
+        };
+        self.operators
+            .prefix
+            .push((power(precedence), Box::new(step)));
+        self
+    }
+
+    /// Adds a postfix operator, folding `operand op` into `fold(operand, op)`
+    pub fn postfix<Op, Fun>(mut self, op: Op, precedence: u16, fold: Fun) -> Self
+    where
+        Op: 'a + Parse<I>,
+        Fun: 'a + Fn(Expr, <Op::Output as Response>::Value) -> Expr,
+    {
+        let step = move |input: &mut I, operand: Expr| {
+            let save_state = input.savestate();
+            match op.parse(input).control_flow() {
+                Continue(op) => Ok(fold(operand, op)),
+                Break(_) => {
+                    input.backtrack(save_state);
+                    Err(operand)
+                }
+            }
+        };
+        self.operators
+            .postfix
+            .push((power(precedence), Box::new(step)));
+        self
+    }
+
+    fn infix<Op, Fun>(mut self, op: Op, left: u32, right: u32, fold: Fun) -> Self
+    where
+        Op: 'a + Parse<I>,
+        Fun: 'a + Fn(Expr, <Op::Output as Response>::Value, Expr) -> Expr,
+    {
+        let step = move |input: &mut I, lhs: Expr, rhs: Operand<'_, I, Expr, Err>| {
+            let save_state = input.savestate();
+            match op.parse(input).control_flow() {
+                Continue(op) => Ok(rhs(input).map(|rhs| fold(lhs, op, rhs))),
+                Break(_) => {
+                    input.backtrack(save_state);
+                    Err(lhs)
+                }
+            }
+        };
+        self.operators.infix.push((left, right, Box::new(step)));
+        self
+    }
+}
+
+/// The binding power of an operator of `precedence`, leaving room for
+/// one more on the side of its associativity, without overflowing
+fn power(precedence: u16) -> u32 {
+    2 * u32::from(precedence)
+}
+
+impl<'a, I, Expr, Err> Operators<'a, I, Expr, Err>
+where
+    I: Stream,
+{
+    /// Parses an expression whose operators bind at least as tight as `min_power`
+    fn parse_expr(
+        &self,
+        input: &mut I,
+        min_power: u32,
+        atom: &mut dyn FnMut(&mut I) -> Result<Expr, Err>,
+    ) -> Result<Expr, Err> {
+        let mut lhs = 'prefix: {
+            for (power, step) in &self.prefix {
+                let mut operand = |input: &mut I| self.parse_expr(input, *power, atom);
+                if let Some(expr) = step(input, &mut operand) {
+                    break 'prefix expr?;
+                }
+            }
+            atom(input)?
+        };
+
+        'fold: loop {
+            for (power, step) in &self.postfix {
+                if *power < min_power {
+                    continue;
+                }
+                match step(input, lhs) {
+                    Ok(expr) => {
+                        lhs = expr;
+                        continue 'fold;
+                    }
+                    Err(operand) => lhs = operand,
+                }
+            }
+            for (left, right, step) in &self.infix {
+                if *left < min_power {
+                    continue;
+                }
+                let mut rhs = |input: &mut I| self.parse_expr(input, *right, atom);
+                match step(input, lhs, &mut rhs) {
+                    Ok(expr) => {
+                        lhs = expr?;
+                        continue 'fold;
+                    }
+                    Err(operand) => lhs = operand,
+                }
+            }
+            return Ok(lhs);
+        }
+    }
+}
+
+#[parser_fn(mut in move)]
+fn pratt<'a, atom, Expr, Err>(mut self: &Pratt<'a, atom, INPUT, Expr, Err>) -> atom::Output
+where
+    atom::Output: Response<Value = Expr, Error = Err>,
+{
+    let operators = &self.operators;
+    let mut atom = |input: &mut INPUT| match parser![self.atom].parse_once(input).control_flow() {
+        Continue(expr) => Ok(expr),
+        Break(error) => Err(error),
+    };
+    match operators.parse_expr(input, 0, &mut atom) {
+        Ok(expr) => atom::Output::from_value(expr),
+        Err(error) => atom::Output::from_error(error),
+    }
+}
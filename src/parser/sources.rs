@@ -1,4 +1,5 @@
 use super::prelude::internal::*;
+use crate::util::char_class::CharClass;
 
 pub use functions::*;
 
@@ -56,7 +57,420 @@ pub mod functions {
         src(AnyEq(v))
     }
 
-    // TODO: Documentation
+    /// Skips tokens until one is equal to `v`, consuming it,
+    /// and returns the value of `fallback` as a placeholder.
+    /// Fails if the end of the input is reached first.
+    ///
+    /// Meant as a strategy for [recover_with](crate::parser::traits::ParseOnce::recover_with).
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// let mut input = "1 + ; 2".chars();
+    /// assert_eq!(skip_until(';', || 0).parse_once(&mut input), Some(0));
+    /// assert_eq!(input.as_str(), " 2");
+    /// assert_eq!(skip_until(';', || 0).parse_once(&mut input), None);
+    /// ```
+    pub fn skip_until<Rhs, Fun, Val, I: Stream>(
+        v: Rhs,
+        fallback: Fun,
+    ) -> Src<SkipUntil<Rhs, Fun>, I>
+    where
+        I::Item: PartialEq<Rhs>,
+        Fun: Fn() -> Val,
+    {
+        src(SkipUntil(v, fallback))
+    }
+
+    /// Expects an `open` token and skips tokens until its balancing `close` token,
+    /// consuming it, and returns the value of `fallback` as a placeholder.
+    /// Fails if the end of the input is reached first.
+    ///
+    /// Meant as a strategy for [recover_with](crate::parser::traits::ParseOnce::recover_with).
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// let mut input = "(1 + (2 *)) + 3".chars();
+    /// let nested = recover_nested('(', ')', || 0);
+    /// assert_eq!(nested.parse_once(&mut input), Some(0));
+    /// assert_eq!(input.as_str(), " + 3");
+    /// ```
+    pub fn recover_nested<Rhs, Fun, Val, I: Stream>(
+        open: Rhs,
+        close: Rhs,
+        fallback: Fun,
+    ) -> Src<RecoverNested<Rhs, Fun>, I>
+    where
+        I::Item: PartialEq<Rhs>,
+        Fun: Fn() -> Val,
+    {
+        src(RecoverNested {
+            open,
+            close,
+            fallback,
+        })
+    }
+
+    /// Uses `parser` as a recovery strategy, its value being the placeholder.
+    ///
+    /// Meant as a strategy for [recover_with](crate::parser::traits::ParseOnce::recover_with).
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// let mut input = "x1".chars().cursor().recovering();
+    /// let digit = any_if(char::is_ascii_digit)
+    ///     .expect(Expected::Label("digit"))
+    ///     .recover_with(recover_via(any().map(|_| '0')));
+    ///
+    /// assert_eq!(digit.parse_once(&mut input).into_result(), Ok('0'));
+    /// assert_eq!(input.errors()[0].to_string(), "expected digit, found 'x'");
+    /// ```
+    pub fn recover_via<Par, I: Stream>(parser: Par) -> Src<Par, I>
+    where
+        Par: ParseOnce<I>,
+    {
+        src(parser)
+    }
+
+    /// Commits to the current offset of the stream, see [StreamCut]
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// let mut input = ReaderStream::bytes(&b"key=value"[..]);
+    /// let key = any_ne(b'=').del().repeat().slice();
+    /// let key = key.and(any_eq(b'=').del()).and(commit()).parse_once(&mut input);
+    /// assert_eq!(key, Some(b"key".to_vec()));
+    /// assert_eq!(input.buffered(), "value".len());
+    /// ```
+    ///
+    /// Failing after a commit only rewinds to the commit:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// let mut input = ReaderStream::bytes(&b"a=1;b=2"[..]);
+    /// let key = any_ne(b'=').del().repeat().slice();
+    /// let value = any_ne(b';').del().repeat().slice();
+    /// let pair = key.and(any_eq(b'=').del()).and(commit());
+    /// let pair = pair.and(value).and(any_eq(b';').del());
+    ///
+    /// let pairs = pair.repeat().to_vec().parse_once(&mut input);
+    /// assert_eq!(pairs.value(), [(b"a".to_vec(), b"1".to_vec())]);
+    /// assert_eq!(input.offset(), "a=1;b=".len());
+    /// ```
+    pub fn commit<I: StreamCut>() -> Src<Commit, I> {
+        src(Commit)
+    }
+
+    /// Skips the trivia at the current offset of the stream, see [StreamTrim]
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::input::adapters::trimming::Trivia;
+    /// use lavan::prelude::*;
+    ///
+    /// let mut input = " /* a comment */ 1".chars().trimming(Trivia::default());
+    /// let one = trim().and(any_eq('1')).parse_once(&mut input);
+    /// assert_eq!(one, Some('1'));
+    /// ```
+    pub fn trim<I: StreamTrim>() -> Src<Trim, I> {
+        src(Trim)
+    }
+
+    /// Expects the longest of `words`, returning it
+    ///
+    /// Unlike trying each word in turn, the order of `words` does not matter.
+    /// Nothing is consumed if none of them is there.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// let op = one_of_words(&["=", "==", "=>"]);
+    /// assert_eq!(op.parse(&mut "==x".chars()), Some("=="));
+    /// assert_eq!(op.parse(&mut "=>x".chars()), Some("=>"));
+    /// assert_eq!(op.parse(&mut "=x".chars()), Some("="));
+    ///
+    /// let mut input = "!=".chars();
+    /// assert_eq!(op.parse(&mut input), None);
+    /// assert_eq!(input.as_str(), "!=");
+    /// ```
+    pub fn one_of_words<'a, I>(words: &[&'a str]) -> Src<Words<&'a str>, I>
+    where
+        I: Stream<Item = char>,
+    {
+        src(Words::new(words.iter().map(|word| (*word, *word)), false))
+    }
+
+    /// Expects the longest of the words of `table`, returning its associated value
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq)]
+    /// enum Op {
+    ///     Lt,
+    ///     Le,
+    ///     Shl,
+    /// }
+    ///
+    /// let op = word_table([("<", Op::Lt), ("<=", Op::Le), ("<<", Op::Shl)]);
+    /// let ops = op.repeat_eoi().to_vec();
+    /// assert_eq!(ops.parse(&mut "<<<=".chars()), Some(vec![Op::Shl, Op::Le]));
+    /// ```
+    pub fn word_table<'a, Val, I>(
+        table: impl IntoIterator<Item = (&'a str, Val)>,
+    ) -> Src<Words<Val>, I>
+    where
+        I: Stream<Item = char>,
+        Val: Clone,
+    {
+        src(Words::new(table, false))
+    }
+
+    /// Expects `word` as a whole word, not followed by an identifier character,
+    /// returning it
+    ///
+    /// Nothing is consumed if the keyword is not there. Along with
+    /// [lexeme](crate::parser::traits::ParseOnce::lexeme), it also skips the trivia after it.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::input::adapters::trimming::Trivia;
+    /// use lavan::prelude::*;
+    ///
+    /// assert_eq!(keyword("if").parse(&mut "if(x)".chars()), Some("if"));
+    ///
+    /// let mut input = "iffy".chars();
+    /// assert_eq!(keyword("if").parse(&mut input), None);
+    /// assert_eq!(input.as_str(), "iffy");
+    ///
+    /// let ident = utf::identifier().lexeme();
+    /// let binding = keyword("let").lexeme().and(ident).and(any_eq('=').del().padded());
+    ///
+    /// let mut input = "let  /* name */ x = 1".chars().trimming(Trivia::default());
+    /// assert_eq!(binding.parse_once(&mut input), Some(("let", "x")));
+    /// assert_eq!(input.next(), Some('1'));
+    /// ```
+    pub fn keyword<I>(word: &str) -> Src<Words<&str>, I>
+    where
+        I: Stream<Item = char>,
+    {
+        src(Words::new([(word, word)], true))
+    }
+
+    /// Expects the longest of `words` as a whole word, not followed by an
+    /// identifier character, returning it
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// let kw = keywords(&["in", "int", "if"]);
+    /// assert_eq!(kw.parse(&mut "int x".chars()), Some("int"));
+    /// assert_eq!(kw.parse(&mut "in(x)".chars()), Some("in"));
+    /// assert_eq!(kw.parse(&mut "iffy".chars()), None);
+    /// assert_eq!(kw.parse(&mut "into".chars()), None);
+    /// ```
+    pub fn keywords<'a, I>(words: &[&'a str]) -> Src<Words<&'a str>, I>
+    where
+        I: Stream<Item = char>,
+    {
+        src(Words::new(words.iter().map(|word| (*word, *word)), true))
+    }
+
+    /// Expects the longest of the words of `table` as a whole word, not followed
+    /// by an identifier character, returning its associated value
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq)]
+    /// enum Kw {
+    ///     If,
+    ///     Else,
+    /// }
+    ///
+    /// let kw = keyword_table([("if", Kw::If), ("else", Kw::Else)]);
+    /// assert_eq!(kw.parse(&mut "else{".chars()), Some(Kw::Else));
+    /// assert_eq!(kw.parse(&mut "elsewhere".chars()), None);
+    /// ```
+    pub fn keyword_table<'a, Val, I>(
+        table: impl IntoIterator<Item = (&'a str, Val)>,
+    ) -> Src<Words<Val>, I>
+    where
+        I: Stream<Item = char>,
+        Val: Clone,
+    {
+        src(Words::new(table, true))
+    }
+
+    /// Expects any of the `char`s of `chars`
+    ///
+    /// Nothing is consumed if the next `char` is not one of them.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// let op = one_of("+-*/");
+    /// assert_eq!(op.parse(&mut "*2".chars()), Some('*'));
+    /// assert_eq!(op.parse(&mut "%2".chars()), None);
+    /// ```
+    pub fn one_of<I>(chars: &str) -> Src<CharClass, I>
+    where
+        I: Stream<Item = char>,
+    {
+        src(CharClass::chars(chars))
+    }
+
+    /// Expects any `char` other than the ones of `chars`
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// let unquoted = none_of("\"\\");
+    /// assert_eq!(unquoted.parse(&mut "a\"".chars()), Some('a'));
+    /// assert_eq!(unquoted.parse(&mut "\"a".chars()), None);
+    /// ```
+    pub fn none_of<I>(chars: &str) -> Src<CharClass, I>
+    where
+        I: Stream<Item = char>,
+    {
+        src(!CharClass::chars(chars))
+    }
+
+    /// Expects any `char` within `range`
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// let octal = range('0'..='7');
+    /// assert_eq!(octal.parse(&mut "7".chars()), Some('7'));
+    /// assert_eq!(octal.parse(&mut "8".chars()), None);
+    /// ```
+    pub fn range<I>(range: std::ops::RangeInclusive<char>) -> Src<CharClass, I>
+    where
+        I: Stream<Item = char>,
+    {
+        src(CharClass::range(range))
+    }
+
+    /// Takes the `char`s of `class` for as long as there are, returning their slice
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// let digits = take_while('0'..='9');
+    /// let Sure(number) = digits.parse(&mut "42a".chars());
+    /// assert_eq!(number, "42");
+    /// let Sure(number) = digits.parse(&mut "a".chars());
+    /// assert_eq!(number, "");
+    /// ```
+    pub fn take_while<I>(class: impl Into<CharClass>) -> Src<TakeWhile, I>
+    where
+        I: StreamSlice<Item = char>,
+    {
+        src(TakeWhile(class.into()))
+    }
+
+    /// Takes the `char`s of `class` for as long as there are, returning their slice,
+    /// but fails if there are none
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// let ident_start = CharClass::predicate(Predicate::Alphabetic) | '_'.into();
+    /// let ident_rest = ident_start.clone() | CharClass::range('0'..='9');
+    /// let ident = take_while1(ident_start)
+    ///     .del()
+    ///     .and(take_while(ident_rest).del())
+    ///     .slice();
+    ///
+    /// assert_eq!(ident.parse(&mut "snake_case2 = 1".chars()), Some("snake_case2"));
+    /// assert_eq!(ident.parse(&mut "2x".chars()), None);
+    /// ```
+    pub fn take_while1<I>(class: impl Into<CharClass>) -> Src<TakeWhile1, I>
+    where
+        I: StreamSlice<Item = char>,
+    {
+        src(TakeWhile1(class.into()))
+    }
+
+    /// Expects the next token to be equal to `token`.
+    /// This is the same as [any_eq], named after its use on [Tokens](crate::input::tokens::Tokens).
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// #[derive(Debug, Clone, PartialEq)]
+    /// enum Tok {
+    ///     Comma,
+    ///     Semicolon,
+    /// }
+    ///
+    /// let lexed = [(Tok::Comma, (0, 1)), (Tok::Semicolon, (1, 2))];
+    /// let mut input = tokens(&lexed);
+    /// assert_eq!(just(Tok::Comma).parse_once(&mut input), Some(Tok::Comma));
+    /// assert_eq!(just(Tok::Comma).parse_once(&mut input), None);
+    /// ```
+    pub fn just<Rhs, I: Stream>(token: Rhs) -> Src<AnyEq<Rhs>, I>
+    where
+        I::Item: PartialEq<Rhs>,
+    {
+        any_eq(token)
+    }
+
+    /// Takes exactly the next `size` items, as a slice.
+    /// Fails without consuming anything if the stream ends before.
+    ///
+    /// Since it stops after `size` items, a `&str` parser built on it, such as `"let"`,
+    /// also matches at the start of a longer word.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// let mut input = "letx".chars();
+    /// assert_eq!(take(3).parse_once(&mut input), Some("let"));
+    /// assert_eq!(input.as_str(), "x");
+    ///
+    /// assert_eq!(take(5).parse_once(&mut "letx".chars()), None);
+    ///
+    /// let mut input = "letx".chars();
+    /// assert!("let".parse_once(&mut input));
+    /// assert_eq!(input.as_str(), "x");
+    /// ```
     pub fn take<'a, I, Ref>(size: usize) -> Src<Take<'a>, I>
     where
         I: StreamSlice<Slice = &'a Ref>,
@@ -110,6 +524,205 @@ pub mod functions {
             }),
         })
     }
+
+    /// Creates a parser which can refer to itself, like [`recursive`], but
+    /// shared through an [`Arc`](std::sync::Arc) instead of an [`Rc`](std::rc::Rc).
+    ///
+    /// The resulting parser is [`Send`] and [`Sync`] as long as the parser
+    /// returned by `f` is, so a grammar can be built once and then used to
+    /// parse from several threads.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// fn deeper(depth: usize) -> usize {
+    ///     depth + 1
+    /// }
+    /// fn flat(_: &mut std::str::Chars) -> Option<usize> {
+    ///     Some(0)
+    /// }
+    ///
+    /// // nesting = '(' nesting ')' | ""
+    /// let nesting = recursive_sync::<Option<usize>, _, _, _>(|nesting| {
+    ///     nesting.delimited('(', ')').map(deeper).or(func(flat))
+    /// });
+    ///
+    /// std::thread::scope(|scope| {
+    ///     let shallow = scope.spawn(|| nesting.parse(&mut "()".chars()));
+    ///     let deep = scope.spawn(|| nesting.parse(&mut "((()))".chars()));
+    ///     assert_eq!(shallow.join().unwrap(), Some(1));
+    ///     assert_eq!(deep.join().unwrap(), Some(3));
+    /// });
+    /// ```
+    pub fn recursive_sync<'a, Out, Par, Fun, I>(f: Fun) -> Src<RecursiveSync<Par>, I>
+    where
+        I: Stream,
+        Par: 'a + Parse<I, Output = Out> + Send + Sync,
+        Fun: Fn(WeakSync<dyn 'a + Parse<I, Output = Out> + Send + Sync>) -> Par,
+    {
+        src(RecursiveSync {
+            parser: std::sync::Arc::new_cyclic(|weak| {
+                f(WeakSync {
+                    parser: weak.clone()
+                        as std::sync::Weak<dyn Parse<I, Output = Par::Output> + Send + Sync>,
+                })
+            }),
+        })
+    }
+
+    /// Declares a parser which is only defined later, so that several rules
+    /// can refer to each other, as in mutually recursive grammars.
+    ///
+    /// Rules refer to a declaration through its [`weak`](Declared::weak)
+    /// handle, and [`define`](Declared::define) fills it in once every rule
+    /// it depends upon has been declared. Parsing with a declaration which has
+    /// not been defined yet panics.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    /// use std::str::Chars;
+    ///
+    /// fn digit(c: char) -> u32 {
+    ///     c.to_digit(10).unwrap()
+    /// }
+    /// fn sum(values: Vec<u32>) -> u32 {
+    ///     values.into_iter().sum()
+    /// }
+    ///
+    /// // expr = digit | block
+    /// // block = '{' expr* '}'
+    /// let expr = declare::<Option<u32>, Chars>();
+    /// let block = declare::<Option<u32>, Chars>();
+    ///
+    /// expr.define(any_if(char::is_ascii_digit).map(digit).or(block.weak()));
+    /// block.define(
+    ///     expr.weak()
+    ///         .repeat()
+    ///         .collect::<Vec<_>>()
+    ///         .map(sum)
+    ///         .delimited('{', '}'),
+    /// );
+    ///
+    /// assert_eq!(expr.parse(&mut "7".chars()), Some(7));
+    /// assert_eq!(expr.parse(&mut "{1{23}4}".chars()), Some(10));
+    /// assert_eq!(block.parse(&mut "{1".chars()), None);
+    /// ```
+    pub fn declare<'a, Out, I>() -> Declared<dyn 'a + Parse<I, Output = Out>>
+    where
+        I: Stream,
+        Out: Response,
+    {
+        Declared {
+            parser: Default::default(),
+        }
+    }
+
+    /// Creates a parser which can refer to itself in left-recursive position,
+    /// such as in `expr = expr '+' term | term`.
+    ///
+    /// When the parser is re-entered through its handle at the same offset,
+    /// the last result obtained at that offset is returned, initially a failure
+    /// with a [Default] error. The parser is then re-run for as long as it
+    /// consumes more than before, growing the result from the shortest match.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// fn digit(c: char) -> i64 {
+    ///     c.to_digit(10).unwrap().into()
+    /// }
+    /// fn sub(((lhs, _), rhs): ((i64, char), i64)) -> i64 {
+    ///     lhs - rhs
+    /// }
+    ///
+    /// // expr = expr '-' digit | digit
+    /// let expr = left_rec::<Option<i64>, _, _, _>(|expr| {
+    ///     expr.and(any_eq('-'))
+    ///         .and(any_if(char::is_ascii_digit).map(digit))
+    ///         .map(sub)
+    ///         .or(any_if(char::is_ascii_digit).map(digit))
+    /// });
+    ///
+    /// // left-associative, as written
+    /// assert_eq!(expr.parse(&mut "9-3-2".chars()), Some(4));
+    /// assert_eq!(expr.parse(&mut "7".chars()), Some(7));
+    /// assert_eq!(expr.parse(&mut "-".chars()), None);
+    ///
+    /// // the failure it starts from is a default error, for responses that carry one
+    /// let digit = || any_if(char::is_ascii_digit).map(digit).expect(Expected::Label("digit"));
+    /// let expr = left_rec::<ParseResult<i64, char, (usize, usize)>, _, _, _>(|expr| {
+    ///     expr.and(any_eq('-').expect(Expected::Token('-')))
+    ///         .and(digit())
+    ///         .map(sub)
+    ///         .or(digit())
+    /// });
+    ///
+    /// let output = expr.parse(&mut "9-3-2".chars().cursor());
+    /// assert_eq!(output.into_result(), Ok(4));
+    /// let error = expr.parse(&mut "x".chars().cursor()).into_result().unwrap_err();
+    /// assert_eq!(error.to_string(), "expected digit, found 'x'");
+    /// ```
+    pub fn left_rec<'a, Out, Par, Fun, I>(f: Fun) -> Src<LeftRec<SeedGrowing<I, Out, Par>>, I>
+    where
+        I: StreamOffset<SaveState: Clone>,
+        Out: Response<Error: Default> + Clone,
+        Par: 'a + Parse<I, Output = Out>,
+        Fun: FnOnce(LeftRecWeak<SeedGrowing<I, Out, dyn 'a + Parse<I, Output = Out>>>) -> Par,
+    {
+        src(LeftRec {
+            parser: std::rc::Rc::new_cyclic(|weak| {
+                let parser = f(LeftRecWeak {
+                    parser: weak.clone()
+                        as std::rc::Weak<SeedGrowing<I, Out, dyn Parse<I, Output = Out>>>,
+                });
+                SeedGrowing {
+                    seeds: Default::default(),
+                    parser,
+                }
+            }),
+        })
+    }
+}
+
+/// Extracts a value from the next token through pattern matching,
+/// failing if none of the patterns match
+///
+/// Arms are written as in a `match`, guards included.
+///
+/// # Examples
+/// Basic usage:
+///```
+/// use lavan::prelude::*;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// enum Tok {
+///     Ident(&'static str),
+///     Int(i64),
+/// }
+///
+/// let lexed = [(Tok::Int(-1), (0, 2)), (Tok::Int(1), (3, 4)), (Tok::Ident("x"), (5, 6))];
+/// let mut input = tokens(&lexed);
+/// let natural = || select! { Tok::Int(n) if n >= 0 => n as u64 };
+///
+/// assert_eq!(natural().parse_once(&mut input), None);
+/// assert_eq!(natural().parse_once(&mut input), Some(1));
+/// assert_eq!(natural().parse_once(&mut input), None);
+/// ```
+#[macro_export]
+macro_rules! select {
+    ($($pattern:pat $(if $guard:expr)? => $value:expr),+ $(,)?) => {
+        $crate::parser::sources::any_then(move |token| match token {
+            $($pattern $(if $guard)? => ::std::option::Option::Some($value),)+
+            #[allow(unreachable_patterns)]
+            _ => ::std::option::Option::None,
+        })
+    };
 }
 
 pub mod adapters {
@@ -202,6 +815,123 @@ pub mod adapters {
     /// See its documentation for more.
     pub type AnyNe<Item> = AnyEq<Item, true>;
 
+    /// A parser for skipping tokens until one is equal to the provided value
+    ///
+    /// This `struct` is created by the [`skip_until`] method on [`sources`](crate::parser::sources).
+    /// See its documentation for more.
+    #[must_use = "Parsers are lazy and do nothing unless consumed"]
+    #[derive(Debug, Clone, Copy)]
+    pub struct SkipUntil<Item, Fun>(pub(crate) Item, pub(crate) Fun);
+
+    /// A parser for skipping a balanced group of delimited tokens
+    ///
+    /// This `struct` is created by the [`recover_nested`] method on [`sources`](crate::parser::sources).
+    /// See its documentation for more.
+    #[must_use = "Parsers are lazy and do nothing unless consumed"]
+    #[derive(Debug, Clone, Copy)]
+    pub struct RecoverNested<Item, Fun> {
+        pub(crate) open: Item,
+        pub(crate) close: Item,
+        pub(crate) fallback: Fun,
+    }
+
+    /// A parser for committing to the current offset of the stream
+    ///
+    /// This `struct` is created by the [`commit`] method on [`sources`](crate::parser::sources).
+    /// See its documentation for more.
+    #[must_use = "Parsers are lazy and do nothing unless consumed"]
+    #[non_exhaustive]
+    #[derive(Debug, Clone, Copy)]
+    pub struct Commit;
+
+    /// A parser for skipping the trivia at the current offset of the stream
+    ///
+    /// This `struct` is created by the [`trim`] method on [`sources`](crate::parser::sources).
+    /// See its documentation for more.
+    #[must_use = "Parsers are lazy and do nothing unless consumed"]
+    #[non_exhaustive]
+    #[derive(Debug, Clone, Copy)]
+    pub struct Trim;
+
+    /// A parser for expecting the longest of a set of words, as a trie
+    ///
+    /// This `struct` is created by the [`one_of_words`], [`word_table`], [`keyword`],
+    /// [`keywords`] and [`keyword_table`] methods on [`sources`](crate::parser::sources).
+    /// See their documentation for more.
+    #[must_use = "Parsers are lazy and do nothing unless consumed"]
+    #[derive(Debug, Clone)]
+    pub struct Words<Val> {
+        pub(crate) nodes: Vec<WordsNode<Val>>,
+        /// Whether the words must not be followed by an identifier character
+        pub(crate) boundary: bool,
+    }
+
+    /// A parser for taking `char`s of a [CharClass], returning their slice
+    ///
+    /// This `struct` is created by the [`take_while`] method on [`sources`](crate::parser::sources).
+    /// See its documentation for more.
+    #[must_use = "Parsers are lazy and do nothing unless consumed"]
+    #[derive(Debug, Clone)]
+    pub struct TakeWhile(pub(crate) CharClass);
+
+    /// A parser for taking at least one `char` of a [CharClass], returning their slice
+    ///
+    /// This `struct` is created by the [`take_while1`] method on [`sources`](crate::parser::sources).
+    /// See its documentation for more.
+    #[must_use = "Parsers are lazy and do nothing unless consumed"]
+    #[derive(Debug, Clone)]
+    pub struct TakeWhile1(pub(crate) CharClass);
+
+    #[derive(Debug, Clone)]
+    pub(crate) struct WordsNode<Val> {
+        /// Sorted by `char`, for binary search
+        pub(crate) children: Vec<(char, usize)>,
+        pub(crate) value: Option<Val>,
+    }
+
+    impl<Val> Words<Val> {
+        pub(crate) fn new<'a>(
+            table: impl IntoIterator<Item = (&'a str, Val)>,
+            boundary: bool,
+        ) -> Self {
+            let mut nodes = vec![WordsNode {
+                children: vec![],
+                value: None,
+            }];
+
+            for (word, value) in table {
+                let mut node = 0;
+                for c in word.chars() {
+                    node = match nodes[node].child(c) {
+                        Ok(child) => child,
+                        Err(idx) => {
+                            let child = nodes.len();
+                            nodes[node].children.insert(idx, (c, child));
+                            nodes.push(WordsNode {
+                                children: vec![],
+                                value: None,
+                            });
+                            child
+                        }
+                    };
+                }
+                // the last of duplicated words wins, as in a map
+                nodes[node].value = Some(value);
+            }
+
+            Self { nodes, boundary }
+        }
+    }
+
+    impl<Val> WordsNode<Val> {
+        /// The index of the child for `c`, or where to insert it
+        pub(crate) fn child(&self, c: char) -> Result<usize, usize> {
+            self.children
+                .binary_search_by_key(&c, |(c, _)| *c)
+                .map(|idx| self.children[idx].1)
+        }
+    }
+
     /// A parser for taking a provided amount of tokens,
     /// returning a stream slice starting from the current offset
     ///
@@ -263,11 +993,121 @@ pub mod adapters {
     pub struct Weak<Par: ?Sized> {
         pub(crate) parser: std::rc::Weak<Par>,
     }
+
+    /// A thread-safe parser which can refer to itself
+    ///
+    /// This `struct` is created by the [`recursive_sync`] method on [`sources`](crate::parser::sources).
+    /// See its documentation for more.
+    #[must_use = "Parsers are lazy and do nothing unless consumed"]
+    #[derive(Debug, Clone)]
+    pub struct RecursiveSync<Par: ?Sized> {
+        pub(crate) parser: std::sync::Arc<Par>,
+    }
+
+    /// A handle of a [RecursiveSync] parser, to refer to it from within itself
+    ///
+    /// This `struct` is created by the [`recursive_sync`] method on [`sources`](crate::parser::sources).
+    /// See its documentation for more.
+    #[must_use = "Parsers are lazy and do nothing unless consumed"]
+    #[derive(Debug, Clone)]
+    pub struct WeakSync<Par: ?Sized> {
+        pub(crate) parser: std::sync::Weak<Par>,
+    }
+
+    /// A parser declared ahead of its definition
+    ///
+    /// This `struct` is created by the [`declare`] method on [`sources`](crate::parser::sources).
+    /// See its documentation for more.
+    #[must_use = "Parsers are lazy and do nothing unless consumed"]
+    #[derive(Debug, Clone)]
+    pub struct Declared<Par: ?Sized> {
+        pub(crate) parser: std::rc::Rc<std::cell::OnceCell<Box<Par>>>,
+    }
+
+    impl<'a, I, Out> Declared<dyn 'a + Parse<I, Output = Out>>
+    where
+        I: Stream,
+        Out: Response,
+    {
+        /// Defines the declared parser
+        ///
+        /// # Panics
+        /// Panics if the parser has already been defined.
+        pub fn define<Par>(&self, parser: Par)
+        where
+            Par: 'a + Parse<I, Output = Out>,
+        {
+            if self.parser.set(Box::new(parser)).is_err() {
+                panic!("parser has already been defined")
+            }
+        }
+    }
+
+    impl<Par: ?Sized> Declared<Par> {
+        /// Whether the declared parser has been defined
+        pub fn is_defined(&self) -> bool {
+            self.parser.get().is_some()
+        }
+
+        /// Creates a handle to refer to the declared parser from within rules,
+        /// without keeping it alive
+        pub fn weak(&self) -> DeclaredWeak<Par> {
+            DeclaredWeak {
+                parser: std::rc::Rc::downgrade(&self.parser),
+            }
+        }
+    }
+
+    /// A handle of a [Declared] parser, to refer to it from within rules
+    ///
+    /// This `struct` is created by the [`Declared::weak`] method on [`Declared`].
+    /// See its documentation for more.
+    #[must_use = "Parsers are lazy and do nothing unless consumed"]
+    #[derive(Debug, Clone)]
+    pub struct DeclaredWeak<Par: ?Sized> {
+        pub(crate) parser: std::rc::Weak<std::cell::OnceCell<Box<Par>>>,
+    }
+
+    /// A parser growing the results of a left-recursive parser, by stream offset
+    ///
+    /// See [`left_rec`] for more.
+    #[derive(Debug)]
+    pub struct SeedGrowing<I: Stream, Out, Par: ?Sized> {
+        #[allow(clippy::type_complexity)]
+        pub(crate) seeds:
+            std::cell::RefCell<std::collections::HashMap<usize, Option<(Out, I::SaveState)>>>,
+        pub(crate) parser: Par,
+    }
+
+    /// A parser for left-recursive grammars
+    ///
+    /// This `struct` is created by the [`left_rec`] method on [`sources`](crate::parser::sources).
+    /// See its documentation for more.
+    #[must_use = "Parsers are lazy and do nothing unless consumed"]
+    #[derive(Debug, Clone)]
+    pub struct LeftRec<Par: ?Sized> {
+        pub(crate) parser: std::rc::Rc<Par>,
+    }
+
+    /// A handle of a [LeftRec] parser, to refer to it from within itself
+    ///
+    /// This `struct` is created by the [`left_rec`] method on [`sources`](crate::parser::sources).
+    /// See its documentation for more.
+    #[must_use = "Parsers are lazy and do nothing unless consumed"]
+    #[derive(Debug, Clone)]
+    pub struct LeftRecWeak<Par: ?Sized> {
+        pub(crate) parser: std::rc::Weak<Par>,
+    }
 }
 
 mod impls {
     use super::{adapters::*, *};
 
+    /// Whether `c` may continue an identifier, so that a keyword cannot end before it
+    fn is_word_char(c: char) -> bool {
+        c.is_alphanumeric() || c == '_'
+    }
+
     #[parser_fn]
     fn nop(self: &NOP) -> () {}
 
@@ -318,13 +1158,125 @@ mod impls {
         parse![any_if(|v| *v != self.0)]
     }
 
+    #[parser_fn]
+    fn skip_until<Rhs, Fun, Val>(self: &SkipUntil<Rhs, Fun>) -> Option<Val>
+    where
+        INPUT::Item: PartialEq<Rhs>,
+        for<'impl_move> Fun: FnOnce() -> Val,
+        for<'impl_mut> Fun: FnMut() -> Val,
+        for<'impl_ref> Fun: Fn() -> Val,
+    {
+        loop {
+            if input.next()? == self.0 {
+                return Some((self.1)());
+            }
+        }
+    }
+
+    #[parser_fn]
+    fn recover_nested<Rhs, Fun, Val>(self: &RecoverNested<Rhs, Fun>) -> Option<Val>
+    where
+        INPUT::Item: PartialEq<Rhs>,
+        for<'impl_move> Fun: FnOnce() -> Val,
+        for<'impl_mut> Fun: FnMut() -> Val,
+        for<'impl_ref> Fun: Fn() -> Val,
+    {
+        if input.next()? != self.open {
+            return None;
+        }
+        let mut depth = 1usize;
+        while depth > 0 {
+            let item = input.next()?;
+            if item == self.open {
+                depth += 1;
+            } else if item == self.close {
+                depth -= 1;
+            }
+        }
+        Some((self.fallback)())
+    }
+
+    #[parser_fn]
+    fn commit(self: &Commit) -> ()
+    where
+        INPUT: StreamCut,
+    {
+        input.cut()
+    }
+
+    #[parser_fn]
+    fn trim(self: &Trim) -> ()
+    where
+        INPUT: StreamTrim,
+    {
+        input.trim()
+    }
+
+    #[parser_fn]
+    fn take_while(self: &TakeWhile) -> Sure<INPUT::Slice>
+    where
+        INPUT: StreamSlice<Item = char>,
+    {
+        let start = input.slice_offset();
+        while self.0.parse(input).is_some() {}
+        Sure(input.slice_since(start))
+    }
+
+    #[parser_fn]
+    fn take_while1(self: &TakeWhile1) -> Option<INPUT::Slice>
+    where
+        INPUT: StreamSlice<Item = char>,
+    {
+        let start = input.slice_offset();
+        self.0.parse(input)?;
+        while self.0.parse(input).is_some() {}
+        Some(input.slice_since(start))
+    }
+
+    #[parser_fn]
+    fn words<Val>(self: &Words<Val>) -> Option<Val>
+    where
+        INPUT: Stream<Item = char>,
+        Val: Clone,
+    {
+        let start = input.savestate();
+        let mut node = 0;
+        let mut len = 0;
+        let mut longest = None;
+
+        loop {
+            let next = input.next();
+            if let Some(value) = &self.nodes[node].value {
+                if !self.boundary || !next.is_some_and(is_word_char) {
+                    longest = Some((len, value));
+                }
+            }
+            match next.and_then(|c| self.nodes[node].child(c).ok()) {
+                Some(child) => {
+                    node = child;
+                    len += 1;
+                }
+                None => break,
+            }
+        }
+
+        // the stream is walked again, as savestates can only be backtracked to once
+        input.backtrack(start);
+        let (len, value) = longest?;
+        for _ in 0..len {
+            input.next();
+        }
+        Some(value.clone())
+    }
+
     #[parser_fn]
     fn take<'a, Ref>(self: &Take<'a>) -> Option<&'a Ref>
     where
         INPUT: Stream + StreamSlice<Slice = &'a Ref>,
         Ref: 'a + ?Sized,
     {
-        any().del().repeat_min(self.0).slice().parse_once(input)
+        input.demand(self.0);
+        any().del().repeat_exact(self.0).slice().parse_once(input)
     }
 
     #[parser_fn]
@@ -373,6 +1325,112 @@ mod impls {
         self.parser.parse(input)
     }
 
+    #[parser_fn]
+    fn recursive_sync<Par>(self: &RecursiveSync<Par>) -> Par::Output
+    where
+        Par: ?Sized + Parse<INPUT>,
+    {
+        self.parser.parse(input)
+    }
+
+    #[parser_fn]
+    fn weak_sync<Par>(self: &WeakSync<Par>) -> Par::Output
+    where
+        Par: ?Sized + Parse<INPUT>,
+    {
+        self.parser.upgrade().unwrap().parse(input)
+    }
+
+    #[parser_fn]
+    fn declared<Par>(self: &Declared<Par>) -> Par::Output
+    where
+        Par: ?Sized + Parse<INPUT>,
+    {
+        let parser: &Par = self.parser.get().expect("parser has not been defined");
+        parser.parse(input)
+    }
+
+    #[parser_fn]
+    fn declared_weak<Par>(self: &DeclaredWeak<Par>) -> Par::Output
+    where
+        Par: ?Sized + Parse<INPUT>,
+    {
+        let cell = self.parser.upgrade().unwrap();
+        let parser: &Par = cell.get().expect("parser has not been defined");
+        parser.parse(input)
+    }
+
+    impl<I, Out, Par> SeedGrowing<I, Out, Par>
+    where
+        I: StreamOffset<SaveState: Clone>,
+        Out: Response<Error: Default> + Clone,
+        Par: ?Sized + Parse<I, Output = Out>,
+    {
+        fn grow(&self, input: &mut I) -> Out {
+            let offset = input.offset();
+            if let Some(seed) = self.seeds.borrow().get(&offset) {
+                // re-entered in left-recursive position
+                return match seed {
+                    Some((out, state)) => {
+                        input.backtrack(state.clone());
+                        out.clone()
+                    }
+                    None => Out::from_error(Default::default()),
+                };
+            }
+
+            self.seeds.borrow_mut().insert(offset, None);
+            let start = input.savestate();
+            let mut seed: Option<(Out, I::SaveState, usize)> = None;
+            let failure = loop {
+                input.backtrack(start.clone());
+                let out = self.parser.parse(input);
+                if out.clone().control_flow().is_break() {
+                    break Some(out);
+                }
+                let end = input.offset();
+                if seed.as_ref().is_some_and(|(_, _, last)| end <= *last) {
+                    break None;
+                }
+                let state = input.savestate();
+                self.seeds
+                    .borrow_mut()
+                    .insert(offset, Some((out.clone(), state.clone())));
+                seed = Some((out, state, end));
+            };
+            self.seeds.borrow_mut().remove(&offset);
+
+            match (seed, failure) {
+                (Some((out, state, _)), _) => {
+                    input.backtrack(state);
+                    out
+                }
+                (None, Some(failure)) => failure,
+                (None, None) => unreachable!("a seed was grown"),
+            }
+        }
+    }
+
+    #[parser_fn]
+    fn left_rec<Out, Par>(self: &LeftRec<SeedGrowing<INPUT, Out, Par>>) -> Out
+    where
+        INPUT: StreamOffset<SaveState: Clone>,
+        Out: Response<Error: Default> + Clone,
+        Par: ?Sized + Parse<INPUT, Output = Out>,
+    {
+        self.parser.grow(input)
+    }
+
+    #[parser_fn]
+    fn left_rec_weak<Out, Par>(self: &LeftRecWeak<SeedGrowing<INPUT, Out, Par>>) -> Out
+    where
+        INPUT: StreamOffset<SaveState: Clone>,
+        Out: Response<Error: Default> + Clone,
+        Par: ?Sized + Parse<INPUT, Output = Out>,
+    {
+        self.parser.upgrade().unwrap().grow(input)
+    }
+
     #[parser_fn]
     fn weak<Par>(self: &Weak<Par>) -> Par::Output
     where
@@ -1,5 +1,7 @@
 use std::process::Output;
 
+pub use lavan_proc_macros::FromParse;
+
 use super::{
     adapters::{
         and::And,
@@ -9,15 +11,20 @@ use super::{
         del::Del,
         delimited::Delimited,
         eq::{Eq, Ne},
+        expect::Expect,
         filter::{Filter, FilterNot},
         lift::Lift,
         map::{Map, MapErr, Sel, SelErr},
+        memoize::Memoize,
+        node::Node,
         ok::Ok,
         opt::Opt,
         or::Or,
         owned::Owned,
+        padded::{Lexeme, Padded},
         parse_str::ParseStr,
         persist::Persist,
+        recover::RecoverWith,
         slice::Slice,
         spanned::Spanned,
         then::Then,
@@ -32,7 +39,10 @@ use super::{
 
 #[cfg(feature = "unstable-repeat-api-2021-v1")]
 use super::adapters::repeat::adapters::*;
+#[cfg(feature = "unstable-trace-2021-v1")]
+use super::adapters::trace::Trace;
 
+use crate::error::{Expected, Report};
 use crate::input::prelude::*;
 use crate::response::prelude::*;
 
@@ -293,6 +303,249 @@ pub trait ParseOnce<Input> {
         Ok { parser: self }
     }
 
+    /// Reports a [ParseError](crate::error::ParseError) in case of failure,
+    /// expecting `expected` at the offset where this parser started.
+    /// On failure, the stream is rewound to that offset.
+    ///
+    /// If this parser already reports an error starting further into the input,
+    /// that error is kept instead. Otherwise, its expectations are replaced.
+    ///
+    /// Alternating such parsers through [or](ParseOnce::or) merges
+    /// their expected-sets when they fail at the same offset.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// let mut input = "c".chars().cursor();
+    /// let error = any_eq('a')
+    ///     .expect(Expected::Token('a'))
+    ///     .or(any_eq('b').expect(Expected::Token('b')))
+    ///     .parse_once(&mut input)
+    ///     .into_result()
+    ///     .unwrap_err();
+    /// assert_eq!(error.expected(), &[Expected::Token('a'), Expected::Token('b')]);
+    /// assert_eq!(error.span(), &(0, 1));
+    /// assert_eq!(error.to_string(), "expected 'a' or 'b', found 'c'");
+    /// ```
+    fn expect(self, expected: Expected<Input::Item>) -> Expect<Self, Input::Item>
+    where
+        Self: Sized,
+        Input: StreamSpan,
+        Self::Output: Report<Input::Item, Input::Span>,
+    {
+        Expect {
+            parser: self,
+            expected,
+        }
+    }
+
+    /// Names this parser in error messages. This is a shorthand for
+    /// [expect](ParseOnce::expect) with an [Expected::Label].
+    ///
+    /// Low-level expectations reported at the offset where this parser
+    /// started, such as the ones of its first token, are replaced by `label`.
+    /// Parsers failing with errors of their own, such as the ones built through
+    /// [or_else](Filter::or_else), are labelled when their error implements
+    /// [ReportError](crate::error::ReportError).
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// let mut input = "42".chars().cursor();
+    /// let error = ascii::identifier()
+    ///     .label("identifier")
+    ///     .parse_once(&mut input)
+    ///     .into_result()
+    ///     .unwrap_err();
+    /// assert_eq!(error.to_string(), "expected identifier, found '4'");
+    /// ```
+    fn label(self, label: &'static str) -> Expect<Self, Input::Item>
+    where
+        Self: Sized,
+        Input: StreamSpan,
+        Self::Output: Report<Input::Item, Input::Span>,
+    {
+        self.expect(Expected::Label(label))
+    }
+
+    /// Recovers from a failure of this parser through `strategy`,
+    /// which is run from the offset where this parser started.
+    ///
+    /// If the strategy succeeds, its value is used as a placeholder and the
+    /// original error is recorded into the stream, see [StreamRecover].
+    /// Otherwise, the stream is rewound and the original error is returned.
+    ///
+    /// Any parser producing the same value can be used as a strategy,
+    /// such as [skip_until](crate::parser::sources::skip_until),
+    /// [recover_nested](crate::parser::sources::recover_nested) or
+    /// [recover_via](crate::parser::sources::recover_via).
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Stmt {
+    ///     Expr(char),
+    ///     Error,
+    /// }
+    ///
+    /// fn stmt((name, _semicolon): (char, char)) -> Stmt {
+    ///     Stmt::Expr(name)
+    /// }
+    ///
+    /// let mut input = "a;42+;b;".chars().cursor().recovering();
+    /// let stmts = any_if(char::is_ascii_alphabetic)
+    ///     .label("statement")
+    ///     .and(any_eq(';').expect(Expected::Token(';')))
+    ///     .map(stmt)
+    ///     .recover_with(skip_until(';', || Stmt::Error))
+    ///     .repeat()
+    ///     .to_vec()
+    ///     .parse_once(&mut input);
+    ///
+    /// assert_eq!(stmts.0, vec![Stmt::Expr('a'), Stmt::Error, Stmt::Expr('b')]);
+    /// assert_eq!(input.errors().len(), 1);
+    /// assert_eq!(input.errors()[0].to_string(), "expected statement, found '4'");
+    /// ```
+    fn recover_with<Strat>(self, strategy: Strat) -> RecoverWith<Self, Strat>
+    where
+        Self: Sized,
+        Input: StreamRecover<Error = <Self::Output as Response>::Error>,
+        Strat: ParseOnce<Input>,
+        Strat::Output: Response<Value = <Self::Output as Response>::Value>,
+    {
+        RecoverWith {
+            parser: self,
+            strategy,
+        }
+    }
+
+    /// Caches the outputs of this parser by stream offset, along with the
+    /// state it left the stream in, so it runs at most once per offset.
+    /// This avoids re-parsing the same input through alternatives.
+    ///
+    /// The input must be a [`Memoizing`](crate::input::adapters::memoizing::Memoizing)
+    /// stream: the cache only holds the outputs of the last stream parsed, and is
+    /// emptied as soon as another one comes.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///```
+    /// use lavan::prelude::*;
+    /// use std::cell::Cell;
+    ///
+    /// let checks = Cell::new(0);
+    /// let digit = any_if(|c: &char| {
+    ///     checks.set(checks.get() + 1);
+    ///     c.is_ascii_digit()
+    /// });
+    /// let number = digit.del().repeat_min(1).slice().memoize();
+    /// let sum = number.as_ref().and(any_eq('+'));
+    /// let sub = number.as_ref().and(any_eq('-'));
+    /// let expr = sum.or(sub);
+    ///
+    /// let output = expr.parse(&mut "123-4".chars().memoizing());
+    /// assert_eq!(output, Some(("123", '-')));
+    /// // the number was parsed only once
+    /// assert_eq!(checks.get(), 4);
+    ///
+    /// // another stream is never given the outputs of the previous one
+    /// let mut input = "45+6".chars().memoizing();
+    /// assert_eq!(expr.parse(&mut input), Some(("45", '+')));
+    /// assert_eq!(input.scanner.as_str(), "6");
+    /// ```
+    fn memoize(self) -> Memoize<Self, Self::Output, Input::SaveState>
+    where
+        Self: Sized,
+        Input: StreamMemo<SaveState: Clone>,
+        Self::Output: Clone,
+    {
+        Memoize {
+            parser: self,
+            memo: Default::default(),
+        }
+    }
+
+    /// Records the span of this parser as a node of `kind`, when it succeeds, in a
+    /// [concrete syntax tree](crate::cst) being built by the input
+    ///
+    /// The nodes recorded within it become its children, and the rest of the
+    /// text it consumed becomes tokens.
+    ///
+    /// # Examples
+    /// Basic usage:
+    /// ```
+    /// use lavan::prelude::*;
+    ///
+    /// let digits = any_if(char::is_ascii_digit).del().repeat_min(1);
+    /// let number = digits.node("number");
+    /// let sum = number.as_ref().and('+').and(number.as_ref()).node("sum");
+    ///
+    /// let mut input = "1+23".chars().cst_builder();
+    /// assert!(sum.parse(&mut input));
+    ///
+    /// let root = input.finish("root");
+    /// let sum = root.child_nodes().next().unwrap();
+    /// let numbers: Vec<_> = sum.child_nodes().map(|node| node.text_range()).collect();
+    /// assert_eq!(*sum.kind(), "sum");
+    /// assert_eq!(numbers, [0..1, 2..4]);
+    /// ```
+    fn node<Kind>(self, kind: Kind) -> Node<Self, Kind>
+    where
+        Self: Sized,
+        Input: StreamNode<Kind = Kind>,
+        Kind: Clone,
+    {
+        Node { parser: self, kind }
+    }
+
+    /// Logs the invocations of this parser under `name`, while a
+    /// [trace](crate::trace) is being recorded
+    ///
+    /// The span of the input consumed and whether the parsing has succeeded are logged,
+    /// along with the invocations of the traced parsers within it. Outside of
+    /// [`trace::record`](crate::trace::record), it parses as this parser does.
+    ///
+    /// # Examples
+    /// Basic usage:
+    /// ```
+    /// use lavan::prelude::*;
+    /// use lavan::trace;
+    ///
+    /// let sign = any_eq('-').trace("sign");
+    /// let digits = any_if(char::is_ascii_digit).del().repeat_min(1).slice().trace("digits");
+    /// let number = sign.opt().and(digits).trace("number");
+    ///
+    /// let (output, trace) = trace::record(|| number.parse(&mut "7".chars().cursor().traced()));
+    /// assert_eq!(output, Some((None, "7")));
+    /// // `opt` gave back the item consumed by `sign`
+    /// assert_eq!(
+    ///     trace.to_string(),
+    ///     "\
+    /// number (0, 1) success
+    ///   sign (0, 1) failure
+    ///   backtrack 1 -> 0
+    ///   digits (0, 1) success
+    ///     backtrack 1 -> 1
+    /// "
+    /// );
+    /// ```
+    #[cfg(feature = "unstable-trace-2021-v1")]
+    fn trace(self, name: &'static str) -> Trace<Self>
+    where
+        Self: Sized,
+        Input: StreamSpan,
+        <Input as StreamSpan>::Span: std::fmt::Debug,
+    {
+        Trace { parser: self, name }
+    }
+
     /// Automatically backtracks if the parsing has failed
     ///
     /// # Examples
@@ -551,6 +804,64 @@ pub trait ParseOnce<Input> {
         Owned { parser: self }
     }
 
+    /// Skips the trivia before and after this parser, when it succeeds,
+    /// see [StreamTrim]
+    ///
+    /// When it fails, the stream is rewound to before the leading trivia.
+    ///
+    /// # Examples
+    /// Basic usage:
+    /// ```
+    /// use lavan::input::adapters::trimming::Trivia;
+    /// use lavan::prelude::*;
+    ///
+    /// let number = any_if(char::is_ascii_digit).del().repeat_min(1).slice();
+    /// let sum = number.padded().and('+').and(number.padded());
+    ///
+    /// let mut input = " 1 // one\n + 2 ".chars().trimming(Trivia::default());
+    /// assert_eq!(sum.parse_once(&mut input), Some(("1", "2")));
+    /// assert_eq!(input.next(), None);
+    ///
+    /// let mut input = " /* no number */ +".chars().trimming(Trivia::default());
+    /// assert_eq!(number.padded().parse_once(&mut input), None);
+    /// assert_eq!(input.next(), Some(' '));
+    /// ```
+    fn padded(self) -> Padded<Self>
+    where
+        Self: Sized,
+        Input: StreamTrim,
+    {
+        Padded { parser: self }
+    }
+
+    /// Skips the trivia after this parser, when it succeeds, see [StreamTrim]
+    ///
+    /// When it fails, no trivia is skipped.
+    ///
+    /// Once the trivia at the start of the input has been skipped, as by
+    /// [`trim`](crate::parser::sources::functions::trim), making each token a
+    /// lexeme is enough for the trivia between them to be skipped.
+    ///
+    /// # Examples
+    /// Basic usage:
+    /// ```
+    /// use lavan::input::adapters::trimming::Trivia;
+    /// use lavan::prelude::*;
+    ///
+    /// let word = any_if(char::is_ascii_alphabetic).del().repeat_min(1).slice().lexeme();
+    /// let words = trim().and(word.repeat_eoi().to_vec());
+    ///
+    /// let mut input = "\tone /* and */ two\n".chars().trimming(Trivia::default());
+    /// assert_eq!(words.parse_once(&mut input), Some(vec!["one", "two"]));
+    /// ```
+    fn lexeme(self) -> Lexeme<Self>
+    where
+        Self: Sized,
+        Input: StreamTrim,
+    {
+        Lexeme { parser: self }
+    }
+
     // TODO: Documentation
     fn spanned(self) -> Spanned<Self>
     where
@@ -860,6 +1171,66 @@ where
     }
 }
 
+/// Parse a value of a type on its own, as through [`mk`](super::sources::mk)
+///
+/// It can be derived for structs, whose fields are parsed in sequence,
+/// and for enums, whose variants are tried in order, backtracking in between.
+/// The derived implementations output an [`Option`] and accept any stream,
+/// unless pinned through `#[lavan(input = Type)]`.
+///
+/// The derive is configured through `#[lavan(...)]` attributes:
+/// - `token = "let"` expects a string or char before a struct, variant or field
+/// - `skip_ws` skips whitespace before each token and field within
+/// - `sep_by = ","` parses a `Vec` field as items separated by a string or char
+/// - `with = path` parses a field with the given parser instead
+///
+/// Using text options requires a `&str` slicing stream of `char`s.
+///
+/// # Examples
+/// Basic usage:
+///```
+/// use lavan::prelude::*;
+///
+/// fn name<'a, I: StrStream<'a>>(input: &mut I) -> Option<String> {
+///     any_if(char::is_ascii_alphabetic)
+///         .del()
+///         .repeat_min(1)
+///         .slice()
+///         .parse_once(input)
+///         .map(str::to_owned)
+/// }
+///
+/// fn digit<I: Stream<Item = char>>(input: &mut I) -> Option<u32> {
+///     input.next()?.to_digit(10)
+/// }
+///
+/// #[derive(Debug, PartialEq, FromParse)]
+/// enum Value {
+///     #[lavan(token = "true")]
+///     True,
+///     #[lavan(token = "false")]
+///     False,
+///     Digit(#[lavan(with = digit)] u32),
+/// }
+///
+/// #[derive(Debug, PartialEq, FromParse)]
+/// #[lavan(token = "let", skip_ws)]
+/// struct Let {
+///     #[lavan(with = name)]
+///     name: String,
+///     #[lavan(token = '=', sep_by = ",")]
+///     values: Vec<Value>,
+/// }
+///
+/// let output = mk::<Let, _>().parse_once(&mut "let xs = 1, true ,2".chars());
+/// assert_eq!(
+///     output,
+///     Some(Let {
+///         name: "xs".to_owned(),
+///         values: vec![Value::Digit(1), Value::True, Value::Digit(2)],
+///     })
+/// );
+/// ```
 pub trait FromParse<Input>
 where
     Input: Stream,
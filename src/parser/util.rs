@@ -0,0 +1,65 @@
+use super::adapters::{as_ref::AsRef, del::Del};
+use crate::error::ParseResult;
+use crate::parser::prelude::internal::*;
+
+/// Takes a parser by reference, as [`ParseOnce::as_ref`] does,
+/// without requiring the input type to be known yet.
+pub fn by_ref<Par>(parser: &Par) -> AsRef<'_, Par> {
+    AsRef { parser }
+}
+
+/// Voids the value of a parser, if its response has any,
+/// so that it can be repeated or sliced.
+pub fn void<Input, Par>(parser: Par) -> <Par::Output as Void>::Voided<Par>
+where
+    Input: Stream,
+    Par: ParseOnce<Input>,
+    Par::Output: Void,
+{
+    <Par::Output as Void>::void(parser)
+}
+
+/// A response whose parsers can be voided, either through [`ParseOnce::del`],
+/// or as they are when the response holds no value.
+pub trait Void {
+    type Voided<Par>;
+
+    fn void<Par>(parser: Par) -> Self::Voided<Par>;
+}
+
+macro_rules! void_del {
+    ($($ty:ty => [$($generics:tt)*]),+ $(,)?) => {$(
+        impl<$($generics)*> Void for $ty {
+            type Voided<Par> = Del<Par>;
+
+            fn void<Par>(parser: Par) -> Self::Voided<Par> {
+                Del { parser }
+            }
+        }
+    )+};
+}
+
+macro_rules! void_identity {
+    ($($ty:ty => [$($generics:tt)*]),+ $(,)?) => {$(
+        impl<$($generics)*> Void for $ty {
+            type Voided<Par> = Par;
+
+            fn void<Par>(parser: Par) -> Self::Voided<Par> {
+                parser
+            }
+        }
+    )+};
+}
+
+void_del! {
+    Option<T> => [T],
+    Sure<T> => [T],
+    Result<T, E> => [T, E],
+    ParseResult<T, Item, Span> => [T, Item, Span],
+}
+
+void_identity! {
+    () => [],
+    bool => [],
+    Unsure<E> => [E],
+}
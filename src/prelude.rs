@@ -1,17 +1,23 @@
 #![cfg(feature = "unstable-prelude-2021-v1")]
 
 pub use crate::util::{
+    binary::Endian,
+    char_class::{Category, CharClass, Predicate},
     text::{ascii, utf},
     *,
 };
 
+pub use error::*;
 pub use input::*;
 pub use parser::*;
 pub use response::*;
 
 pub mod parser {
+    pub use crate::parser::lexer::{lex_then_parse, Lexer};
+    pub use crate::parser::pratt::pratt;
     pub use crate::parser::sources::functions::*;
     pub use crate::parser::traits::{FromParse, Parse, ParseMut, ParseOnce};
+    pub use crate::{grammar, parser_fn, parser_impl, select};
 }
 
 pub mod response {
@@ -19,6 +25,10 @@ pub mod response {
     pub use crate::response::traits::Response;
 }
 
+pub mod error {
+    pub use crate::error::{Expected, ParseError, ParseResult, Report, ReportError};
+}
+
 pub mod input {
     pub use crate::input::prelude::*;
 }
@@ -0,0 +1,249 @@
+//! Recording of parser invocations, for debugging grammars
+//!
+//! Parsers wrapped by [`trace`](crate::parser::traits::ParseOnce::trace) log their
+//! invocations while [`record`] runs on the same thread, and streams wrapped by
+//! [`traced`](crate::input::traits::Stream::traced) log their backtracks.
+//! Outside of [`record`], traced parsers and streams behave as the ones they wrap.
+
+use std::cell::RefCell;
+use std::fmt::{self, Display, Write};
+
+/// The tree of invocations recorded by [`record`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Trace {
+    pub events: Vec<Event>,
+}
+
+/// Something that happened while recording
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// A traced parser was invoked, along with what happened within it
+    Invocation(Invocation),
+    /// A traced stream went back from the item offset `from` to `to`
+    Backtrack { from: usize, to: usize },
+}
+
+/// An invocation of a traced parser
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invocation {
+    pub name: &'static str,
+    /// The span consumed by the parser, as formatted by `Debug`
+    pub span: String,
+    pub outcome: Outcome,
+    pub events: Vec<Event>,
+}
+
+/// Whether the response of an invocation continued or broke,
+/// as told by [`Response::control_flow`](crate::response::traits::Response::control_flow)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Success,
+    Failure,
+}
+
+thread_local! {
+    /// The events of the invocations that have yet to exit, from the outermost
+    static RECORDER: RefCell<Option<Vec<Vec<Event>>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, recording the invocations of traced parsers and the backtracks of
+/// traced streams on the current thread
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// use lavan::prelude::*;
+/// use lavan::trace::{self, Event, Outcome};
+///
+/// let digit = any_if(char::is_ascii_digit).trace("digit");
+/// let number = digit.as_ref().del().repeat_min(1).slice().trace("number");
+/// let word = any_if(char::is_ascii_alphabetic).trace("letter");
+/// let token = number.or(word.as_ref().del().repeat_min(1).slice()).trace("token");
+///
+/// let mut input = "42".chars().cursor().traced();
+/// let (output, trace) = trace::record(|| token.parse(&mut input));
+/// assert_eq!(output, Some("42"));
+///
+/// let Event::Invocation(root) = &trace.events[0] else {
+///     panic!("expected an invocation");
+/// };
+/// assert_eq!((root.name, root.outcome), ("token", Outcome::Success));
+/// assert_eq!(root.span, "(0, 2)");
+///
+/// // the repetition stopped at the third digit, rewinding to where it was missing
+/// assert_eq!(
+///     trace.to_string(),
+///     "\
+/// token (0, 2) success
+///   number (0, 2) success
+///     digit (0, 1) success
+///     digit (1, 2) success
+///     digit (2, 2) failure
+///     backtrack 2 -> 2
+/// "
+/// );
+/// ```
+pub fn record<Out>(f: impl FnOnce() -> Out) -> (Out, Trace) {
+    /// Restores the recorder of an outer [`record`], even when `f` panics
+    struct Guard(Option<Option<Vec<Vec<Event>>>>);
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            if let Some(outer) = self.0.take() {
+                RECORDER.with_borrow_mut(|recorder| *recorder = outer);
+            }
+        }
+    }
+
+    let mut guard = Guard(Some(
+        RECORDER.with_borrow_mut(|recorder| recorder.replace(vec![vec![]])),
+    ));
+    let output = f();
+    let outer = guard.0.take().unwrap();
+    let frames = RECORDER.with_borrow_mut(|recorder| std::mem::replace(recorder, outer));
+
+    let events = frames.into_iter().flatten().flatten().collect();
+    (output, Trace { events })
+}
+
+/// Starts an invocation, returning whether it is being recorded
+pub(crate) fn enter() -> bool {
+    RECORDER.with_borrow_mut(|recorder| match recorder {
+        Some(frames) => {
+            frames.push(vec![]);
+            true
+        }
+        None => false,
+    })
+}
+
+/// Ends the innermost invocation started by [`enter`]
+pub(crate) fn exit(name: &'static str, span: String, outcome: Outcome) {
+    RECORDER.with_borrow_mut(|recorder| {
+        let Some(frames) = recorder else { return };
+        let events = frames.pop().unwrap_or_default();
+        let invocation = Invocation {
+            name,
+            span,
+            outcome,
+            events,
+        };
+        match frames.last_mut() {
+            Some(parent) => parent.push(Event::Invocation(invocation)),
+            None => frames.push(vec![Event::Invocation(invocation)]),
+        }
+    })
+}
+
+pub(crate) fn backtrack(from: usize, to: usize) {
+    RECORDER.with_borrow_mut(|recorder| {
+        if let Some(parent) = recorder.as_mut().and_then(|frames| frames.last_mut()) {
+            parent.push(Event::Backtrack { from, to });
+        }
+    })
+}
+
+impl Trace {
+    /// Exports the trace as a JSON array of events
+    ///
+    /// Invocations are objects with the `name`, `span`, `outcome` and `events` keys,
+    /// and backtracks are objects with the `backtrack` key holding `[from, to]`.
+    ///
+    /// # Examples
+    /// Basic usage:
+    /// ```
+    /// use lavan::prelude::*;
+    /// use lavan::trace;
+    ///
+    /// let letter = any_if(char::is_ascii_alphabetic).trace("letter");
+    /// let (_, trace) = trace::record(|| letter.parse(&mut "1".chars().cursor().traced()));
+    ///
+    /// assert_eq!(
+    ///     trace.to_json(),
+    ///     r#"[{"name":"letter","span":"(0, 1)","outcome":"failure","events":[]}]"#
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        write_json(&mut json, &self.events);
+        json
+    }
+}
+
+fn write_json(json: &mut String, events: &[Event]) {
+    json.push('[');
+    for (idx, event) in events.iter().enumerate() {
+        if idx > 0 {
+            json.push(',');
+        }
+        match event {
+            Event::Invocation(invocation) => {
+                json.push_str("{\"name\":");
+                write_json_str(json, invocation.name);
+                json.push_str(",\"span\":");
+                write_json_str(json, &invocation.span);
+                let _ = write!(json, ",\"outcome\":\"{}\",\"events\":", invocation.outcome);
+                write_json(json, &invocation.events);
+                json.push('}');
+            }
+            Event::Backtrack { from, to } => {
+                let _ = write!(json, "{{\"backtrack\":[{from},{to}]}}");
+            }
+        }
+    }
+    json.push(']');
+}
+
+fn write_json_str(json: &mut String, string: &str) {
+    json.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+/// Prints the trace as an indented tree, one event per line
+impl Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_events(f: &mut fmt::Formatter<'_>, events: &[Event], depth: usize) -> fmt::Result {
+            for event in events {
+                let indent = depth * 2;
+                match event {
+                    Event::Invocation(invocation) => {
+                        writeln!(
+                            f,
+                            "{:indent$}{} {} {}",
+                            "", invocation.name, invocation.span, invocation.outcome
+                        )?;
+                        write_events(f, &invocation.events, depth + 1)?;
+                    }
+                    Event::Backtrack { from, to } => {
+                        writeln!(f, "{:indent$}backtrack {from} -> {to}", "")?
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        write_events(f, &self.events, 0)
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Outcome::Success => "success",
+            Outcome::Failure => "failure",
+        })
+    }
+}
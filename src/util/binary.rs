@@ -0,0 +1,244 @@
+use crate::parser::{prelude::internal::*, sources::src};
+use adapters::*;
+
+pub use adapters::Endian;
+
+/// A fixed-size integer, decodable from its bytes in either [Endian]
+pub trait FixedInt: Sized {
+    type Bytes: Default + AsMut<[u8]>;
+
+    fn from_le_bytes(bytes: Self::Bytes) -> Self;
+    fn from_be_bytes(bytes: Self::Bytes) -> Self;
+}
+
+macro_rules! fixed_int {
+    ($($int:ty),*) => {$(
+        impl FixedInt for $int {
+            type Bytes = [u8; std::mem::size_of::<$int>()];
+
+            fn from_le_bytes(bytes: Self::Bytes) -> Self {
+                <$int>::from_le_bytes(bytes)
+            }
+
+            fn from_be_bytes(bytes: Self::Bytes) -> Self {
+                <$int>::from_be_bytes(bytes)
+            }
+        }
+    )*};
+}
+
+fixed_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// Decodes a [FixedInt] from the next bytes, in the `endian` byte order
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// use lavan::prelude::*;
+///
+/// let mut input = Bytes::new(&[0xff, 0xfe]);
+/// assert_eq!(binary::int::<i16, _>(Endian::Big).parse_once(&mut input), Some(-2));
+/// ```
+pub fn int<T, I>(endian: Endian) -> Src<Int<T>, I>
+where
+    T: FixedInt,
+    I: Stream<Item = u8>,
+{
+    src(Int(endian, PhantomData))
+}
+
+macro_rules! int_fns {
+    ($($name:ident: $int:ty, $endian:ident;)*) => {$(
+        #[doc = concat!(
+            "Decodes a [", stringify!($int), "] from the next bytes, in the ",
+            stringify!($endian), "-endian byte order. See [int] for more."
+        )]
+        pub fn $name<I>() -> Src<Int<$int>, I>
+        where
+            I: Stream<Item = u8>,
+        {
+            int(Endian::$endian)
+        }
+    )*};
+}
+
+int_fns! {
+    u16_le: u16, Little;
+    u16_be: u16, Big;
+    u32_le: u32, Little;
+    u32_be: u32, Big;
+    u64_le: u64, Little;
+    u64_be: u64, Big;
+}
+
+/// Decodes an unsigned [LEB128](https://en.wikipedia.org/wiki/LEB128) variable-length integer.
+/// Fails if the end of input is reached, or if the value overflows an [u64].
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// use lavan::prelude::*;
+///
+/// let mut input = Bytes::new(&[0xe5, 0x8e, 0x26, 0x01]);
+/// assert_eq!(binary::varint().parse_once(&mut input), Some(624485));
+/// assert_eq!(binary::varint().parse_once(&mut input), Some(1));
+/// ```
+pub fn varint<I>() -> Src<Varint, I>
+where
+    I: Stream<Item = u8>,
+{
+    src(Varint)
+}
+
+/// Expects the next bytes to be equal to `bytes`
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// use lavan::prelude::*;
+///
+/// let mut input = Bytes::new(b"\x89PNG\r\n\x1a\n");
+/// assert!(binary::tag(b"\x89PNG").parse_once(&mut input));
+/// assert!(!binary::tag(b"\x89PNG").parse_once(&mut input));
+/// ```
+pub fn tag<'t, I>(bytes: &'t [u8]) -> Src<Tag<'t>, I>
+where
+    I: Stream<Item = u8>,
+{
+    src(Tag(bytes))
+}
+
+/// Parses a length through `length`, then takes that many bytes
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// use lavan::prelude::*;
+///
+/// let mut input = Bytes::new(b"\x00\x00\x00\x05hello world");
+/// let frame = binary::length_prefixed(binary::u32_be()).parse_once(&mut input);
+/// assert_eq!(frame, Some(&b"hello"[..]));
+/// assert_eq!(input.as_slice(), b" world");
+/// ```
+pub fn length_prefixed<Par, I>(length: Par) -> Src<LengthPrefixed<Par>, I>
+where
+    Par: ParseOnce<I>,
+    <Par::Output as Response>::Value: TryInto<usize>,
+    I: StreamSlice<Item = u8>,
+{
+    src(LengthPrefixed(length))
+}
+
+pub mod adapters {
+    use super::*;
+
+    pub use crate::parser::sources::adapters::Src;
+
+    /// A byte order
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Endian {
+        Little,
+        Big,
+    }
+
+    /// A parser for decoding a fixed-size integer
+    ///
+    /// This `struct` is created by the [`int`] method on [`binary`](crate::util::binary).
+    /// See its documentation for more.
+    #[must_use = "Parsers are lazy and do nothing unless consumed"]
+    #[derive(Debug, Clone, Copy)]
+    pub struct Int<T>(pub(crate) Endian, pub(crate) PhantomData<T>);
+
+    /// A parser for decoding a LEB128 variable-length integer
+    ///
+    /// This `struct` is created by the [`varint`] method on [`binary`](crate::util::binary).
+    /// See its documentation for more.
+    #[must_use = "Parsers are lazy and do nothing unless consumed"]
+    #[non_exhaustive]
+    #[derive(Debug, Clone, Copy)]
+    pub struct Varint;
+
+    /// A parser for expecting a sequence of bytes
+    ///
+    /// This `struct` is created by the [`tag`] method on [`binary`](crate::util::binary).
+    /// See its documentation for more.
+    #[must_use = "Parsers are lazy and do nothing unless consumed"]
+    #[derive(Debug, Clone, Copy)]
+    pub struct Tag<'t>(pub(crate) &'t [u8]);
+
+    /// A parser for taking a length-prefixed slice of bytes
+    ///
+    /// This `struct` is created by the [`length_prefixed`] method on [`binary`](crate::util::binary).
+    /// See its documentation for more.
+    #[must_use = "Parsers are lazy and do nothing unless consumed"]
+    #[derive(Debug, Clone, Copy)]
+    pub struct LengthPrefixed<Par>(pub(crate) Par);
+}
+
+mod impls {
+    use super::*;
+
+    #[parser_fn]
+    fn int<T>(self: &Int<T>) -> Option<T>
+    where
+        INPUT: Stream<Item = u8>,
+        T: FixedInt,
+    {
+        let mut bytes = T::Bytes::default();
+        input.demand(bytes.as_mut().len());
+        for byte in bytes.as_mut() {
+            *byte = input.next()?;
+        }
+        Some(match self.0 {
+            Endian::Little => T::from_le_bytes(bytes),
+            Endian::Big => T::from_be_bytes(bytes),
+        })
+    }
+
+    #[parser_fn]
+    fn varint(self: &Varint) -> Option<u64>
+    where
+        INPUT: Stream<Item = u8>,
+    {
+        let mut value = 0u64;
+        for shift in (0..u64::BITS).step_by(7) {
+            let byte = input.next()?;
+            let bits = u64::from(byte & 0x7f);
+            if bits << shift >> shift != bits {
+                return None;
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    #[parser_fn]
+    fn tag<'t>(self: &Tag<'t>) -> bool
+    where
+        INPUT: Stream<Item = u8>,
+    {
+        input.demand(self.0.len());
+        self.0.iter().all(|&byte| input.next() == Some(byte))
+    }
+
+    #[parser_fn]
+    fn length_prefixed<par>(self: &LengthPrefixed<par>) -> Option<INPUT::Slice>
+    where
+        INPUT: StreamSlice<Item = u8>,
+        val![par]: TryInto<usize>,
+    {
+        let length = match parse![self.0].control_flow() {
+            Continue(length) => length.try_into().ok()?,
+            Break(_) => return None,
+        };
+        input.demand(length);
+        let start = input.slice_offset();
+        for _ in 0..length {
+            input.next()?;
+        }
+        Some(input.slice_since(start))
+    }
+}
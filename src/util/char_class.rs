@@ -0,0 +1,313 @@
+use crate::parser::prelude::internal::*;
+use std::ops::{BitAnd, BitOr, Not, RangeInclusive};
+
+/// A set of `char`s, parsing any `char` it contains
+///
+/// Classes compose through `|` (union), `&` (intersection) and `!` (negation).
+/// Membership of ASCII `char`s is precomputed into a lookup table, so that only
+/// the other `char`s go through the composition.
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// use lavan::prelude::*;
+///
+/// let vowel = CharClass::chars("aeiou");
+/// let consonant = CharClass::range('a'..='z') & !vowel.clone();
+///
+/// assert!(vowel.contains('e'));
+/// assert!(consonant.contains('z'));
+/// assert!(!consonant.contains('a'));
+/// assert_eq!(consonant.parse(&mut "xy".chars()), Some('x'));
+///
+/// let greek = CharClass::range('α'..='ω');
+/// let letter = CharClass::predicate(Predicate::Lowercase) & !greek;
+/// assert!(letter.contains('é'));
+/// assert!(!letter.contains('λ'));
+///
+/// let punctuation = CharClass::category(Category::OpenPunctuation) | Category::ClosePunctuation.into();
+/// assert!(punctuation.contains('[') && punctuation.contains(')'));
+/// assert!(!punctuation.contains('-'));
+/// ```
+#[must_use = "Parsers are lazy and do nothing unless consumed"]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharClass {
+    /// Bit `n` tells whether the `char` `n` belongs to the class
+    ascii: u128,
+    set: Set,
+}
+
+/// The Unicode properties exposed by the methods of `char`
+///
+/// These are derived properties, spanning several [general categories](Category):
+/// [Alphabetic](Predicate::Alphabetic) also holds for letter numbers such as `'Ⅻ'`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Predicate {
+    Alphabetic,
+    Alphanumeric,
+    Numeric,
+    Lowercase,
+    Uppercase,
+    Whitespace,
+    Control,
+}
+
+/// A Unicode general category, as of Unicode 14.0
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// use lavan::prelude::*;
+///
+/// assert_eq!(Category::of('A'), Category::UppercaseLetter);
+/// assert_eq!(Category::of('ǅ'), Category::TitlecaseLetter);
+/// assert_eq!(Category::of('€'), Category::CurrencySymbol);
+/// assert_eq!(Category::of('\u{e000}'), Category::PrivateUse);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    /// `Lu`
+    UppercaseLetter,
+    /// `Ll`
+    LowercaseLetter,
+    /// `Lt`
+    TitlecaseLetter,
+    /// `Lm`
+    ModifierLetter,
+    /// `Lo`
+    OtherLetter,
+    /// `Mn`
+    NonspacingMark,
+    /// `Mc`
+    SpacingMark,
+    /// `Me`
+    EnclosingMark,
+    /// `Nd`
+    DecimalNumber,
+    /// `Nl`
+    LetterNumber,
+    /// `No`
+    OtherNumber,
+    /// `Pc`
+    ConnectorPunctuation,
+    /// `Pd`
+    DashPunctuation,
+    /// `Ps`
+    OpenPunctuation,
+    /// `Pe`
+    ClosePunctuation,
+    /// `Pi`
+    InitialPunctuation,
+    /// `Pf`
+    FinalPunctuation,
+    /// `Po`
+    OtherPunctuation,
+    /// `Sm`
+    MathSymbol,
+    /// `Sc`
+    CurrencySymbol,
+    /// `Sk`
+    ModifierSymbol,
+    /// `So`
+    OtherSymbol,
+    /// `Zs`
+    SpaceSeparator,
+    /// `Zl`
+    LineSeparator,
+    /// `Zp`
+    ParagraphSeparator,
+    /// `Cc`
+    Control,
+    /// `Cf`
+    Format,
+    /// `Co`
+    PrivateUse,
+    /// `Cn`
+    Unassigned,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Set {
+    Ranges(Vec<RangeInclusive<char>>),
+    Predicate(Predicate),
+    Category(Category),
+    Union(Box<Set>, Box<Set>),
+    Intersection(Box<Set>, Box<Set>),
+    Not(Box<Set>),
+}
+
+impl Predicate {
+    pub fn contains(self, c: char) -> bool {
+        match self {
+            Predicate::Alphabetic => c.is_alphabetic(),
+            Predicate::Alphanumeric => c.is_alphanumeric(),
+            Predicate::Numeric => c.is_numeric(),
+            Predicate::Lowercase => c.is_lowercase(),
+            Predicate::Uppercase => c.is_uppercase(),
+            Predicate::Whitespace => c.is_whitespace(),
+            Predicate::Control => c.is_control(),
+        }
+    }
+}
+
+impl Category {
+    /// The general category of `c`
+    pub fn of(c: char) -> Self {
+        super::general_category::category(c)
+    }
+
+    pub fn contains(self, c: char) -> bool {
+        Self::of(c) == self
+    }
+}
+
+impl Set {
+    fn contains(&self, c: char) -> bool {
+        match self {
+            Set::Ranges(ranges) => ranges.iter().any(|range| range.contains(&c)),
+            Set::Predicate(predicate) => predicate.contains(c),
+            Set::Category(category) => category.contains(c),
+            Set::Union(lhs, rhs) => lhs.contains(c) || rhs.contains(c),
+            Set::Intersection(lhs, rhs) => lhs.contains(c) && rhs.contains(c),
+            Set::Not(set) => !set.contains(c),
+        }
+    }
+}
+
+impl CharClass {
+    fn new(set: Set) -> Self {
+        let ascii = (0..128u8)
+            .filter(|&b| set.contains(b as char))
+            .fold(0, |ascii, b| ascii | 1 << b);
+        Self { ascii, set }
+    }
+
+    /// The class of no `char`
+    pub fn empty() -> Self {
+        Self::new(Set::Ranges(vec![]))
+    }
+
+    /// The class of every `char`
+    pub fn any() -> Self {
+        Self::new(Set::Ranges(vec![char::MIN..=char::MAX]))
+    }
+
+    /// The class of the `char`s of `chars`
+    pub fn chars(chars: &str) -> Self {
+        Self::new(Set::Ranges(chars.chars().map(|c| c..=c).collect()))
+    }
+
+    pub fn range(range: RangeInclusive<char>) -> Self {
+        Self::new(Set::Ranges(vec![range]))
+    }
+
+    pub fn predicate(predicate: Predicate) -> Self {
+        Self::new(Set::Predicate(predicate))
+    }
+
+    pub fn category(category: Category) -> Self {
+        Self::new(Set::Category(category))
+    }
+
+    pub fn contains(&self, c: char) -> bool {
+        if c.is_ascii() {
+            self.ascii & 1 << c as u32 != 0
+        } else {
+            self.set.contains(c)
+        }
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            ascii: self.ascii | other.ascii,
+            set: Set::Union(Box::new(self.set), Box::new(other.set)),
+        }
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self {
+            ascii: self.ascii & other.ascii,
+            set: Set::Intersection(Box::new(self.set), Box::new(other.set)),
+        }
+    }
+
+    pub fn negate(self) -> Self {
+        let set = match self.set {
+            Set::Not(set) => *set,
+            set => Set::Not(Box::new(set)),
+        };
+        Self {
+            ascii: !self.ascii,
+            set,
+        }
+    }
+}
+
+impl BitOr for CharClass {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl BitAnd for CharClass {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+impl Not for CharClass {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        self.negate()
+    }
+}
+
+impl From<char> for CharClass {
+    fn from(c: char) -> Self {
+        Self::range(c..=c)
+    }
+}
+
+impl From<&str> for CharClass {
+    fn from(chars: &str) -> Self {
+        Self::chars(chars)
+    }
+}
+
+impl From<RangeInclusive<char>> for CharClass {
+    fn from(range: RangeInclusive<char>) -> Self {
+        Self::range(range)
+    }
+}
+
+impl From<Predicate> for CharClass {
+    fn from(predicate: Predicate) -> Self {
+        Self::predicate(predicate)
+    }
+}
+
+impl From<Category> for CharClass {
+    fn from(category: Category) -> Self {
+        Self::category(category)
+    }
+}
+
+#[parser_fn]
+fn char_class(self: &CharClass) -> Option<char>
+where
+    INPUT: Stream<Item = char>,
+{
+    let state = input.savestate();
+    match input.next() {
+        Some(c) if self.contains(c) => Some(c),
+        _ => {
+            input.backtrack(state);
+            None
+        }
+    }
+}
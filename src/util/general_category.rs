@@ -0,0 +1,3290 @@
+//! The Unicode general categories of `char`s, as of Unicode 14.0.0
+//!
+//! Generated from the Unicode Character Database: each row is an inclusive range
+//! of code points sharing a category, sorted by start. Unassigned code points are
+//! left out.
+
+use super::char_class::Category::{self, *};
+
+/// Looks up the category of `c` by binary search
+pub(super) fn category(c: char) -> Category {
+    let c = c as u32;
+    let index = TABLE.partition_point(|&(_, end, _)| end < c);
+    match TABLE.get(index) {
+        Some(&(start, _, category)) if start <= c => category,
+        _ => Unassigned,
+    }
+}
+
+#[rustfmt::skip]
+const TABLE: &[(u32, u32, Category)] = &[
+    (0x0, 0x1f, Control),
+    (0x20, 0x20, SpaceSeparator),
+    (0x21, 0x23, OtherPunctuation),
+    (0x24, 0x24, CurrencySymbol),
+    (0x25, 0x27, OtherPunctuation),
+    (0x28, 0x28, OpenPunctuation),
+    (0x29, 0x29, ClosePunctuation),
+    (0x2a, 0x2a, OtherPunctuation),
+    (0x2b, 0x2b, MathSymbol),
+    (0x2c, 0x2c, OtherPunctuation),
+    (0x2d, 0x2d, DashPunctuation),
+    (0x2e, 0x2f, OtherPunctuation),
+    (0x30, 0x39, DecimalNumber),
+    (0x3a, 0x3b, OtherPunctuation),
+    (0x3c, 0x3e, MathSymbol),
+    (0x3f, 0x40, OtherPunctuation),
+    (0x41, 0x5a, UppercaseLetter),
+    (0x5b, 0x5b, OpenPunctuation),
+    (0x5c, 0x5c, OtherPunctuation),
+    (0x5d, 0x5d, ClosePunctuation),
+    (0x5e, 0x5e, ModifierSymbol),
+    (0x5f, 0x5f, ConnectorPunctuation),
+    (0x60, 0x60, ModifierSymbol),
+    (0x61, 0x7a, LowercaseLetter),
+    (0x7b, 0x7b, OpenPunctuation),
+    (0x7c, 0x7c, MathSymbol),
+    (0x7d, 0x7d, ClosePunctuation),
+    (0x7e, 0x7e, MathSymbol),
+    (0x7f, 0x9f, Control),
+    (0xa0, 0xa0, SpaceSeparator),
+    (0xa1, 0xa1, OtherPunctuation),
+    (0xa2, 0xa5, CurrencySymbol),
+    (0xa6, 0xa6, OtherSymbol),
+    (0xa7, 0xa7, OtherPunctuation),
+    (0xa8, 0xa8, ModifierSymbol),
+    (0xa9, 0xa9, OtherSymbol),
+    (0xaa, 0xaa, OtherLetter),
+    (0xab, 0xab, InitialPunctuation),
+    (0xac, 0xac, MathSymbol),
+    (0xad, 0xad, Format),
+    (0xae, 0xae, OtherSymbol),
+    (0xaf, 0xaf, ModifierSymbol),
+    (0xb0, 0xb0, OtherSymbol),
+    (0xb1, 0xb1, MathSymbol),
+    (0xb2, 0xb3, OtherNumber),
+    (0xb4, 0xb4, ModifierSymbol),
+    (0xb5, 0xb5, LowercaseLetter),
+    (0xb6, 0xb7, OtherPunctuation),
+    (0xb8, 0xb8, ModifierSymbol),
+    (0xb9, 0xb9, OtherNumber),
+    (0xba, 0xba, OtherLetter),
+    (0xbb, 0xbb, FinalPunctuation),
+    (0xbc, 0xbe, OtherNumber),
+    (0xbf, 0xbf, OtherPunctuation),
+    (0xc0, 0xd6, UppercaseLetter),
+    (0xd7, 0xd7, MathSymbol),
+    (0xd8, 0xde, UppercaseLetter),
+    (0xdf, 0xf6, LowercaseLetter),
+    (0xf7, 0xf7, MathSymbol),
+    (0xf8, 0xff, LowercaseLetter),
+    (0x100, 0x100, UppercaseLetter),
+    (0x101, 0x101, LowercaseLetter),
+    (0x102, 0x102, UppercaseLetter),
+    (0x103, 0x103, LowercaseLetter),
+    (0x104, 0x104, UppercaseLetter),
+    (0x105, 0x105, LowercaseLetter),
+    (0x106, 0x106, UppercaseLetter),
+    (0x107, 0x107, LowercaseLetter),
+    (0x108, 0x108, UppercaseLetter),
+    (0x109, 0x109, LowercaseLetter),
+    (0x10a, 0x10a, UppercaseLetter),
+    (0x10b, 0x10b, LowercaseLetter),
+    (0x10c, 0x10c, UppercaseLetter),
+    (0x10d, 0x10d, LowercaseLetter),
+    (0x10e, 0x10e, UppercaseLetter),
+    (0x10f, 0x10f, LowercaseLetter),
+    (0x110, 0x110, UppercaseLetter),
+    (0x111, 0x111, LowercaseLetter),
+    (0x112, 0x112, UppercaseLetter),
+    (0x113, 0x113, LowercaseLetter),
+    (0x114, 0x114, UppercaseLetter),
+    (0x115, 0x115, LowercaseLetter),
+    (0x116, 0x116, UppercaseLetter),
+    (0x117, 0x117, LowercaseLetter),
+    (0x118, 0x118, UppercaseLetter),
+    (0x119, 0x119, LowercaseLetter),
+    (0x11a, 0x11a, UppercaseLetter),
+    (0x11b, 0x11b, LowercaseLetter),
+    (0x11c, 0x11c, UppercaseLetter),
+    (0x11d, 0x11d, LowercaseLetter),
+    (0x11e, 0x11e, UppercaseLetter),
+    (0x11f, 0x11f, LowercaseLetter),
+    (0x120, 0x120, UppercaseLetter),
+    (0x121, 0x121, LowercaseLetter),
+    (0x122, 0x122, UppercaseLetter),
+    (0x123, 0x123, LowercaseLetter),
+    (0x124, 0x124, UppercaseLetter),
+    (0x125, 0x125, LowercaseLetter),
+    (0x126, 0x126, UppercaseLetter),
+    (0x127, 0x127, LowercaseLetter),
+    (0x128, 0x128, UppercaseLetter),
+    (0x129, 0x129, LowercaseLetter),
+    (0x12a, 0x12a, UppercaseLetter),
+    (0x12b, 0x12b, LowercaseLetter),
+    (0x12c, 0x12c, UppercaseLetter),
+    (0x12d, 0x12d, LowercaseLetter),
+    (0x12e, 0x12e, UppercaseLetter),
+    (0x12f, 0x12f, LowercaseLetter),
+    (0x130, 0x130, UppercaseLetter),
+    (0x131, 0x131, LowercaseLetter),
+    (0x132, 0x132, UppercaseLetter),
+    (0x133, 0x133, LowercaseLetter),
+    (0x134, 0x134, UppercaseLetter),
+    (0x135, 0x135, LowercaseLetter),
+    (0x136, 0x136, UppercaseLetter),
+    (0x137, 0x138, LowercaseLetter),
+    (0x139, 0x139, UppercaseLetter),
+    (0x13a, 0x13a, LowercaseLetter),
+    (0x13b, 0x13b, UppercaseLetter),
+    (0x13c, 0x13c, LowercaseLetter),
+    (0x13d, 0x13d, UppercaseLetter),
+    (0x13e, 0x13e, LowercaseLetter),
+    (0x13f, 0x13f, UppercaseLetter),
+    (0x140, 0x140, LowercaseLetter),
+    (0x141, 0x141, UppercaseLetter),
+    (0x142, 0x142, LowercaseLetter),
+    (0x143, 0x143, UppercaseLetter),
+    (0x144, 0x144, LowercaseLetter),
+    (0x145, 0x145, UppercaseLetter),
+    (0x146, 0x146, LowercaseLetter),
+    (0x147, 0x147, UppercaseLetter),
+    (0x148, 0x149, LowercaseLetter),
+    (0x14a, 0x14a, UppercaseLetter),
+    (0x14b, 0x14b, LowercaseLetter),
+    (0x14c, 0x14c, UppercaseLetter),
+    (0x14d, 0x14d, LowercaseLetter),
+    (0x14e, 0x14e, UppercaseLetter),
+    (0x14f, 0x14f, LowercaseLetter),
+    (0x150, 0x150, UppercaseLetter),
+    (0x151, 0x151, LowercaseLetter),
+    (0x152, 0x152, UppercaseLetter),
+    (0x153, 0x153, LowercaseLetter),
+    (0x154, 0x154, UppercaseLetter),
+    (0x155, 0x155, LowercaseLetter),
+    (0x156, 0x156, UppercaseLetter),
+    (0x157, 0x157, LowercaseLetter),
+    (0x158, 0x158, UppercaseLetter),
+    (0x159, 0x159, LowercaseLetter),
+    (0x15a, 0x15a, UppercaseLetter),
+    (0x15b, 0x15b, LowercaseLetter),
+    (0x15c, 0x15c, UppercaseLetter),
+    (0x15d, 0x15d, LowercaseLetter),
+    (0x15e, 0x15e, UppercaseLetter),
+    (0x15f, 0x15f, LowercaseLetter),
+    (0x160, 0x160, UppercaseLetter),
+    (0x161, 0x161, LowercaseLetter),
+    (0x162, 0x162, UppercaseLetter),
+    (0x163, 0x163, LowercaseLetter),
+    (0x164, 0x164, UppercaseLetter),
+    (0x165, 0x165, LowercaseLetter),
+    (0x166, 0x166, UppercaseLetter),
+    (0x167, 0x167, LowercaseLetter),
+    (0x168, 0x168, UppercaseLetter),
+    (0x169, 0x169, LowercaseLetter),
+    (0x16a, 0x16a, UppercaseLetter),
+    (0x16b, 0x16b, LowercaseLetter),
+    (0x16c, 0x16c, UppercaseLetter),
+    (0x16d, 0x16d, LowercaseLetter),
+    (0x16e, 0x16e, UppercaseLetter),
+    (0x16f, 0x16f, LowercaseLetter),
+    (0x170, 0x170, UppercaseLetter),
+    (0x171, 0x171, LowercaseLetter),
+    (0x172, 0x172, UppercaseLetter),
+    (0x173, 0x173, LowercaseLetter),
+    (0x174, 0x174, UppercaseLetter),
+    (0x175, 0x175, LowercaseLetter),
+    (0x176, 0x176, UppercaseLetter),
+    (0x177, 0x177, LowercaseLetter),
+    (0x178, 0x179, UppercaseLetter),
+    (0x17a, 0x17a, LowercaseLetter),
+    (0x17b, 0x17b, UppercaseLetter),
+    (0x17c, 0x17c, LowercaseLetter),
+    (0x17d, 0x17d, UppercaseLetter),
+    (0x17e, 0x180, LowercaseLetter),
+    (0x181, 0x182, UppercaseLetter),
+    (0x183, 0x183, LowercaseLetter),
+    (0x184, 0x184, UppercaseLetter),
+    (0x185, 0x185, LowercaseLetter),
+    (0x186, 0x187, UppercaseLetter),
+    (0x188, 0x188, LowercaseLetter),
+    (0x189, 0x18b, UppercaseLetter),
+    (0x18c, 0x18d, LowercaseLetter),
+    (0x18e, 0x191, UppercaseLetter),
+    (0x192, 0x192, LowercaseLetter),
+    (0x193, 0x194, UppercaseLetter),
+    (0x195, 0x195, LowercaseLetter),
+    (0x196, 0x198, UppercaseLetter),
+    (0x199, 0x19b, LowercaseLetter),
+    (0x19c, 0x19d, UppercaseLetter),
+    (0x19e, 0x19e, LowercaseLetter),
+    (0x19f, 0x1a0, UppercaseLetter),
+    (0x1a1, 0x1a1, LowercaseLetter),
+    (0x1a2, 0x1a2, UppercaseLetter),
+    (0x1a3, 0x1a3, LowercaseLetter),
+    (0x1a4, 0x1a4, UppercaseLetter),
+    (0x1a5, 0x1a5, LowercaseLetter),
+    (0x1a6, 0x1a7, UppercaseLetter),
+    (0x1a8, 0x1a8, LowercaseLetter),
+    (0x1a9, 0x1a9, UppercaseLetter),
+    (0x1aa, 0x1ab, LowercaseLetter),
+    (0x1ac, 0x1ac, UppercaseLetter),
+    (0x1ad, 0x1ad, LowercaseLetter),
+    (0x1ae, 0x1af, UppercaseLetter),
+    (0x1b0, 0x1b0, LowercaseLetter),
+    (0x1b1, 0x1b3, UppercaseLetter),
+    (0x1b4, 0x1b4, LowercaseLetter),
+    (0x1b5, 0x1b5, UppercaseLetter),
+    (0x1b6, 0x1b6, LowercaseLetter),
+    (0x1b7, 0x1b8, UppercaseLetter),
+    (0x1b9, 0x1ba, LowercaseLetter),
+    (0x1bb, 0x1bb, OtherLetter),
+    (0x1bc, 0x1bc, UppercaseLetter),
+    (0x1bd, 0x1bf, LowercaseLetter),
+    (0x1c0, 0x1c3, OtherLetter),
+    (0x1c4, 0x1c4, UppercaseLetter),
+    (0x1c5, 0x1c5, TitlecaseLetter),
+    (0x1c6, 0x1c6, LowercaseLetter),
+    (0x1c7, 0x1c7, UppercaseLetter),
+    (0x1c8, 0x1c8, TitlecaseLetter),
+    (0x1c9, 0x1c9, LowercaseLetter),
+    (0x1ca, 0x1ca, UppercaseLetter),
+    (0x1cb, 0x1cb, TitlecaseLetter),
+    (0x1cc, 0x1cc, LowercaseLetter),
+    (0x1cd, 0x1cd, UppercaseLetter),
+    (0x1ce, 0x1ce, LowercaseLetter),
+    (0x1cf, 0x1cf, UppercaseLetter),
+    (0x1d0, 0x1d0, LowercaseLetter),
+    (0x1d1, 0x1d1, UppercaseLetter),
+    (0x1d2, 0x1d2, LowercaseLetter),
+    (0x1d3, 0x1d3, UppercaseLetter),
+    (0x1d4, 0x1d4, LowercaseLetter),
+    (0x1d5, 0x1d5, UppercaseLetter),
+    (0x1d6, 0x1d6, LowercaseLetter),
+    (0x1d7, 0x1d7, UppercaseLetter),
+    (0x1d8, 0x1d8, LowercaseLetter),
+    (0x1d9, 0x1d9, UppercaseLetter),
+    (0x1da, 0x1da, LowercaseLetter),
+    (0x1db, 0x1db, UppercaseLetter),
+    (0x1dc, 0x1dd, LowercaseLetter),
+    (0x1de, 0x1de, UppercaseLetter),
+    (0x1df, 0x1df, LowercaseLetter),
+    (0x1e0, 0x1e0, UppercaseLetter),
+    (0x1e1, 0x1e1, LowercaseLetter),
+    (0x1e2, 0x1e2, UppercaseLetter),
+    (0x1e3, 0x1e3, LowercaseLetter),
+    (0x1e4, 0x1e4, UppercaseLetter),
+    (0x1e5, 0x1e5, LowercaseLetter),
+    (0x1e6, 0x1e6, UppercaseLetter),
+    (0x1e7, 0x1e7, LowercaseLetter),
+    (0x1e8, 0x1e8, UppercaseLetter),
+    (0x1e9, 0x1e9, LowercaseLetter),
+    (0x1ea, 0x1ea, UppercaseLetter),
+    (0x1eb, 0x1eb, LowercaseLetter),
+    (0x1ec, 0x1ec, UppercaseLetter),
+    (0x1ed, 0x1ed, LowercaseLetter),
+    (0x1ee, 0x1ee, UppercaseLetter),
+    (0x1ef, 0x1f0, LowercaseLetter),
+    (0x1f1, 0x1f1, UppercaseLetter),
+    (0x1f2, 0x1f2, TitlecaseLetter),
+    (0x1f3, 0x1f3, LowercaseLetter),
+    (0x1f4, 0x1f4, UppercaseLetter),
+    (0x1f5, 0x1f5, LowercaseLetter),
+    (0x1f6, 0x1f8, UppercaseLetter),
+    (0x1f9, 0x1f9, LowercaseLetter),
+    (0x1fa, 0x1fa, UppercaseLetter),
+    (0x1fb, 0x1fb, LowercaseLetter),
+    (0x1fc, 0x1fc, UppercaseLetter),
+    (0x1fd, 0x1fd, LowercaseLetter),
+    (0x1fe, 0x1fe, UppercaseLetter),
+    (0x1ff, 0x1ff, LowercaseLetter),
+    (0x200, 0x200, UppercaseLetter),
+    (0x201, 0x201, LowercaseLetter),
+    (0x202, 0x202, UppercaseLetter),
+    (0x203, 0x203, LowercaseLetter),
+    (0x204, 0x204, UppercaseLetter),
+    (0x205, 0x205, LowercaseLetter),
+    (0x206, 0x206, UppercaseLetter),
+    (0x207, 0x207, LowercaseLetter),
+    (0x208, 0x208, UppercaseLetter),
+    (0x209, 0x209, LowercaseLetter),
+    (0x20a, 0x20a, UppercaseLetter),
+    (0x20b, 0x20b, LowercaseLetter),
+    (0x20c, 0x20c, UppercaseLetter),
+    (0x20d, 0x20d, LowercaseLetter),
+    (0x20e, 0x20e, UppercaseLetter),
+    (0x20f, 0x20f, LowercaseLetter),
+    (0x210, 0x210, UppercaseLetter),
+    (0x211, 0x211, LowercaseLetter),
+    (0x212, 0x212, UppercaseLetter),
+    (0x213, 0x213, LowercaseLetter),
+    (0x214, 0x214, UppercaseLetter),
+    (0x215, 0x215, LowercaseLetter),
+    (0x216, 0x216, UppercaseLetter),
+    (0x217, 0x217, LowercaseLetter),
+    (0x218, 0x218, UppercaseLetter),
+    (0x219, 0x219, LowercaseLetter),
+    (0x21a, 0x21a, UppercaseLetter),
+    (0x21b, 0x21b, LowercaseLetter),
+    (0x21c, 0x21c, UppercaseLetter),
+    (0x21d, 0x21d, LowercaseLetter),
+    (0x21e, 0x21e, UppercaseLetter),
+    (0x21f, 0x21f, LowercaseLetter),
+    (0x220, 0x220, UppercaseLetter),
+    (0x221, 0x221, LowercaseLetter),
+    (0x222, 0x222, UppercaseLetter),
+    (0x223, 0x223, LowercaseLetter),
+    (0x224, 0x224, UppercaseLetter),
+    (0x225, 0x225, LowercaseLetter),
+    (0x226, 0x226, UppercaseLetter),
+    (0x227, 0x227, LowercaseLetter),
+    (0x228, 0x228, UppercaseLetter),
+    (0x229, 0x229, LowercaseLetter),
+    (0x22a, 0x22a, UppercaseLetter),
+    (0x22b, 0x22b, LowercaseLetter),
+    (0x22c, 0x22c, UppercaseLetter),
+    (0x22d, 0x22d, LowercaseLetter),
+    (0x22e, 0x22e, UppercaseLetter),
+    (0x22f, 0x22f, LowercaseLetter),
+    (0x230, 0x230, UppercaseLetter),
+    (0x231, 0x231, LowercaseLetter),
+    (0x232, 0x232, UppercaseLetter),
+    (0x233, 0x239, LowercaseLetter),
+    (0x23a, 0x23b, UppercaseLetter),
+    (0x23c, 0x23c, LowercaseLetter),
+    (0x23d, 0x23e, UppercaseLetter),
+    (0x23f, 0x240, LowercaseLetter),
+    (0x241, 0x241, UppercaseLetter),
+    (0x242, 0x242, LowercaseLetter),
+    (0x243, 0x246, UppercaseLetter),
+    (0x247, 0x247, LowercaseLetter),
+    (0x248, 0x248, UppercaseLetter),
+    (0x249, 0x249, LowercaseLetter),
+    (0x24a, 0x24a, UppercaseLetter),
+    (0x24b, 0x24b, LowercaseLetter),
+    (0x24c, 0x24c, UppercaseLetter),
+    (0x24d, 0x24d, LowercaseLetter),
+    (0x24e, 0x24e, UppercaseLetter),
+    (0x24f, 0x293, LowercaseLetter),
+    (0x294, 0x294, OtherLetter),
+    (0x295, 0x2af, LowercaseLetter),
+    (0x2b0, 0x2c1, ModifierLetter),
+    (0x2c2, 0x2c5, ModifierSymbol),
+    (0x2c6, 0x2d1, ModifierLetter),
+    (0x2d2, 0x2df, ModifierSymbol),
+    (0x2e0, 0x2e4, ModifierLetter),
+    (0x2e5, 0x2eb, ModifierSymbol),
+    (0x2ec, 0x2ec, ModifierLetter),
+    (0x2ed, 0x2ed, ModifierSymbol),
+    (0x2ee, 0x2ee, ModifierLetter),
+    (0x2ef, 0x2ff, ModifierSymbol),
+    (0x300, 0x36f, NonspacingMark),
+    (0x370, 0x370, UppercaseLetter),
+    (0x371, 0x371, LowercaseLetter),
+    (0x372, 0x372, UppercaseLetter),
+    (0x373, 0x373, LowercaseLetter),
+    (0x374, 0x374, ModifierLetter),
+    (0x375, 0x375, ModifierSymbol),
+    (0x376, 0x376, UppercaseLetter),
+    (0x377, 0x377, LowercaseLetter),
+    (0x37a, 0x37a, ModifierLetter),
+    (0x37b, 0x37d, LowercaseLetter),
+    (0x37e, 0x37e, OtherPunctuation),
+    (0x37f, 0x37f, UppercaseLetter),
+    (0x384, 0x385, ModifierSymbol),
+    (0x386, 0x386, UppercaseLetter),
+    (0x387, 0x387, OtherPunctuation),
+    (0x388, 0x38a, UppercaseLetter),
+    (0x38c, 0x38c, UppercaseLetter),
+    (0x38e, 0x38f, UppercaseLetter),
+    (0x390, 0x390, LowercaseLetter),
+    (0x391, 0x3a1, UppercaseLetter),
+    (0x3a3, 0x3ab, UppercaseLetter),
+    (0x3ac, 0x3ce, LowercaseLetter),
+    (0x3cf, 0x3cf, UppercaseLetter),
+    (0x3d0, 0x3d1, LowercaseLetter),
+    (0x3d2, 0x3d4, UppercaseLetter),
+    (0x3d5, 0x3d7, LowercaseLetter),
+    (0x3d8, 0x3d8, UppercaseLetter),
+    (0x3d9, 0x3d9, LowercaseLetter),
+    (0x3da, 0x3da, UppercaseLetter),
+    (0x3db, 0x3db, LowercaseLetter),
+    (0x3dc, 0x3dc, UppercaseLetter),
+    (0x3dd, 0x3dd, LowercaseLetter),
+    (0x3de, 0x3de, UppercaseLetter),
+    (0x3df, 0x3df, LowercaseLetter),
+    (0x3e0, 0x3e0, UppercaseLetter),
+    (0x3e1, 0x3e1, LowercaseLetter),
+    (0x3e2, 0x3e2, UppercaseLetter),
+    (0x3e3, 0x3e3, LowercaseLetter),
+    (0x3e4, 0x3e4, UppercaseLetter),
+    (0x3e5, 0x3e5, LowercaseLetter),
+    (0x3e6, 0x3e6, UppercaseLetter),
+    (0x3e7, 0x3e7, LowercaseLetter),
+    (0x3e8, 0x3e8, UppercaseLetter),
+    (0x3e9, 0x3e9, LowercaseLetter),
+    (0x3ea, 0x3ea, UppercaseLetter),
+    (0x3eb, 0x3eb, LowercaseLetter),
+    (0x3ec, 0x3ec, UppercaseLetter),
+    (0x3ed, 0x3ed, LowercaseLetter),
+    (0x3ee, 0x3ee, UppercaseLetter),
+    (0x3ef, 0x3f3, LowercaseLetter),
+    (0x3f4, 0x3f4, UppercaseLetter),
+    (0x3f5, 0x3f5, LowercaseLetter),
+    (0x3f6, 0x3f6, MathSymbol),
+    (0x3f7, 0x3f7, UppercaseLetter),
+    (0x3f8, 0x3f8, LowercaseLetter),
+    (0x3f9, 0x3fa, UppercaseLetter),
+    (0x3fb, 0x3fc, LowercaseLetter),
+    (0x3fd, 0x42f, UppercaseLetter),
+    (0x430, 0x45f, LowercaseLetter),
+    (0x460, 0x460, UppercaseLetter),
+    (0x461, 0x461, LowercaseLetter),
+    (0x462, 0x462, UppercaseLetter),
+    (0x463, 0x463, LowercaseLetter),
+    (0x464, 0x464, UppercaseLetter),
+    (0x465, 0x465, LowercaseLetter),
+    (0x466, 0x466, UppercaseLetter),
+    (0x467, 0x467, LowercaseLetter),
+    (0x468, 0x468, UppercaseLetter),
+    (0x469, 0x469, LowercaseLetter),
+    (0x46a, 0x46a, UppercaseLetter),
+    (0x46b, 0x46b, LowercaseLetter),
+    (0x46c, 0x46c, UppercaseLetter),
+    (0x46d, 0x46d, LowercaseLetter),
+    (0x46e, 0x46e, UppercaseLetter),
+    (0x46f, 0x46f, LowercaseLetter),
+    (0x470, 0x470, UppercaseLetter),
+    (0x471, 0x471, LowercaseLetter),
+    (0x472, 0x472, UppercaseLetter),
+    (0x473, 0x473, LowercaseLetter),
+    (0x474, 0x474, UppercaseLetter),
+    (0x475, 0x475, LowercaseLetter),
+    (0x476, 0x476, UppercaseLetter),
+    (0x477, 0x477, LowercaseLetter),
+    (0x478, 0x478, UppercaseLetter),
+    (0x479, 0x479, LowercaseLetter),
+    (0x47a, 0x47a, UppercaseLetter),
+    (0x47b, 0x47b, LowercaseLetter),
+    (0x47c, 0x47c, UppercaseLetter),
+    (0x47d, 0x47d, LowercaseLetter),
+    (0x47e, 0x47e, UppercaseLetter),
+    (0x47f, 0x47f, LowercaseLetter),
+    (0x480, 0x480, UppercaseLetter),
+    (0x481, 0x481, LowercaseLetter),
+    (0x482, 0x482, OtherSymbol),
+    (0x483, 0x487, NonspacingMark),
+    (0x488, 0x489, EnclosingMark),
+    (0x48a, 0x48a, UppercaseLetter),
+    (0x48b, 0x48b, LowercaseLetter),
+    (0x48c, 0x48c, UppercaseLetter),
+    (0x48d, 0x48d, LowercaseLetter),
+    (0x48e, 0x48e, UppercaseLetter),
+    (0x48f, 0x48f, LowercaseLetter),
+    (0x490, 0x490, UppercaseLetter),
+    (0x491, 0x491, LowercaseLetter),
+    (0x492, 0x492, UppercaseLetter),
+    (0x493, 0x493, LowercaseLetter),
+    (0x494, 0x494, UppercaseLetter),
+    (0x495, 0x495, LowercaseLetter),
+    (0x496, 0x496, UppercaseLetter),
+    (0x497, 0x497, LowercaseLetter),
+    (0x498, 0x498, UppercaseLetter),
+    (0x499, 0x499, LowercaseLetter),
+    (0x49a, 0x49a, UppercaseLetter),
+    (0x49b, 0x49b, LowercaseLetter),
+    (0x49c, 0x49c, UppercaseLetter),
+    (0x49d, 0x49d, LowercaseLetter),
+    (0x49e, 0x49e, UppercaseLetter),
+    (0x49f, 0x49f, LowercaseLetter),
+    (0x4a0, 0x4a0, UppercaseLetter),
+    (0x4a1, 0x4a1, LowercaseLetter),
+    (0x4a2, 0x4a2, UppercaseLetter),
+    (0x4a3, 0x4a3, LowercaseLetter),
+    (0x4a4, 0x4a4, UppercaseLetter),
+    (0x4a5, 0x4a5, LowercaseLetter),
+    (0x4a6, 0x4a6, UppercaseLetter),
+    (0x4a7, 0x4a7, LowercaseLetter),
+    (0x4a8, 0x4a8, UppercaseLetter),
+    (0x4a9, 0x4a9, LowercaseLetter),
+    (0x4aa, 0x4aa, UppercaseLetter),
+    (0x4ab, 0x4ab, LowercaseLetter),
+    (0x4ac, 0x4ac, UppercaseLetter),
+    (0x4ad, 0x4ad, LowercaseLetter),
+    (0x4ae, 0x4ae, UppercaseLetter),
+    (0x4af, 0x4af, LowercaseLetter),
+    (0x4b0, 0x4b0, UppercaseLetter),
+    (0x4b1, 0x4b1, LowercaseLetter),
+    (0x4b2, 0x4b2, UppercaseLetter),
+    (0x4b3, 0x4b3, LowercaseLetter),
+    (0x4b4, 0x4b4, UppercaseLetter),
+    (0x4b5, 0x4b5, LowercaseLetter),
+    (0x4b6, 0x4b6, UppercaseLetter),
+    (0x4b7, 0x4b7, LowercaseLetter),
+    (0x4b8, 0x4b8, UppercaseLetter),
+    (0x4b9, 0x4b9, LowercaseLetter),
+    (0x4ba, 0x4ba, UppercaseLetter),
+    (0x4bb, 0x4bb, LowercaseLetter),
+    (0x4bc, 0x4bc, UppercaseLetter),
+    (0x4bd, 0x4bd, LowercaseLetter),
+    (0x4be, 0x4be, UppercaseLetter),
+    (0x4bf, 0x4bf, LowercaseLetter),
+    (0x4c0, 0x4c1, UppercaseLetter),
+    (0x4c2, 0x4c2, LowercaseLetter),
+    (0x4c3, 0x4c3, UppercaseLetter),
+    (0x4c4, 0x4c4, LowercaseLetter),
+    (0x4c5, 0x4c5, UppercaseLetter),
+    (0x4c6, 0x4c6, LowercaseLetter),
+    (0x4c7, 0x4c7, UppercaseLetter),
+    (0x4c8, 0x4c8, LowercaseLetter),
+    (0x4c9, 0x4c9, UppercaseLetter),
+    (0x4ca, 0x4ca, LowercaseLetter),
+    (0x4cb, 0x4cb, UppercaseLetter),
+    (0x4cc, 0x4cc, LowercaseLetter),
+    (0x4cd, 0x4cd, UppercaseLetter),
+    (0x4ce, 0x4cf, LowercaseLetter),
+    (0x4d0, 0x4d0, UppercaseLetter),
+    (0x4d1, 0x4d1, LowercaseLetter),
+    (0x4d2, 0x4d2, UppercaseLetter),
+    (0x4d3, 0x4d3, LowercaseLetter),
+    (0x4d4, 0x4d4, UppercaseLetter),
+    (0x4d5, 0x4d5, LowercaseLetter),
+    (0x4d6, 0x4d6, UppercaseLetter),
+    (0x4d7, 0x4d7, LowercaseLetter),
+    (0x4d8, 0x4d8, UppercaseLetter),
+    (0x4d9, 0x4d9, LowercaseLetter),
+    (0x4da, 0x4da, UppercaseLetter),
+    (0x4db, 0x4db, LowercaseLetter),
+    (0x4dc, 0x4dc, UppercaseLetter),
+    (0x4dd, 0x4dd, LowercaseLetter),
+    (0x4de, 0x4de, UppercaseLetter),
+    (0x4df, 0x4df, LowercaseLetter),
+    (0x4e0, 0x4e0, UppercaseLetter),
+    (0x4e1, 0x4e1, LowercaseLetter),
+    (0x4e2, 0x4e2, UppercaseLetter),
+    (0x4e3, 0x4e3, LowercaseLetter),
+    (0x4e4, 0x4e4, UppercaseLetter),
+    (0x4e5, 0x4e5, LowercaseLetter),
+    (0x4e6, 0x4e6, UppercaseLetter),
+    (0x4e7, 0x4e7, LowercaseLetter),
+    (0x4e8, 0x4e8, UppercaseLetter),
+    (0x4e9, 0x4e9, LowercaseLetter),
+    (0x4ea, 0x4ea, UppercaseLetter),
+    (0x4eb, 0x4eb, LowercaseLetter),
+    (0x4ec, 0x4ec, UppercaseLetter),
+    (0x4ed, 0x4ed, LowercaseLetter),
+    (0x4ee, 0x4ee, UppercaseLetter),
+    (0x4ef, 0x4ef, LowercaseLetter),
+    (0x4f0, 0x4f0, UppercaseLetter),
+    (0x4f1, 0x4f1, LowercaseLetter),
+    (0x4f2, 0x4f2, UppercaseLetter),
+    (0x4f3, 0x4f3, LowercaseLetter),
+    (0x4f4, 0x4f4, UppercaseLetter),
+    (0x4f5, 0x4f5, LowercaseLetter),
+    (0x4f6, 0x4f6, UppercaseLetter),
+    (0x4f7, 0x4f7, LowercaseLetter),
+    (0x4f8, 0x4f8, UppercaseLetter),
+    (0x4f9, 0x4f9, LowercaseLetter),
+    (0x4fa, 0x4fa, UppercaseLetter),
+    (0x4fb, 0x4fb, LowercaseLetter),
+    (0x4fc, 0x4fc, UppercaseLetter),
+    (0x4fd, 0x4fd, LowercaseLetter),
+    (0x4fe, 0x4fe, UppercaseLetter),
+    (0x4ff, 0x4ff, LowercaseLetter),
+    (0x500, 0x500, UppercaseLetter),
+    (0x501, 0x501, LowercaseLetter),
+    (0x502, 0x502, UppercaseLetter),
+    (0x503, 0x503, LowercaseLetter),
+    (0x504, 0x504, UppercaseLetter),
+    (0x505, 0x505, LowercaseLetter),
+    (0x506, 0x506, UppercaseLetter),
+    (0x507, 0x507, LowercaseLetter),
+    (0x508, 0x508, UppercaseLetter),
+    (0x509, 0x509, LowercaseLetter),
+    (0x50a, 0x50a, UppercaseLetter),
+    (0x50b, 0x50b, LowercaseLetter),
+    (0x50c, 0x50c, UppercaseLetter),
+    (0x50d, 0x50d, LowercaseLetter),
+    (0x50e, 0x50e, UppercaseLetter),
+    (0x50f, 0x50f, LowercaseLetter),
+    (0x510, 0x510, UppercaseLetter),
+    (0x511, 0x511, LowercaseLetter),
+    (0x512, 0x512, UppercaseLetter),
+    (0x513, 0x513, LowercaseLetter),
+    (0x514, 0x514, UppercaseLetter),
+    (0x515, 0x515, LowercaseLetter),
+    (0x516, 0x516, UppercaseLetter),
+    (0x517, 0x517, LowercaseLetter),
+    (0x518, 0x518, UppercaseLetter),
+    (0x519, 0x519, LowercaseLetter),
+    (0x51a, 0x51a, UppercaseLetter),
+    (0x51b, 0x51b, LowercaseLetter),
+    (0x51c, 0x51c, UppercaseLetter),
+    (0x51d, 0x51d, LowercaseLetter),
+    (0x51e, 0x51e, UppercaseLetter),
+    (0x51f, 0x51f, LowercaseLetter),
+    (0x520, 0x520, UppercaseLetter),
+    (0x521, 0x521, LowercaseLetter),
+    (0x522, 0x522, UppercaseLetter),
+    (0x523, 0x523, LowercaseLetter),
+    (0x524, 0x524, UppercaseLetter),
+    (0x525, 0x525, LowercaseLetter),
+    (0x526, 0x526, UppercaseLetter),
+    (0x527, 0x527, LowercaseLetter),
+    (0x528, 0x528, UppercaseLetter),
+    (0x529, 0x529, LowercaseLetter),
+    (0x52a, 0x52a, UppercaseLetter),
+    (0x52b, 0x52b, LowercaseLetter),
+    (0x52c, 0x52c, UppercaseLetter),
+    (0x52d, 0x52d, LowercaseLetter),
+    (0x52e, 0x52e, UppercaseLetter),
+    (0x52f, 0x52f, LowercaseLetter),
+    (0x531, 0x556, UppercaseLetter),
+    (0x559, 0x559, ModifierLetter),
+    (0x55a, 0x55f, OtherPunctuation),
+    (0x560, 0x588, LowercaseLetter),
+    (0x589, 0x589, OtherPunctuation),
+    (0x58a, 0x58a, DashPunctuation),
+    (0x58d, 0x58e, OtherSymbol),
+    (0x58f, 0x58f, CurrencySymbol),
+    (0x591, 0x5bd, NonspacingMark),
+    (0x5be, 0x5be, DashPunctuation),
+    (0x5bf, 0x5bf, NonspacingMark),
+    (0x5c0, 0x5c0, OtherPunctuation),
+    (0x5c1, 0x5c2, NonspacingMark),
+    (0x5c3, 0x5c3, OtherPunctuation),
+    (0x5c4, 0x5c5, NonspacingMark),
+    (0x5c6, 0x5c6, OtherPunctuation),
+    (0x5c7, 0x5c7, NonspacingMark),
+    (0x5d0, 0x5ea, OtherLetter),
+    (0x5ef, 0x5f2, OtherLetter),
+    (0x5f3, 0x5f4, OtherPunctuation),
+    (0x600, 0x605, Format),
+    (0x606, 0x608, MathSymbol),
+    (0x609, 0x60a, OtherPunctuation),
+    (0x60b, 0x60b, CurrencySymbol),
+    (0x60c, 0x60d, OtherPunctuation),
+    (0x60e, 0x60f, OtherSymbol),
+    (0x610, 0x61a, NonspacingMark),
+    (0x61b, 0x61b, OtherPunctuation),
+    (0x61c, 0x61c, Format),
+    (0x61d, 0x61f, OtherPunctuation),
+    (0x620, 0x63f, OtherLetter),
+    (0x640, 0x640, ModifierLetter),
+    (0x641, 0x64a, OtherLetter),
+    (0x64b, 0x65f, NonspacingMark),
+    (0x660, 0x669, DecimalNumber),
+    (0x66a, 0x66d, OtherPunctuation),
+    (0x66e, 0x66f, OtherLetter),
+    (0x670, 0x670, NonspacingMark),
+    (0x671, 0x6d3, OtherLetter),
+    (0x6d4, 0x6d4, OtherPunctuation),
+    (0x6d5, 0x6d5, OtherLetter),
+    (0x6d6, 0x6dc, NonspacingMark),
+    (0x6dd, 0x6dd, Format),
+    (0x6de, 0x6de, OtherSymbol),
+    (0x6df, 0x6e4, NonspacingMark),
+    (0x6e5, 0x6e6, ModifierLetter),
+    (0x6e7, 0x6e8, NonspacingMark),
+    (0x6e9, 0x6e9, OtherSymbol),
+    (0x6ea, 0x6ed, NonspacingMark),
+    (0x6ee, 0x6ef, OtherLetter),
+    (0x6f0, 0x6f9, DecimalNumber),
+    (0x6fa, 0x6fc, OtherLetter),
+    (0x6fd, 0x6fe, OtherSymbol),
+    (0x6ff, 0x6ff, OtherLetter),
+    (0x700, 0x70d, OtherPunctuation),
+    (0x70f, 0x70f, Format),
+    (0x710, 0x710, OtherLetter),
+    (0x711, 0x711, NonspacingMark),
+    (0x712, 0x72f, OtherLetter),
+    (0x730, 0x74a, NonspacingMark),
+    (0x74d, 0x7a5, OtherLetter),
+    (0x7a6, 0x7b0, NonspacingMark),
+    (0x7b1, 0x7b1, OtherLetter),
+    (0x7c0, 0x7c9, DecimalNumber),
+    (0x7ca, 0x7ea, OtherLetter),
+    (0x7eb, 0x7f3, NonspacingMark),
+    (0x7f4, 0x7f5, ModifierLetter),
+    (0x7f6, 0x7f6, OtherSymbol),
+    (0x7f7, 0x7f9, OtherPunctuation),
+    (0x7fa, 0x7fa, ModifierLetter),
+    (0x7fd, 0x7fd, NonspacingMark),
+    (0x7fe, 0x7ff, CurrencySymbol),
+    (0x800, 0x815, OtherLetter),
+    (0x816, 0x819, NonspacingMark),
+    (0x81a, 0x81a, ModifierLetter),
+    (0x81b, 0x823, NonspacingMark),
+    (0x824, 0x824, ModifierLetter),
+    (0x825, 0x827, NonspacingMark),
+    (0x828, 0x828, ModifierLetter),
+    (0x829, 0x82d, NonspacingMark),
+    (0x830, 0x83e, OtherPunctuation),
+    (0x840, 0x858, OtherLetter),
+    (0x859, 0x85b, NonspacingMark),
+    (0x85e, 0x85e, OtherPunctuation),
+    (0x860, 0x86a, OtherLetter),
+    (0x870, 0x887, OtherLetter),
+    (0x888, 0x888, ModifierSymbol),
+    (0x889, 0x88e, OtherLetter),
+    (0x890, 0x891, Format),
+    (0x898, 0x89f, NonspacingMark),
+    (0x8a0, 0x8c8, OtherLetter),
+    (0x8c9, 0x8c9, ModifierLetter),
+    (0x8ca, 0x8e1, NonspacingMark),
+    (0x8e2, 0x8e2, Format),
+    (0x8e3, 0x902, NonspacingMark),
+    (0x903, 0x903, SpacingMark),
+    (0x904, 0x939, OtherLetter),
+    (0x93a, 0x93a, NonspacingMark),
+    (0x93b, 0x93b, SpacingMark),
+    (0x93c, 0x93c, NonspacingMark),
+    (0x93d, 0x93d, OtherLetter),
+    (0x93e, 0x940, SpacingMark),
+    (0x941, 0x948, NonspacingMark),
+    (0x949, 0x94c, SpacingMark),
+    (0x94d, 0x94d, NonspacingMark),
+    (0x94e, 0x94f, SpacingMark),
+    (0x950, 0x950, OtherLetter),
+    (0x951, 0x957, NonspacingMark),
+    (0x958, 0x961, OtherLetter),
+    (0x962, 0x963, NonspacingMark),
+    (0x964, 0x965, OtherPunctuation),
+    (0x966, 0x96f, DecimalNumber),
+    (0x970, 0x970, OtherPunctuation),
+    (0x971, 0x971, ModifierLetter),
+    (0x972, 0x980, OtherLetter),
+    (0x981, 0x981, NonspacingMark),
+    (0x982, 0x983, SpacingMark),
+    (0x985, 0x98c, OtherLetter),
+    (0x98f, 0x990, OtherLetter),
+    (0x993, 0x9a8, OtherLetter),
+    (0x9aa, 0x9b0, OtherLetter),
+    (0x9b2, 0x9b2, OtherLetter),
+    (0x9b6, 0x9b9, OtherLetter),
+    (0x9bc, 0x9bc, NonspacingMark),
+    (0x9bd, 0x9bd, OtherLetter),
+    (0x9be, 0x9c0, SpacingMark),
+    (0x9c1, 0x9c4, NonspacingMark),
+    (0x9c7, 0x9c8, SpacingMark),
+    (0x9cb, 0x9cc, SpacingMark),
+    (0x9cd, 0x9cd, NonspacingMark),
+    (0x9ce, 0x9ce, OtherLetter),
+    (0x9d7, 0x9d7, SpacingMark),
+    (0x9dc, 0x9dd, OtherLetter),
+    (0x9df, 0x9e1, OtherLetter),
+    (0x9e2, 0x9e3, NonspacingMark),
+    (0x9e6, 0x9ef, DecimalNumber),
+    (0x9f0, 0x9f1, OtherLetter),
+    (0x9f2, 0x9f3, CurrencySymbol),
+    (0x9f4, 0x9f9, OtherNumber),
+    (0x9fa, 0x9fa, OtherSymbol),
+    (0x9fb, 0x9fb, CurrencySymbol),
+    (0x9fc, 0x9fc, OtherLetter),
+    (0x9fd, 0x9fd, OtherPunctuation),
+    (0x9fe, 0x9fe, NonspacingMark),
+    (0xa01, 0xa02, NonspacingMark),
+    (0xa03, 0xa03, SpacingMark),
+    (0xa05, 0xa0a, OtherLetter),
+    (0xa0f, 0xa10, OtherLetter),
+    (0xa13, 0xa28, OtherLetter),
+    (0xa2a, 0xa30, OtherLetter),
+    (0xa32, 0xa33, OtherLetter),
+    (0xa35, 0xa36, OtherLetter),
+    (0xa38, 0xa39, OtherLetter),
+    (0xa3c, 0xa3c, NonspacingMark),
+    (0xa3e, 0xa40, SpacingMark),
+    (0xa41, 0xa42, NonspacingMark),
+    (0xa47, 0xa48, NonspacingMark),
+    (0xa4b, 0xa4d, NonspacingMark),
+    (0xa51, 0xa51, NonspacingMark),
+    (0xa59, 0xa5c, OtherLetter),
+    (0xa5e, 0xa5e, OtherLetter),
+    (0xa66, 0xa6f, DecimalNumber),
+    (0xa70, 0xa71, NonspacingMark),
+    (0xa72, 0xa74, OtherLetter),
+    (0xa75, 0xa75, NonspacingMark),
+    (0xa76, 0xa76, OtherPunctuation),
+    (0xa81, 0xa82, NonspacingMark),
+    (0xa83, 0xa83, SpacingMark),
+    (0xa85, 0xa8d, OtherLetter),
+    (0xa8f, 0xa91, OtherLetter),
+    (0xa93, 0xaa8, OtherLetter),
+    (0xaaa, 0xab0, OtherLetter),
+    (0xab2, 0xab3, OtherLetter),
+    (0xab5, 0xab9, OtherLetter),
+    (0xabc, 0xabc, NonspacingMark),
+    (0xabd, 0xabd, OtherLetter),
+    (0xabe, 0xac0, SpacingMark),
+    (0xac1, 0xac5, NonspacingMark),
+    (0xac7, 0xac8, NonspacingMark),
+    (0xac9, 0xac9, SpacingMark),
+    (0xacb, 0xacc, SpacingMark),
+    (0xacd, 0xacd, NonspacingMark),
+    (0xad0, 0xad0, OtherLetter),
+    (0xae0, 0xae1, OtherLetter),
+    (0xae2, 0xae3, NonspacingMark),
+    (0xae6, 0xaef, DecimalNumber),
+    (0xaf0, 0xaf0, OtherPunctuation),
+    (0xaf1, 0xaf1, CurrencySymbol),
+    (0xaf9, 0xaf9, OtherLetter),
+    (0xafa, 0xaff, NonspacingMark),
+    (0xb01, 0xb01, NonspacingMark),
+    (0xb02, 0xb03, SpacingMark),
+    (0xb05, 0xb0c, OtherLetter),
+    (0xb0f, 0xb10, OtherLetter),
+    (0xb13, 0xb28, OtherLetter),
+    (0xb2a, 0xb30, OtherLetter),
+    (0xb32, 0xb33, OtherLetter),
+    (0xb35, 0xb39, OtherLetter),
+    (0xb3c, 0xb3c, NonspacingMark),
+    (0xb3d, 0xb3d, OtherLetter),
+    (0xb3e, 0xb3e, SpacingMark),
+    (0xb3f, 0xb3f, NonspacingMark),
+    (0xb40, 0xb40, SpacingMark),
+    (0xb41, 0xb44, NonspacingMark),
+    (0xb47, 0xb48, SpacingMark),
+    (0xb4b, 0xb4c, SpacingMark),
+    (0xb4d, 0xb4d, NonspacingMark),
+    (0xb55, 0xb56, NonspacingMark),
+    (0xb57, 0xb57, SpacingMark),
+    (0xb5c, 0xb5d, OtherLetter),
+    (0xb5f, 0xb61, OtherLetter),
+    (0xb62, 0xb63, NonspacingMark),
+    (0xb66, 0xb6f, DecimalNumber),
+    (0xb70, 0xb70, OtherSymbol),
+    (0xb71, 0xb71, OtherLetter),
+    (0xb72, 0xb77, OtherNumber),
+    (0xb82, 0xb82, NonspacingMark),
+    (0xb83, 0xb83, OtherLetter),
+    (0xb85, 0xb8a, OtherLetter),
+    (0xb8e, 0xb90, OtherLetter),
+    (0xb92, 0xb95, OtherLetter),
+    (0xb99, 0xb9a, OtherLetter),
+    (0xb9c, 0xb9c, OtherLetter),
+    (0xb9e, 0xb9f, OtherLetter),
+    (0xba3, 0xba4, OtherLetter),
+    (0xba8, 0xbaa, OtherLetter),
+    (0xbae, 0xbb9, OtherLetter),
+    (0xbbe, 0xbbf, SpacingMark),
+    (0xbc0, 0xbc0, NonspacingMark),
+    (0xbc1, 0xbc2, SpacingMark),
+    (0xbc6, 0xbc8, SpacingMark),
+    (0xbca, 0xbcc, SpacingMark),
+    (0xbcd, 0xbcd, NonspacingMark),
+    (0xbd0, 0xbd0, OtherLetter),
+    (0xbd7, 0xbd7, SpacingMark),
+    (0xbe6, 0xbef, DecimalNumber),
+    (0xbf0, 0xbf2, OtherNumber),
+    (0xbf3, 0xbf8, OtherSymbol),
+    (0xbf9, 0xbf9, CurrencySymbol),
+    (0xbfa, 0xbfa, OtherSymbol),
+    (0xc00, 0xc00, NonspacingMark),
+    (0xc01, 0xc03, SpacingMark),
+    (0xc04, 0xc04, NonspacingMark),
+    (0xc05, 0xc0c, OtherLetter),
+    (0xc0e, 0xc10, OtherLetter),
+    (0xc12, 0xc28, OtherLetter),
+    (0xc2a, 0xc39, OtherLetter),
+    (0xc3c, 0xc3c, NonspacingMark),
+    (0xc3d, 0xc3d, OtherLetter),
+    (0xc3e, 0xc40, NonspacingMark),
+    (0xc41, 0xc44, SpacingMark),
+    (0xc46, 0xc48, NonspacingMark),
+    (0xc4a, 0xc4d, NonspacingMark),
+    (0xc55, 0xc56, NonspacingMark),
+    (0xc58, 0xc5a, OtherLetter),
+    (0xc5d, 0xc5d, OtherLetter),
+    (0xc60, 0xc61, OtherLetter),
+    (0xc62, 0xc63, NonspacingMark),
+    (0xc66, 0xc6f, DecimalNumber),
+    (0xc77, 0xc77, OtherPunctuation),
+    (0xc78, 0xc7e, OtherNumber),
+    (0xc7f, 0xc7f, OtherSymbol),
+    (0xc80, 0xc80, OtherLetter),
+    (0xc81, 0xc81, NonspacingMark),
+    (0xc82, 0xc83, SpacingMark),
+    (0xc84, 0xc84, OtherPunctuation),
+    (0xc85, 0xc8c, OtherLetter),
+    (0xc8e, 0xc90, OtherLetter),
+    (0xc92, 0xca8, OtherLetter),
+    (0xcaa, 0xcb3, OtherLetter),
+    (0xcb5, 0xcb9, OtherLetter),
+    (0xcbc, 0xcbc, NonspacingMark),
+    (0xcbd, 0xcbd, OtherLetter),
+    (0xcbe, 0xcbe, SpacingMark),
+    (0xcbf, 0xcbf, NonspacingMark),
+    (0xcc0, 0xcc4, SpacingMark),
+    (0xcc6, 0xcc6, NonspacingMark),
+    (0xcc7, 0xcc8, SpacingMark),
+    (0xcca, 0xccb, SpacingMark),
+    (0xccc, 0xccd, NonspacingMark),
+    (0xcd5, 0xcd6, SpacingMark),
+    (0xcdd, 0xcde, OtherLetter),
+    (0xce0, 0xce1, OtherLetter),
+    (0xce2, 0xce3, NonspacingMark),
+    (0xce6, 0xcef, DecimalNumber),
+    (0xcf1, 0xcf2, OtherLetter),
+    (0xd00, 0xd01, NonspacingMark),
+    (0xd02, 0xd03, SpacingMark),
+    (0xd04, 0xd0c, OtherLetter),
+    (0xd0e, 0xd10, OtherLetter),
+    (0xd12, 0xd3a, OtherLetter),
+    (0xd3b, 0xd3c, NonspacingMark),
+    (0xd3d, 0xd3d, OtherLetter),
+    (0xd3e, 0xd40, SpacingMark),
+    (0xd41, 0xd44, NonspacingMark),
+    (0xd46, 0xd48, SpacingMark),
+    (0xd4a, 0xd4c, SpacingMark),
+    (0xd4d, 0xd4d, NonspacingMark),
+    (0xd4e, 0xd4e, OtherLetter),
+    (0xd4f, 0xd4f, OtherSymbol),
+    (0xd54, 0xd56, OtherLetter),
+    (0xd57, 0xd57, SpacingMark),
+    (0xd58, 0xd5e, OtherNumber),
+    (0xd5f, 0xd61, OtherLetter),
+    (0xd62, 0xd63, NonspacingMark),
+    (0xd66, 0xd6f, DecimalNumber),
+    (0xd70, 0xd78, OtherNumber),
+    (0xd79, 0xd79, OtherSymbol),
+    (0xd7a, 0xd7f, OtherLetter),
+    (0xd81, 0xd81, NonspacingMark),
+    (0xd82, 0xd83, SpacingMark),
+    (0xd85, 0xd96, OtherLetter),
+    (0xd9a, 0xdb1, OtherLetter),
+    (0xdb3, 0xdbb, OtherLetter),
+    (0xdbd, 0xdbd, OtherLetter),
+    (0xdc0, 0xdc6, OtherLetter),
+    (0xdca, 0xdca, NonspacingMark),
+    (0xdcf, 0xdd1, SpacingMark),
+    (0xdd2, 0xdd4, NonspacingMark),
+    (0xdd6, 0xdd6, NonspacingMark),
+    (0xdd8, 0xddf, SpacingMark),
+    (0xde6, 0xdef, DecimalNumber),
+    (0xdf2, 0xdf3, SpacingMark),
+    (0xdf4, 0xdf4, OtherPunctuation),
+    (0xe01, 0xe30, OtherLetter),
+    (0xe31, 0xe31, NonspacingMark),
+    (0xe32, 0xe33, OtherLetter),
+    (0xe34, 0xe3a, NonspacingMark),
+    (0xe3f, 0xe3f, CurrencySymbol),
+    (0xe40, 0xe45, OtherLetter),
+    (0xe46, 0xe46, ModifierLetter),
+    (0xe47, 0xe4e, NonspacingMark),
+    (0xe4f, 0xe4f, OtherPunctuation),
+    (0xe50, 0xe59, DecimalNumber),
+    (0xe5a, 0xe5b, OtherPunctuation),
+    (0xe81, 0xe82, OtherLetter),
+    (0xe84, 0xe84, OtherLetter),
+    (0xe86, 0xe8a, OtherLetter),
+    (0xe8c, 0xea3, OtherLetter),
+    (0xea5, 0xea5, OtherLetter),
+    (0xea7, 0xeb0, OtherLetter),
+    (0xeb1, 0xeb1, NonspacingMark),
+    (0xeb2, 0xeb3, OtherLetter),
+    (0xeb4, 0xebc, NonspacingMark),
+    (0xebd, 0xebd, OtherLetter),
+    (0xec0, 0xec4, OtherLetter),
+    (0xec6, 0xec6, ModifierLetter),
+    (0xec8, 0xecd, NonspacingMark),
+    (0xed0, 0xed9, DecimalNumber),
+    (0xedc, 0xedf, OtherLetter),
+    (0xf00, 0xf00, OtherLetter),
+    (0xf01, 0xf03, OtherSymbol),
+    (0xf04, 0xf12, OtherPunctuation),
+    (0xf13, 0xf13, OtherSymbol),
+    (0xf14, 0xf14, OtherPunctuation),
+    (0xf15, 0xf17, OtherSymbol),
+    (0xf18, 0xf19, NonspacingMark),
+    (0xf1a, 0xf1f, OtherSymbol),
+    (0xf20, 0xf29, DecimalNumber),
+    (0xf2a, 0xf33, OtherNumber),
+    (0xf34, 0xf34, OtherSymbol),
+    (0xf35, 0xf35, NonspacingMark),
+    (0xf36, 0xf36, OtherSymbol),
+    (0xf37, 0xf37, NonspacingMark),
+    (0xf38, 0xf38, OtherSymbol),
+    (0xf39, 0xf39, NonspacingMark),
+    (0xf3a, 0xf3a, OpenPunctuation),
+    (0xf3b, 0xf3b, ClosePunctuation),
+    (0xf3c, 0xf3c, OpenPunctuation),
+    (0xf3d, 0xf3d, ClosePunctuation),
+    (0xf3e, 0xf3f, SpacingMark),
+    (0xf40, 0xf47, OtherLetter),
+    (0xf49, 0xf6c, OtherLetter),
+    (0xf71, 0xf7e, NonspacingMark),
+    (0xf7f, 0xf7f, SpacingMark),
+    (0xf80, 0xf84, NonspacingMark),
+    (0xf85, 0xf85, OtherPunctuation),
+    (0xf86, 0xf87, NonspacingMark),
+    (0xf88, 0xf8c, OtherLetter),
+    (0xf8d, 0xf97, NonspacingMark),
+    (0xf99, 0xfbc, NonspacingMark),
+    (0xfbe, 0xfc5, OtherSymbol),
+    (0xfc6, 0xfc6, NonspacingMark),
+    (0xfc7, 0xfcc, OtherSymbol),
+    (0xfce, 0xfcf, OtherSymbol),
+    (0xfd0, 0xfd4, OtherPunctuation),
+    (0xfd5, 0xfd8, OtherSymbol),
+    (0xfd9, 0xfda, OtherPunctuation),
+    (0x1000, 0x102a, OtherLetter),
+    (0x102b, 0x102c, SpacingMark),
+    (0x102d, 0x1030, NonspacingMark),
+    (0x1031, 0x1031, SpacingMark),
+    (0x1032, 0x1037, NonspacingMark),
+    (0x1038, 0x1038, SpacingMark),
+    (0x1039, 0x103a, NonspacingMark),
+    (0x103b, 0x103c, SpacingMark),
+    (0x103d, 0x103e, NonspacingMark),
+    (0x103f, 0x103f, OtherLetter),
+    (0x1040, 0x1049, DecimalNumber),
+    (0x104a, 0x104f, OtherPunctuation),
+    (0x1050, 0x1055, OtherLetter),
+    (0x1056, 0x1057, SpacingMark),
+    (0x1058, 0x1059, NonspacingMark),
+    (0x105a, 0x105d, OtherLetter),
+    (0x105e, 0x1060, NonspacingMark),
+    (0x1061, 0x1061, OtherLetter),
+    (0x1062, 0x1064, SpacingMark),
+    (0x1065, 0x1066, OtherLetter),
+    (0x1067, 0x106d, SpacingMark),
+    (0x106e, 0x1070, OtherLetter),
+    (0x1071, 0x1074, NonspacingMark),
+    (0x1075, 0x1081, OtherLetter),
+    (0x1082, 0x1082, NonspacingMark),
+    (0x1083, 0x1084, SpacingMark),
+    (0x1085, 0x1086, NonspacingMark),
+    (0x1087, 0x108c, SpacingMark),
+    (0x108d, 0x108d, NonspacingMark),
+    (0x108e, 0x108e, OtherLetter),
+    (0x108f, 0x108f, SpacingMark),
+    (0x1090, 0x1099, DecimalNumber),
+    (0x109a, 0x109c, SpacingMark),
+    (0x109d, 0x109d, NonspacingMark),
+    (0x109e, 0x109f, OtherSymbol),
+    (0x10a0, 0x10c5, UppercaseLetter),
+    (0x10c7, 0x10c7, UppercaseLetter),
+    (0x10cd, 0x10cd, UppercaseLetter),
+    (0x10d0, 0x10fa, LowercaseLetter),
+    (0x10fb, 0x10fb, OtherPunctuation),
+    (0x10fc, 0x10fc, ModifierLetter),
+    (0x10fd, 0x10ff, LowercaseLetter),
+    (0x1100, 0x1248, OtherLetter),
+    (0x124a, 0x124d, OtherLetter),
+    (0x1250, 0x1256, OtherLetter),
+    (0x1258, 0x1258, OtherLetter),
+    (0x125a, 0x125d, OtherLetter),
+    (0x1260, 0x1288, OtherLetter),
+    (0x128a, 0x128d, OtherLetter),
+    (0x1290, 0x12b0, OtherLetter),
+    (0x12b2, 0x12b5, OtherLetter),
+    (0x12b8, 0x12be, OtherLetter),
+    (0x12c0, 0x12c0, OtherLetter),
+    (0x12c2, 0x12c5, OtherLetter),
+    (0x12c8, 0x12d6, OtherLetter),
+    (0x12d8, 0x1310, OtherLetter),
+    (0x1312, 0x1315, OtherLetter),
+    (0x1318, 0x135a, OtherLetter),
+    (0x135d, 0x135f, NonspacingMark),
+    (0x1360, 0x1368, OtherPunctuation),
+    (0x1369, 0x137c, OtherNumber),
+    (0x1380, 0x138f, OtherLetter),
+    (0x1390, 0x1399, OtherSymbol),
+    (0x13a0, 0x13f5, UppercaseLetter),
+    (0x13f8, 0x13fd, LowercaseLetter),
+    (0x1400, 0x1400, DashPunctuation),
+    (0x1401, 0x166c, OtherLetter),
+    (0x166d, 0x166d, OtherSymbol),
+    (0x166e, 0x166e, OtherPunctuation),
+    (0x166f, 0x167f, OtherLetter),
+    (0x1680, 0x1680, SpaceSeparator),
+    (0x1681, 0x169a, OtherLetter),
+    (0x169b, 0x169b, OpenPunctuation),
+    (0x169c, 0x169c, ClosePunctuation),
+    (0x16a0, 0x16ea, OtherLetter),
+    (0x16eb, 0x16ed, OtherPunctuation),
+    (0x16ee, 0x16f0, LetterNumber),
+    (0x16f1, 0x16f8, OtherLetter),
+    (0x1700, 0x1711, OtherLetter),
+    (0x1712, 0x1714, NonspacingMark),
+    (0x1715, 0x1715, SpacingMark),
+    (0x171f, 0x1731, OtherLetter),
+    (0x1732, 0x1733, NonspacingMark),
+    (0x1734, 0x1734, SpacingMark),
+    (0x1735, 0x1736, OtherPunctuation),
+    (0x1740, 0x1751, OtherLetter),
+    (0x1752, 0x1753, NonspacingMark),
+    (0x1760, 0x176c, OtherLetter),
+    (0x176e, 0x1770, OtherLetter),
+    (0x1772, 0x1773, NonspacingMark),
+    (0x1780, 0x17b3, OtherLetter),
+    (0x17b4, 0x17b5, NonspacingMark),
+    (0x17b6, 0x17b6, SpacingMark),
+    (0x17b7, 0x17bd, NonspacingMark),
+    (0x17be, 0x17c5, SpacingMark),
+    (0x17c6, 0x17c6, NonspacingMark),
+    (0x17c7, 0x17c8, SpacingMark),
+    (0x17c9, 0x17d3, NonspacingMark),
+    (0x17d4, 0x17d6, OtherPunctuation),
+    (0x17d7, 0x17d7, ModifierLetter),
+    (0x17d8, 0x17da, OtherPunctuation),
+    (0x17db, 0x17db, CurrencySymbol),
+    (0x17dc, 0x17dc, OtherLetter),
+    (0x17dd, 0x17dd, NonspacingMark),
+    (0x17e0, 0x17e9, DecimalNumber),
+    (0x17f0, 0x17f9, OtherNumber),
+    (0x1800, 0x1805, OtherPunctuation),
+    (0x1806, 0x1806, DashPunctuation),
+    (0x1807, 0x180a, OtherPunctuation),
+    (0x180b, 0x180d, NonspacingMark),
+    (0x180e, 0x180e, Format),
+    (0x180f, 0x180f, NonspacingMark),
+    (0x1810, 0x1819, DecimalNumber),
+    (0x1820, 0x1842, OtherLetter),
+    (0x1843, 0x1843, ModifierLetter),
+    (0x1844, 0x1878, OtherLetter),
+    (0x1880, 0x1884, OtherLetter),
+    (0x1885, 0x1886, NonspacingMark),
+    (0x1887, 0x18a8, OtherLetter),
+    (0x18a9, 0x18a9, NonspacingMark),
+    (0x18aa, 0x18aa, OtherLetter),
+    (0x18b0, 0x18f5, OtherLetter),
+    (0x1900, 0x191e, OtherLetter),
+    (0x1920, 0x1922, NonspacingMark),
+    (0x1923, 0x1926, SpacingMark),
+    (0x1927, 0x1928, NonspacingMark),
+    (0x1929, 0x192b, SpacingMark),
+    (0x1930, 0x1931, SpacingMark),
+    (0x1932, 0x1932, NonspacingMark),
+    (0x1933, 0x1938, SpacingMark),
+    (0x1939, 0x193b, NonspacingMark),
+    (0x1940, 0x1940, OtherSymbol),
+    (0x1944, 0x1945, OtherPunctuation),
+    (0x1946, 0x194f, DecimalNumber),
+    (0x1950, 0x196d, OtherLetter),
+    (0x1970, 0x1974, OtherLetter),
+    (0x1980, 0x19ab, OtherLetter),
+    (0x19b0, 0x19c9, OtherLetter),
+    (0x19d0, 0x19d9, DecimalNumber),
+    (0x19da, 0x19da, OtherNumber),
+    (0x19de, 0x19ff, OtherSymbol),
+    (0x1a00, 0x1a16, OtherLetter),
+    (0x1a17, 0x1a18, NonspacingMark),
+    (0x1a19, 0x1a1a, SpacingMark),
+    (0x1a1b, 0x1a1b, NonspacingMark),
+    (0x1a1e, 0x1a1f, OtherPunctuation),
+    (0x1a20, 0x1a54, OtherLetter),
+    (0x1a55, 0x1a55, SpacingMark),
+    (0x1a56, 0x1a56, NonspacingMark),
+    (0x1a57, 0x1a57, SpacingMark),
+    (0x1a58, 0x1a5e, NonspacingMark),
+    (0x1a60, 0x1a60, NonspacingMark),
+    (0x1a61, 0x1a61, SpacingMark),
+    (0x1a62, 0x1a62, NonspacingMark),
+    (0x1a63, 0x1a64, SpacingMark),
+    (0x1a65, 0x1a6c, NonspacingMark),
+    (0x1a6d, 0x1a72, SpacingMark),
+    (0x1a73, 0x1a7c, NonspacingMark),
+    (0x1a7f, 0x1a7f, NonspacingMark),
+    (0x1a80, 0x1a89, DecimalNumber),
+    (0x1a90, 0x1a99, DecimalNumber),
+    (0x1aa0, 0x1aa6, OtherPunctuation),
+    (0x1aa7, 0x1aa7, ModifierLetter),
+    (0x1aa8, 0x1aad, OtherPunctuation),
+    (0x1ab0, 0x1abd, NonspacingMark),
+    (0x1abe, 0x1abe, EnclosingMark),
+    (0x1abf, 0x1ace, NonspacingMark),
+    (0x1b00, 0x1b03, NonspacingMark),
+    (0x1b04, 0x1b04, SpacingMark),
+    (0x1b05, 0x1b33, OtherLetter),
+    (0x1b34, 0x1b34, NonspacingMark),
+    (0x1b35, 0x1b35, SpacingMark),
+    (0x1b36, 0x1b3a, NonspacingMark),
+    (0x1b3b, 0x1b3b, SpacingMark),
+    (0x1b3c, 0x1b3c, NonspacingMark),
+    (0x1b3d, 0x1b41, SpacingMark),
+    (0x1b42, 0x1b42, NonspacingMark),
+    (0x1b43, 0x1b44, SpacingMark),
+    (0x1b45, 0x1b4c, OtherLetter),
+    (0x1b50, 0x1b59, DecimalNumber),
+    (0x1b5a, 0x1b60, OtherPunctuation),
+    (0x1b61, 0x1b6a, OtherSymbol),
+    (0x1b6b, 0x1b73, NonspacingMark),
+    (0x1b74, 0x1b7c, OtherSymbol),
+    (0x1b7d, 0x1b7e, OtherPunctuation),
+    (0x1b80, 0x1b81, NonspacingMark),
+    (0x1b82, 0x1b82, SpacingMark),
+    (0x1b83, 0x1ba0, OtherLetter),
+    (0x1ba1, 0x1ba1, SpacingMark),
+    (0x1ba2, 0x1ba5, NonspacingMark),
+    (0x1ba6, 0x1ba7, SpacingMark),
+    (0x1ba8, 0x1ba9, NonspacingMark),
+    (0x1baa, 0x1baa, SpacingMark),
+    (0x1bab, 0x1bad, NonspacingMark),
+    (0x1bae, 0x1baf, OtherLetter),
+    (0x1bb0, 0x1bb9, DecimalNumber),
+    (0x1bba, 0x1be5, OtherLetter),
+    (0x1be6, 0x1be6, NonspacingMark),
+    (0x1be7, 0x1be7, SpacingMark),
+    (0x1be8, 0x1be9, NonspacingMark),
+    (0x1bea, 0x1bec, SpacingMark),
+    (0x1bed, 0x1bed, NonspacingMark),
+    (0x1bee, 0x1bee, SpacingMark),
+    (0x1bef, 0x1bf1, NonspacingMark),
+    (0x1bf2, 0x1bf3, SpacingMark),
+    (0x1bfc, 0x1bff, OtherPunctuation),
+    (0x1c00, 0x1c23, OtherLetter),
+    (0x1c24, 0x1c2b, SpacingMark),
+    (0x1c2c, 0x1c33, NonspacingMark),
+    (0x1c34, 0x1c35, SpacingMark),
+    (0x1c36, 0x1c37, NonspacingMark),
+    (0x1c3b, 0x1c3f, OtherPunctuation),
+    (0x1c40, 0x1c49, DecimalNumber),
+    (0x1c4d, 0x1c4f, OtherLetter),
+    (0x1c50, 0x1c59, DecimalNumber),
+    (0x1c5a, 0x1c77, OtherLetter),
+    (0x1c78, 0x1c7d, ModifierLetter),
+    (0x1c7e, 0x1c7f, OtherPunctuation),
+    (0x1c80, 0x1c88, LowercaseLetter),
+    (0x1c90, 0x1cba, UppercaseLetter),
+    (0x1cbd, 0x1cbf, UppercaseLetter),
+    (0x1cc0, 0x1cc7, OtherPunctuation),
+    (0x1cd0, 0x1cd2, NonspacingMark),
+    (0x1cd3, 0x1cd3, OtherPunctuation),
+    (0x1cd4, 0x1ce0, NonspacingMark),
+    (0x1ce1, 0x1ce1, SpacingMark),
+    (0x1ce2, 0x1ce8, NonspacingMark),
+    (0x1ce9, 0x1cec, OtherLetter),
+    (0x1ced, 0x1ced, NonspacingMark),
+    (0x1cee, 0x1cf3, OtherLetter),
+    (0x1cf4, 0x1cf4, NonspacingMark),
+    (0x1cf5, 0x1cf6, OtherLetter),
+    (0x1cf7, 0x1cf7, SpacingMark),
+    (0x1cf8, 0x1cf9, NonspacingMark),
+    (0x1cfa, 0x1cfa, OtherLetter),
+    (0x1d00, 0x1d2b, LowercaseLetter),
+    (0x1d2c, 0x1d6a, ModifierLetter),
+    (0x1d6b, 0x1d77, LowercaseLetter),
+    (0x1d78, 0x1d78, ModifierLetter),
+    (0x1d79, 0x1d9a, LowercaseLetter),
+    (0x1d9b, 0x1dbf, ModifierLetter),
+    (0x1dc0, 0x1dff, NonspacingMark),
+    (0x1e00, 0x1e00, UppercaseLetter),
+    (0x1e01, 0x1e01, LowercaseLetter),
+    (0x1e02, 0x1e02, UppercaseLetter),
+    (0x1e03, 0x1e03, LowercaseLetter),
+    (0x1e04, 0x1e04, UppercaseLetter),
+    (0x1e05, 0x1e05, LowercaseLetter),
+    (0x1e06, 0x1e06, UppercaseLetter),
+    (0x1e07, 0x1e07, LowercaseLetter),
+    (0x1e08, 0x1e08, UppercaseLetter),
+    (0x1e09, 0x1e09, LowercaseLetter),
+    (0x1e0a, 0x1e0a, UppercaseLetter),
+    (0x1e0b, 0x1e0b, LowercaseLetter),
+    (0x1e0c, 0x1e0c, UppercaseLetter),
+    (0x1e0d, 0x1e0d, LowercaseLetter),
+    (0x1e0e, 0x1e0e, UppercaseLetter),
+    (0x1e0f, 0x1e0f, LowercaseLetter),
+    (0x1e10, 0x1e10, UppercaseLetter),
+    (0x1e11, 0x1e11, LowercaseLetter),
+    (0x1e12, 0x1e12, UppercaseLetter),
+    (0x1e13, 0x1e13, LowercaseLetter),
+    (0x1e14, 0x1e14, UppercaseLetter),
+    (0x1e15, 0x1e15, LowercaseLetter),
+    (0x1e16, 0x1e16, UppercaseLetter),
+    (0x1e17, 0x1e17, LowercaseLetter),
+    (0x1e18, 0x1e18, UppercaseLetter),
+    (0x1e19, 0x1e19, LowercaseLetter),
+    (0x1e1a, 0x1e1a, UppercaseLetter),
+    (0x1e1b, 0x1e1b, LowercaseLetter),
+    (0x1e1c, 0x1e1c, UppercaseLetter),
+    (0x1e1d, 0x1e1d, LowercaseLetter),
+    (0x1e1e, 0x1e1e, UppercaseLetter),
+    (0x1e1f, 0x1e1f, LowercaseLetter),
+    (0x1e20, 0x1e20, UppercaseLetter),
+    (0x1e21, 0x1e21, LowercaseLetter),
+    (0x1e22, 0x1e22, UppercaseLetter),
+    (0x1e23, 0x1e23, LowercaseLetter),
+    (0x1e24, 0x1e24, UppercaseLetter),
+    (0x1e25, 0x1e25, LowercaseLetter),
+    (0x1e26, 0x1e26, UppercaseLetter),
+    (0x1e27, 0x1e27, LowercaseLetter),
+    (0x1e28, 0x1e28, UppercaseLetter),
+    (0x1e29, 0x1e29, LowercaseLetter),
+    (0x1e2a, 0x1e2a, UppercaseLetter),
+    (0x1e2b, 0x1e2b, LowercaseLetter),
+    (0x1e2c, 0x1e2c, UppercaseLetter),
+    (0x1e2d, 0x1e2d, LowercaseLetter),
+    (0x1e2e, 0x1e2e, UppercaseLetter),
+    (0x1e2f, 0x1e2f, LowercaseLetter),
+    (0x1e30, 0x1e30, UppercaseLetter),
+    (0x1e31, 0x1e31, LowercaseLetter),
+    (0x1e32, 0x1e32, UppercaseLetter),
+    (0x1e33, 0x1e33, LowercaseLetter),
+    (0x1e34, 0x1e34, UppercaseLetter),
+    (0x1e35, 0x1e35, LowercaseLetter),
+    (0x1e36, 0x1e36, UppercaseLetter),
+    (0x1e37, 0x1e37, LowercaseLetter),
+    (0x1e38, 0x1e38, UppercaseLetter),
+    (0x1e39, 0x1e39, LowercaseLetter),
+    (0x1e3a, 0x1e3a, UppercaseLetter),
+    (0x1e3b, 0x1e3b, LowercaseLetter),
+    (0x1e3c, 0x1e3c, UppercaseLetter),
+    (0x1e3d, 0x1e3d, LowercaseLetter),
+    (0x1e3e, 0x1e3e, UppercaseLetter),
+    (0x1e3f, 0x1e3f, LowercaseLetter),
+    (0x1e40, 0x1e40, UppercaseLetter),
+    (0x1e41, 0x1e41, LowercaseLetter),
+    (0x1e42, 0x1e42, UppercaseLetter),
+    (0x1e43, 0x1e43, LowercaseLetter),
+    (0x1e44, 0x1e44, UppercaseLetter),
+    (0x1e45, 0x1e45, LowercaseLetter),
+    (0x1e46, 0x1e46, UppercaseLetter),
+    (0x1e47, 0x1e47, LowercaseLetter),
+    (0x1e48, 0x1e48, UppercaseLetter),
+    (0x1e49, 0x1e49, LowercaseLetter),
+    (0x1e4a, 0x1e4a, UppercaseLetter),
+    (0x1e4b, 0x1e4b, LowercaseLetter),
+    (0x1e4c, 0x1e4c, UppercaseLetter),
+    (0x1e4d, 0x1e4d, LowercaseLetter),
+    (0x1e4e, 0x1e4e, UppercaseLetter),
+    (0x1e4f, 0x1e4f, LowercaseLetter),
+    (0x1e50, 0x1e50, UppercaseLetter),
+    (0x1e51, 0x1e51, LowercaseLetter),
+    (0x1e52, 0x1e52, UppercaseLetter),
+    (0x1e53, 0x1e53, LowercaseLetter),
+    (0x1e54, 0x1e54, UppercaseLetter),
+    (0x1e55, 0x1e55, LowercaseLetter),
+    (0x1e56, 0x1e56, UppercaseLetter),
+    (0x1e57, 0x1e57, LowercaseLetter),
+    (0x1e58, 0x1e58, UppercaseLetter),
+    (0x1e59, 0x1e59, LowercaseLetter),
+    (0x1e5a, 0x1e5a, UppercaseLetter),
+    (0x1e5b, 0x1e5b, LowercaseLetter),
+    (0x1e5c, 0x1e5c, UppercaseLetter),
+    (0x1e5d, 0x1e5d, LowercaseLetter),
+    (0x1e5e, 0x1e5e, UppercaseLetter),
+    (0x1e5f, 0x1e5f, LowercaseLetter),
+    (0x1e60, 0x1e60, UppercaseLetter),
+    (0x1e61, 0x1e61, LowercaseLetter),
+    (0x1e62, 0x1e62, UppercaseLetter),
+    (0x1e63, 0x1e63, LowercaseLetter),
+    (0x1e64, 0x1e64, UppercaseLetter),
+    (0x1e65, 0x1e65, LowercaseLetter),
+    (0x1e66, 0x1e66, UppercaseLetter),
+    (0x1e67, 0x1e67, LowercaseLetter),
+    (0x1e68, 0x1e68, UppercaseLetter),
+    (0x1e69, 0x1e69, LowercaseLetter),
+    (0x1e6a, 0x1e6a, UppercaseLetter),
+    (0x1e6b, 0x1e6b, LowercaseLetter),
+    (0x1e6c, 0x1e6c, UppercaseLetter),
+    (0x1e6d, 0x1e6d, LowercaseLetter),
+    (0x1e6e, 0x1e6e, UppercaseLetter),
+    (0x1e6f, 0x1e6f, LowercaseLetter),
+    (0x1e70, 0x1e70, UppercaseLetter),
+    (0x1e71, 0x1e71, LowercaseLetter),
+    (0x1e72, 0x1e72, UppercaseLetter),
+    (0x1e73, 0x1e73, LowercaseLetter),
+    (0x1e74, 0x1e74, UppercaseLetter),
+    (0x1e75, 0x1e75, LowercaseLetter),
+    (0x1e76, 0x1e76, UppercaseLetter),
+    (0x1e77, 0x1e77, LowercaseLetter),
+    (0x1e78, 0x1e78, UppercaseLetter),
+    (0x1e79, 0x1e79, LowercaseLetter),
+    (0x1e7a, 0x1e7a, UppercaseLetter),
+    (0x1e7b, 0x1e7b, LowercaseLetter),
+    (0x1e7c, 0x1e7c, UppercaseLetter),
+    (0x1e7d, 0x1e7d, LowercaseLetter),
+    (0x1e7e, 0x1e7e, UppercaseLetter),
+    (0x1e7f, 0x1e7f, LowercaseLetter),
+    (0x1e80, 0x1e80, UppercaseLetter),
+    (0x1e81, 0x1e81, LowercaseLetter),
+    (0x1e82, 0x1e82, UppercaseLetter),
+    (0x1e83, 0x1e83, LowercaseLetter),
+    (0x1e84, 0x1e84, UppercaseLetter),
+    (0x1e85, 0x1e85, LowercaseLetter),
+    (0x1e86, 0x1e86, UppercaseLetter),
+    (0x1e87, 0x1e87, LowercaseLetter),
+    (0x1e88, 0x1e88, UppercaseLetter),
+    (0x1e89, 0x1e89, LowercaseLetter),
+    (0x1e8a, 0x1e8a, UppercaseLetter),
+    (0x1e8b, 0x1e8b, LowercaseLetter),
+    (0x1e8c, 0x1e8c, UppercaseLetter),
+    (0x1e8d, 0x1e8d, LowercaseLetter),
+    (0x1e8e, 0x1e8e, UppercaseLetter),
+    (0x1e8f, 0x1e8f, LowercaseLetter),
+    (0x1e90, 0x1e90, UppercaseLetter),
+    (0x1e91, 0x1e91, LowercaseLetter),
+    (0x1e92, 0x1e92, UppercaseLetter),
+    (0x1e93, 0x1e93, LowercaseLetter),
+    (0x1e94, 0x1e94, UppercaseLetter),
+    (0x1e95, 0x1e9d, LowercaseLetter),
+    (0x1e9e, 0x1e9e, UppercaseLetter),
+    (0x1e9f, 0x1e9f, LowercaseLetter),
+    (0x1ea0, 0x1ea0, UppercaseLetter),
+    (0x1ea1, 0x1ea1, LowercaseLetter),
+    (0x1ea2, 0x1ea2, UppercaseLetter),
+    (0x1ea3, 0x1ea3, LowercaseLetter),
+    (0x1ea4, 0x1ea4, UppercaseLetter),
+    (0x1ea5, 0x1ea5, LowercaseLetter),
+    (0x1ea6, 0x1ea6, UppercaseLetter),
+    (0x1ea7, 0x1ea7, LowercaseLetter),
+    (0x1ea8, 0x1ea8, UppercaseLetter),
+    (0x1ea9, 0x1ea9, LowercaseLetter),
+    (0x1eaa, 0x1eaa, UppercaseLetter),
+    (0x1eab, 0x1eab, LowercaseLetter),
+    (0x1eac, 0x1eac, UppercaseLetter),
+    (0x1ead, 0x1ead, LowercaseLetter),
+    (0x1eae, 0x1eae, UppercaseLetter),
+    (0x1eaf, 0x1eaf, LowercaseLetter),
+    (0x1eb0, 0x1eb0, UppercaseLetter),
+    (0x1eb1, 0x1eb1, LowercaseLetter),
+    (0x1eb2, 0x1eb2, UppercaseLetter),
+    (0x1eb3, 0x1eb3, LowercaseLetter),
+    (0x1eb4, 0x1eb4, UppercaseLetter),
+    (0x1eb5, 0x1eb5, LowercaseLetter),
+    (0x1eb6, 0x1eb6, UppercaseLetter),
+    (0x1eb7, 0x1eb7, LowercaseLetter),
+    (0x1eb8, 0x1eb8, UppercaseLetter),
+    (0x1eb9, 0x1eb9, LowercaseLetter),
+    (0x1eba, 0x1eba, UppercaseLetter),
+    (0x1ebb, 0x1ebb, LowercaseLetter),
+    (0x1ebc, 0x1ebc, UppercaseLetter),
+    (0x1ebd, 0x1ebd, LowercaseLetter),
+    (0x1ebe, 0x1ebe, UppercaseLetter),
+    (0x1ebf, 0x1ebf, LowercaseLetter),
+    (0x1ec0, 0x1ec0, UppercaseLetter),
+    (0x1ec1, 0x1ec1, LowercaseLetter),
+    (0x1ec2, 0x1ec2, UppercaseLetter),
+    (0x1ec3, 0x1ec3, LowercaseLetter),
+    (0x1ec4, 0x1ec4, UppercaseLetter),
+    (0x1ec5, 0x1ec5, LowercaseLetter),
+    (0x1ec6, 0x1ec6, UppercaseLetter),
+    (0x1ec7, 0x1ec7, LowercaseLetter),
+    (0x1ec8, 0x1ec8, UppercaseLetter),
+    (0x1ec9, 0x1ec9, LowercaseLetter),
+    (0x1eca, 0x1eca, UppercaseLetter),
+    (0x1ecb, 0x1ecb, LowercaseLetter),
+    (0x1ecc, 0x1ecc, UppercaseLetter),
+    (0x1ecd, 0x1ecd, LowercaseLetter),
+    (0x1ece, 0x1ece, UppercaseLetter),
+    (0x1ecf, 0x1ecf, LowercaseLetter),
+    (0x1ed0, 0x1ed0, UppercaseLetter),
+    (0x1ed1, 0x1ed1, LowercaseLetter),
+    (0x1ed2, 0x1ed2, UppercaseLetter),
+    (0x1ed3, 0x1ed3, LowercaseLetter),
+    (0x1ed4, 0x1ed4, UppercaseLetter),
+    (0x1ed5, 0x1ed5, LowercaseLetter),
+    (0x1ed6, 0x1ed6, UppercaseLetter),
+    (0x1ed7, 0x1ed7, LowercaseLetter),
+    (0x1ed8, 0x1ed8, UppercaseLetter),
+    (0x1ed9, 0x1ed9, LowercaseLetter),
+    (0x1eda, 0x1eda, UppercaseLetter),
+    (0x1edb, 0x1edb, LowercaseLetter),
+    (0x1edc, 0x1edc, UppercaseLetter),
+    (0x1edd, 0x1edd, LowercaseLetter),
+    (0x1ede, 0x1ede, UppercaseLetter),
+    (0x1edf, 0x1edf, LowercaseLetter),
+    (0x1ee0, 0x1ee0, UppercaseLetter),
+    (0x1ee1, 0x1ee1, LowercaseLetter),
+    (0x1ee2, 0x1ee2, UppercaseLetter),
+    (0x1ee3, 0x1ee3, LowercaseLetter),
+    (0x1ee4, 0x1ee4, UppercaseLetter),
+    (0x1ee5, 0x1ee5, LowercaseLetter),
+    (0x1ee6, 0x1ee6, UppercaseLetter),
+    (0x1ee7, 0x1ee7, LowercaseLetter),
+    (0x1ee8, 0x1ee8, UppercaseLetter),
+    (0x1ee9, 0x1ee9, LowercaseLetter),
+    (0x1eea, 0x1eea, UppercaseLetter),
+    (0x1eeb, 0x1eeb, LowercaseLetter),
+    (0x1eec, 0x1eec, UppercaseLetter),
+    (0x1eed, 0x1eed, LowercaseLetter),
+    (0x1eee, 0x1eee, UppercaseLetter),
+    (0x1eef, 0x1eef, LowercaseLetter),
+    (0x1ef0, 0x1ef0, UppercaseLetter),
+    (0x1ef1, 0x1ef1, LowercaseLetter),
+    (0x1ef2, 0x1ef2, UppercaseLetter),
+    (0x1ef3, 0x1ef3, LowercaseLetter),
+    (0x1ef4, 0x1ef4, UppercaseLetter),
+    (0x1ef5, 0x1ef5, LowercaseLetter),
+    (0x1ef6, 0x1ef6, UppercaseLetter),
+    (0x1ef7, 0x1ef7, LowercaseLetter),
+    (0x1ef8, 0x1ef8, UppercaseLetter),
+    (0x1ef9, 0x1ef9, LowercaseLetter),
+    (0x1efa, 0x1efa, UppercaseLetter),
+    (0x1efb, 0x1efb, LowercaseLetter),
+    (0x1efc, 0x1efc, UppercaseLetter),
+    (0x1efd, 0x1efd, LowercaseLetter),
+    (0x1efe, 0x1efe, UppercaseLetter),
+    (0x1eff, 0x1f07, LowercaseLetter),
+    (0x1f08, 0x1f0f, UppercaseLetter),
+    (0x1f10, 0x1f15, LowercaseLetter),
+    (0x1f18, 0x1f1d, UppercaseLetter),
+    (0x1f20, 0x1f27, LowercaseLetter),
+    (0x1f28, 0x1f2f, UppercaseLetter),
+    (0x1f30, 0x1f37, LowercaseLetter),
+    (0x1f38, 0x1f3f, UppercaseLetter),
+    (0x1f40, 0x1f45, LowercaseLetter),
+    (0x1f48, 0x1f4d, UppercaseLetter),
+    (0x1f50, 0x1f57, LowercaseLetter),
+    (0x1f59, 0x1f59, UppercaseLetter),
+    (0x1f5b, 0x1f5b, UppercaseLetter),
+    (0x1f5d, 0x1f5d, UppercaseLetter),
+    (0x1f5f, 0x1f5f, UppercaseLetter),
+    (0x1f60, 0x1f67, LowercaseLetter),
+    (0x1f68, 0x1f6f, UppercaseLetter),
+    (0x1f70, 0x1f7d, LowercaseLetter),
+    (0x1f80, 0x1f87, LowercaseLetter),
+    (0x1f88, 0x1f8f, TitlecaseLetter),
+    (0x1f90, 0x1f97, LowercaseLetter),
+    (0x1f98, 0x1f9f, TitlecaseLetter),
+    (0x1fa0, 0x1fa7, LowercaseLetter),
+    (0x1fa8, 0x1faf, TitlecaseLetter),
+    (0x1fb0, 0x1fb4, LowercaseLetter),
+    (0x1fb6, 0x1fb7, LowercaseLetter),
+    (0x1fb8, 0x1fbb, UppercaseLetter),
+    (0x1fbc, 0x1fbc, TitlecaseLetter),
+    (0x1fbd, 0x1fbd, ModifierSymbol),
+    (0x1fbe, 0x1fbe, LowercaseLetter),
+    (0x1fbf, 0x1fc1, ModifierSymbol),
+    (0x1fc2, 0x1fc4, LowercaseLetter),
+    (0x1fc6, 0x1fc7, LowercaseLetter),
+    (0x1fc8, 0x1fcb, UppercaseLetter),
+    (0x1fcc, 0x1fcc, TitlecaseLetter),
+    (0x1fcd, 0x1fcf, ModifierSymbol),
+    (0x1fd0, 0x1fd3, LowercaseLetter),
+    (0x1fd6, 0x1fd7, LowercaseLetter),
+    (0x1fd8, 0x1fdb, UppercaseLetter),
+    (0x1fdd, 0x1fdf, ModifierSymbol),
+    (0x1fe0, 0x1fe7, LowercaseLetter),
+    (0x1fe8, 0x1fec, UppercaseLetter),
+    (0x1fed, 0x1fef, ModifierSymbol),
+    (0x1ff2, 0x1ff4, LowercaseLetter),
+    (0x1ff6, 0x1ff7, LowercaseLetter),
+    (0x1ff8, 0x1ffb, UppercaseLetter),
+    (0x1ffc, 0x1ffc, TitlecaseLetter),
+    (0x1ffd, 0x1ffe, ModifierSymbol),
+    (0x2000, 0x200a, SpaceSeparator),
+    (0x200b, 0x200f, Format),
+    (0x2010, 0x2015, DashPunctuation),
+    (0x2016, 0x2017, OtherPunctuation),
+    (0x2018, 0x2018, InitialPunctuation),
+    (0x2019, 0x2019, FinalPunctuation),
+    (0x201a, 0x201a, OpenPunctuation),
+    (0x201b, 0x201c, InitialPunctuation),
+    (0x201d, 0x201d, FinalPunctuation),
+    (0x201e, 0x201e, OpenPunctuation),
+    (0x201f, 0x201f, InitialPunctuation),
+    (0x2020, 0x2027, OtherPunctuation),
+    (0x2028, 0x2028, LineSeparator),
+    (0x2029, 0x2029, ParagraphSeparator),
+    (0x202a, 0x202e, Format),
+    (0x202f, 0x202f, SpaceSeparator),
+    (0x2030, 0x2038, OtherPunctuation),
+    (0x2039, 0x2039, InitialPunctuation),
+    (0x203a, 0x203a, FinalPunctuation),
+    (0x203b, 0x203e, OtherPunctuation),
+    (0x203f, 0x2040, ConnectorPunctuation),
+    (0x2041, 0x2043, OtherPunctuation),
+    (0x2044, 0x2044, MathSymbol),
+    (0x2045, 0x2045, OpenPunctuation),
+    (0x2046, 0x2046, ClosePunctuation),
+    (0x2047, 0x2051, OtherPunctuation),
+    (0x2052, 0x2052, MathSymbol),
+    (0x2053, 0x2053, OtherPunctuation),
+    (0x2054, 0x2054, ConnectorPunctuation),
+    (0x2055, 0x205e, OtherPunctuation),
+    (0x205f, 0x205f, SpaceSeparator),
+    (0x2060, 0x2064, Format),
+    (0x2066, 0x206f, Format),
+    (0x2070, 0x2070, OtherNumber),
+    (0x2071, 0x2071, ModifierLetter),
+    (0x2074, 0x2079, OtherNumber),
+    (0x207a, 0x207c, MathSymbol),
+    (0x207d, 0x207d, OpenPunctuation),
+    (0x207e, 0x207e, ClosePunctuation),
+    (0x207f, 0x207f, ModifierLetter),
+    (0x2080, 0x2089, OtherNumber),
+    (0x208a, 0x208c, MathSymbol),
+    (0x208d, 0x208d, OpenPunctuation),
+    (0x208e, 0x208e, ClosePunctuation),
+    (0x2090, 0x209c, ModifierLetter),
+    (0x20a0, 0x20c0, CurrencySymbol),
+    (0x20d0, 0x20dc, NonspacingMark),
+    (0x20dd, 0x20e0, EnclosingMark),
+    (0x20e1, 0x20e1, NonspacingMark),
+    (0x20e2, 0x20e4, EnclosingMark),
+    (0x20e5, 0x20f0, NonspacingMark),
+    (0x2100, 0x2101, OtherSymbol),
+    (0x2102, 0x2102, UppercaseLetter),
+    (0x2103, 0x2106, OtherSymbol),
+    (0x2107, 0x2107, UppercaseLetter),
+    (0x2108, 0x2109, OtherSymbol),
+    (0x210a, 0x210a, LowercaseLetter),
+    (0x210b, 0x210d, UppercaseLetter),
+    (0x210e, 0x210f, LowercaseLetter),
+    (0x2110, 0x2112, UppercaseLetter),
+    (0x2113, 0x2113, LowercaseLetter),
+    (0x2114, 0x2114, OtherSymbol),
+    (0x2115, 0x2115, UppercaseLetter),
+    (0x2116, 0x2117, OtherSymbol),
+    (0x2118, 0x2118, MathSymbol),
+    (0x2119, 0x211d, UppercaseLetter),
+    (0x211e, 0x2123, OtherSymbol),
+    (0x2124, 0x2124, UppercaseLetter),
+    (0x2125, 0x2125, OtherSymbol),
+    (0x2126, 0x2126, UppercaseLetter),
+    (0x2127, 0x2127, OtherSymbol),
+    (0x2128, 0x2128, UppercaseLetter),
+    (0x2129, 0x2129, OtherSymbol),
+    (0x212a, 0x212d, UppercaseLetter),
+    (0x212e, 0x212e, OtherSymbol),
+    (0x212f, 0x212f, LowercaseLetter),
+    (0x2130, 0x2133, UppercaseLetter),
+    (0x2134, 0x2134, LowercaseLetter),
+    (0x2135, 0x2138, OtherLetter),
+    (0x2139, 0x2139, LowercaseLetter),
+    (0x213a, 0x213b, OtherSymbol),
+    (0x213c, 0x213d, LowercaseLetter),
+    (0x213e, 0x213f, UppercaseLetter),
+    (0x2140, 0x2144, MathSymbol),
+    (0x2145, 0x2145, UppercaseLetter),
+    (0x2146, 0x2149, LowercaseLetter),
+    (0x214a, 0x214a, OtherSymbol),
+    (0x214b, 0x214b, MathSymbol),
+    (0x214c, 0x214d, OtherSymbol),
+    (0x214e, 0x214e, LowercaseLetter),
+    (0x214f, 0x214f, OtherSymbol),
+    (0x2150, 0x215f, OtherNumber),
+    (0x2160, 0x2182, LetterNumber),
+    (0x2183, 0x2183, UppercaseLetter),
+    (0x2184, 0x2184, LowercaseLetter),
+    (0x2185, 0x2188, LetterNumber),
+    (0x2189, 0x2189, OtherNumber),
+    (0x218a, 0x218b, OtherSymbol),
+    (0x2190, 0x2194, MathSymbol),
+    (0x2195, 0x2199, OtherSymbol),
+    (0x219a, 0x219b, MathSymbol),
+    (0x219c, 0x219f, OtherSymbol),
+    (0x21a0, 0x21a0, MathSymbol),
+    (0x21a1, 0x21a2, OtherSymbol),
+    (0x21a3, 0x21a3, MathSymbol),
+    (0x21a4, 0x21a5, OtherSymbol),
+    (0x21a6, 0x21a6, MathSymbol),
+    (0x21a7, 0x21ad, OtherSymbol),
+    (0x21ae, 0x21ae, MathSymbol),
+    (0x21af, 0x21cd, OtherSymbol),
+    (0x21ce, 0x21cf, MathSymbol),
+    (0x21d0, 0x21d1, OtherSymbol),
+    (0x21d2, 0x21d2, MathSymbol),
+    (0x21d3, 0x21d3, OtherSymbol),
+    (0x21d4, 0x21d4, MathSymbol),
+    (0x21d5, 0x21f3, OtherSymbol),
+    (0x21f4, 0x22ff, MathSymbol),
+    (0x2300, 0x2307, OtherSymbol),
+    (0x2308, 0x2308, OpenPunctuation),
+    (0x2309, 0x2309, ClosePunctuation),
+    (0x230a, 0x230a, OpenPunctuation),
+    (0x230b, 0x230b, ClosePunctuation),
+    (0x230c, 0x231f, OtherSymbol),
+    (0x2320, 0x2321, MathSymbol),
+    (0x2322, 0x2328, OtherSymbol),
+    (0x2329, 0x2329, OpenPunctuation),
+    (0x232a, 0x232a, ClosePunctuation),
+    (0x232b, 0x237b, OtherSymbol),
+    (0x237c, 0x237c, MathSymbol),
+    (0x237d, 0x239a, OtherSymbol),
+    (0x239b, 0x23b3, MathSymbol),
+    (0x23b4, 0x23db, OtherSymbol),
+    (0x23dc, 0x23e1, MathSymbol),
+    (0x23e2, 0x2426, OtherSymbol),
+    (0x2440, 0x244a, OtherSymbol),
+    (0x2460, 0x249b, OtherNumber),
+    (0x249c, 0x24e9, OtherSymbol),
+    (0x24ea, 0x24ff, OtherNumber),
+    (0x2500, 0x25b6, OtherSymbol),
+    (0x25b7, 0x25b7, MathSymbol),
+    (0x25b8, 0x25c0, OtherSymbol),
+    (0x25c1, 0x25c1, MathSymbol),
+    (0x25c2, 0x25f7, OtherSymbol),
+    (0x25f8, 0x25ff, MathSymbol),
+    (0x2600, 0x266e, OtherSymbol),
+    (0x266f, 0x266f, MathSymbol),
+    (0x2670, 0x2767, OtherSymbol),
+    (0x2768, 0x2768, OpenPunctuation),
+    (0x2769, 0x2769, ClosePunctuation),
+    (0x276a, 0x276a, OpenPunctuation),
+    (0x276b, 0x276b, ClosePunctuation),
+    (0x276c, 0x276c, OpenPunctuation),
+    (0x276d, 0x276d, ClosePunctuation),
+    (0x276e, 0x276e, OpenPunctuation),
+    (0x276f, 0x276f, ClosePunctuation),
+    (0x2770, 0x2770, OpenPunctuation),
+    (0x2771, 0x2771, ClosePunctuation),
+    (0x2772, 0x2772, OpenPunctuation),
+    (0x2773, 0x2773, ClosePunctuation),
+    (0x2774, 0x2774, OpenPunctuation),
+    (0x2775, 0x2775, ClosePunctuation),
+    (0x2776, 0x2793, OtherNumber),
+    (0x2794, 0x27bf, OtherSymbol),
+    (0x27c0, 0x27c4, MathSymbol),
+    (0x27c5, 0x27c5, OpenPunctuation),
+    (0x27c6, 0x27c6, ClosePunctuation),
+    (0x27c7, 0x27e5, MathSymbol),
+    (0x27e6, 0x27e6, OpenPunctuation),
+    (0x27e7, 0x27e7, ClosePunctuation),
+    (0x27e8, 0x27e8, OpenPunctuation),
+    (0x27e9, 0x27e9, ClosePunctuation),
+    (0x27ea, 0x27ea, OpenPunctuation),
+    (0x27eb, 0x27eb, ClosePunctuation),
+    (0x27ec, 0x27ec, OpenPunctuation),
+    (0x27ed, 0x27ed, ClosePunctuation),
+    (0x27ee, 0x27ee, OpenPunctuation),
+    (0x27ef, 0x27ef, ClosePunctuation),
+    (0x27f0, 0x27ff, MathSymbol),
+    (0x2800, 0x28ff, OtherSymbol),
+    (0x2900, 0x2982, MathSymbol),
+    (0x2983, 0x2983, OpenPunctuation),
+    (0x2984, 0x2984, ClosePunctuation),
+    (0x2985, 0x2985, OpenPunctuation),
+    (0x2986, 0x2986, ClosePunctuation),
+    (0x2987, 0x2987, OpenPunctuation),
+    (0x2988, 0x2988, ClosePunctuation),
+    (0x2989, 0x2989, OpenPunctuation),
+    (0x298a, 0x298a, ClosePunctuation),
+    (0x298b, 0x298b, OpenPunctuation),
+    (0x298c, 0x298c, ClosePunctuation),
+    (0x298d, 0x298d, OpenPunctuation),
+    (0x298e, 0x298e, ClosePunctuation),
+    (0x298f, 0x298f, OpenPunctuation),
+    (0x2990, 0x2990, ClosePunctuation),
+    (0x2991, 0x2991, OpenPunctuation),
+    (0x2992, 0x2992, ClosePunctuation),
+    (0x2993, 0x2993, OpenPunctuation),
+    (0x2994, 0x2994, ClosePunctuation),
+    (0x2995, 0x2995, OpenPunctuation),
+    (0x2996, 0x2996, ClosePunctuation),
+    (0x2997, 0x2997, OpenPunctuation),
+    (0x2998, 0x2998, ClosePunctuation),
+    (0x2999, 0x29d7, MathSymbol),
+    (0x29d8, 0x29d8, OpenPunctuation),
+    (0x29d9, 0x29d9, ClosePunctuation),
+    (0x29da, 0x29da, OpenPunctuation),
+    (0x29db, 0x29db, ClosePunctuation),
+    (0x29dc, 0x29fb, MathSymbol),
+    (0x29fc, 0x29fc, OpenPunctuation),
+    (0x29fd, 0x29fd, ClosePunctuation),
+    (0x29fe, 0x2aff, MathSymbol),
+    (0x2b00, 0x2b2f, OtherSymbol),
+    (0x2b30, 0x2b44, MathSymbol),
+    (0x2b45, 0x2b46, OtherSymbol),
+    (0x2b47, 0x2b4c, MathSymbol),
+    (0x2b4d, 0x2b73, OtherSymbol),
+    (0x2b76, 0x2b95, OtherSymbol),
+    (0x2b97, 0x2bff, OtherSymbol),
+    (0x2c00, 0x2c2f, UppercaseLetter),
+    (0x2c30, 0x2c5f, LowercaseLetter),
+    (0x2c60, 0x2c60, UppercaseLetter),
+    (0x2c61, 0x2c61, LowercaseLetter),
+    (0x2c62, 0x2c64, UppercaseLetter),
+    (0x2c65, 0x2c66, LowercaseLetter),
+    (0x2c67, 0x2c67, UppercaseLetter),
+    (0x2c68, 0x2c68, LowercaseLetter),
+    (0x2c69, 0x2c69, UppercaseLetter),
+    (0x2c6a, 0x2c6a, LowercaseLetter),
+    (0x2c6b, 0x2c6b, UppercaseLetter),
+    (0x2c6c, 0x2c6c, LowercaseLetter),
+    (0x2c6d, 0x2c70, UppercaseLetter),
+    (0x2c71, 0x2c71, LowercaseLetter),
+    (0x2c72, 0x2c72, UppercaseLetter),
+    (0x2c73, 0x2c74, LowercaseLetter),
+    (0x2c75, 0x2c75, UppercaseLetter),
+    (0x2c76, 0x2c7b, LowercaseLetter),
+    (0x2c7c, 0x2c7d, ModifierLetter),
+    (0x2c7e, 0x2c80, UppercaseLetter),
+    (0x2c81, 0x2c81, LowercaseLetter),
+    (0x2c82, 0x2c82, UppercaseLetter),
+    (0x2c83, 0x2c83, LowercaseLetter),
+    (0x2c84, 0x2c84, UppercaseLetter),
+    (0x2c85, 0x2c85, LowercaseLetter),
+    (0x2c86, 0x2c86, UppercaseLetter),
+    (0x2c87, 0x2c87, LowercaseLetter),
+    (0x2c88, 0x2c88, UppercaseLetter),
+    (0x2c89, 0x2c89, LowercaseLetter),
+    (0x2c8a, 0x2c8a, UppercaseLetter),
+    (0x2c8b, 0x2c8b, LowercaseLetter),
+    (0x2c8c, 0x2c8c, UppercaseLetter),
+    (0x2c8d, 0x2c8d, LowercaseLetter),
+    (0x2c8e, 0x2c8e, UppercaseLetter),
+    (0x2c8f, 0x2c8f, LowercaseLetter),
+    (0x2c90, 0x2c90, UppercaseLetter),
+    (0x2c91, 0x2c91, LowercaseLetter),
+    (0x2c92, 0x2c92, UppercaseLetter),
+    (0x2c93, 0x2c93, LowercaseLetter),
+    (0x2c94, 0x2c94, UppercaseLetter),
+    (0x2c95, 0x2c95, LowercaseLetter),
+    (0x2c96, 0x2c96, UppercaseLetter),
+    (0x2c97, 0x2c97, LowercaseLetter),
+    (0x2c98, 0x2c98, UppercaseLetter),
+    (0x2c99, 0x2c99, LowercaseLetter),
+    (0x2c9a, 0x2c9a, UppercaseLetter),
+    (0x2c9b, 0x2c9b, LowercaseLetter),
+    (0x2c9c, 0x2c9c, UppercaseLetter),
+    (0x2c9d, 0x2c9d, LowercaseLetter),
+    (0x2c9e, 0x2c9e, UppercaseLetter),
+    (0x2c9f, 0x2c9f, LowercaseLetter),
+    (0x2ca0, 0x2ca0, UppercaseLetter),
+    (0x2ca1, 0x2ca1, LowercaseLetter),
+    (0x2ca2, 0x2ca2, UppercaseLetter),
+    (0x2ca3, 0x2ca3, LowercaseLetter),
+    (0x2ca4, 0x2ca4, UppercaseLetter),
+    (0x2ca5, 0x2ca5, LowercaseLetter),
+    (0x2ca6, 0x2ca6, UppercaseLetter),
+    (0x2ca7, 0x2ca7, LowercaseLetter),
+    (0x2ca8, 0x2ca8, UppercaseLetter),
+    (0x2ca9, 0x2ca9, LowercaseLetter),
+    (0x2caa, 0x2caa, UppercaseLetter),
+    (0x2cab, 0x2cab, LowercaseLetter),
+    (0x2cac, 0x2cac, UppercaseLetter),
+    (0x2cad, 0x2cad, LowercaseLetter),
+    (0x2cae, 0x2cae, UppercaseLetter),
+    (0x2caf, 0x2caf, LowercaseLetter),
+    (0x2cb0, 0x2cb0, UppercaseLetter),
+    (0x2cb1, 0x2cb1, LowercaseLetter),
+    (0x2cb2, 0x2cb2, UppercaseLetter),
+    (0x2cb3, 0x2cb3, LowercaseLetter),
+    (0x2cb4, 0x2cb4, UppercaseLetter),
+    (0x2cb5, 0x2cb5, LowercaseLetter),
+    (0x2cb6, 0x2cb6, UppercaseLetter),
+    (0x2cb7, 0x2cb7, LowercaseLetter),
+    (0x2cb8, 0x2cb8, UppercaseLetter),
+    (0x2cb9, 0x2cb9, LowercaseLetter),
+    (0x2cba, 0x2cba, UppercaseLetter),
+    (0x2cbb, 0x2cbb, LowercaseLetter),
+    (0x2cbc, 0x2cbc, UppercaseLetter),
+    (0x2cbd, 0x2cbd, LowercaseLetter),
+    (0x2cbe, 0x2cbe, UppercaseLetter),
+    (0x2cbf, 0x2cbf, LowercaseLetter),
+    (0x2cc0, 0x2cc0, UppercaseLetter),
+    (0x2cc1, 0x2cc1, LowercaseLetter),
+    (0x2cc2, 0x2cc2, UppercaseLetter),
+    (0x2cc3, 0x2cc3, LowercaseLetter),
+    (0x2cc4, 0x2cc4, UppercaseLetter),
+    (0x2cc5, 0x2cc5, LowercaseLetter),
+    (0x2cc6, 0x2cc6, UppercaseLetter),
+    (0x2cc7, 0x2cc7, LowercaseLetter),
+    (0x2cc8, 0x2cc8, UppercaseLetter),
+    (0x2cc9, 0x2cc9, LowercaseLetter),
+    (0x2cca, 0x2cca, UppercaseLetter),
+    (0x2ccb, 0x2ccb, LowercaseLetter),
+    (0x2ccc, 0x2ccc, UppercaseLetter),
+    (0x2ccd, 0x2ccd, LowercaseLetter),
+    (0x2cce, 0x2cce, UppercaseLetter),
+    (0x2ccf, 0x2ccf, LowercaseLetter),
+    (0x2cd0, 0x2cd0, UppercaseLetter),
+    (0x2cd1, 0x2cd1, LowercaseLetter),
+    (0x2cd2, 0x2cd2, UppercaseLetter),
+    (0x2cd3, 0x2cd3, LowercaseLetter),
+    (0x2cd4, 0x2cd4, UppercaseLetter),
+    (0x2cd5, 0x2cd5, LowercaseLetter),
+    (0x2cd6, 0x2cd6, UppercaseLetter),
+    (0x2cd7, 0x2cd7, LowercaseLetter),
+    (0x2cd8, 0x2cd8, UppercaseLetter),
+    (0x2cd9, 0x2cd9, LowercaseLetter),
+    (0x2cda, 0x2cda, UppercaseLetter),
+    (0x2cdb, 0x2cdb, LowercaseLetter),
+    (0x2cdc, 0x2cdc, UppercaseLetter),
+    (0x2cdd, 0x2cdd, LowercaseLetter),
+    (0x2cde, 0x2cde, UppercaseLetter),
+    (0x2cdf, 0x2cdf, LowercaseLetter),
+    (0x2ce0, 0x2ce0, UppercaseLetter),
+    (0x2ce1, 0x2ce1, LowercaseLetter),
+    (0x2ce2, 0x2ce2, UppercaseLetter),
+    (0x2ce3, 0x2ce4, LowercaseLetter),
+    (0x2ce5, 0x2cea, OtherSymbol),
+    (0x2ceb, 0x2ceb, UppercaseLetter),
+    (0x2cec, 0x2cec, LowercaseLetter),
+    (0x2ced, 0x2ced, UppercaseLetter),
+    (0x2cee, 0x2cee, LowercaseLetter),
+    (0x2cef, 0x2cf1, NonspacingMark),
+    (0x2cf2, 0x2cf2, UppercaseLetter),
+    (0x2cf3, 0x2cf3, LowercaseLetter),
+    (0x2cf9, 0x2cfc, OtherPunctuation),
+    (0x2cfd, 0x2cfd, OtherNumber),
+    (0x2cfe, 0x2cff, OtherPunctuation),
+    (0x2d00, 0x2d25, LowercaseLetter),
+    (0x2d27, 0x2d27, LowercaseLetter),
+    (0x2d2d, 0x2d2d, LowercaseLetter),
+    (0x2d30, 0x2d67, OtherLetter),
+    (0x2d6f, 0x2d6f, ModifierLetter),
+    (0x2d70, 0x2d70, OtherPunctuation),
+    (0x2d7f, 0x2d7f, NonspacingMark),
+    (0x2d80, 0x2d96, OtherLetter),
+    (0x2da0, 0x2da6, OtherLetter),
+    (0x2da8, 0x2dae, OtherLetter),
+    (0x2db0, 0x2db6, OtherLetter),
+    (0x2db8, 0x2dbe, OtherLetter),
+    (0x2dc0, 0x2dc6, OtherLetter),
+    (0x2dc8, 0x2dce, OtherLetter),
+    (0x2dd0, 0x2dd6, OtherLetter),
+    (0x2dd8, 0x2dde, OtherLetter),
+    (0x2de0, 0x2dff, NonspacingMark),
+    (0x2e00, 0x2e01, OtherPunctuation),
+    (0x2e02, 0x2e02, InitialPunctuation),
+    (0x2e03, 0x2e03, FinalPunctuation),
+    (0x2e04, 0x2e04, InitialPunctuation),
+    (0x2e05, 0x2e05, FinalPunctuation),
+    (0x2e06, 0x2e08, OtherPunctuation),
+    (0x2e09, 0x2e09, InitialPunctuation),
+    (0x2e0a, 0x2e0a, FinalPunctuation),
+    (0x2e0b, 0x2e0b, OtherPunctuation),
+    (0x2e0c, 0x2e0c, InitialPunctuation),
+    (0x2e0d, 0x2e0d, FinalPunctuation),
+    (0x2e0e, 0x2e16, OtherPunctuation),
+    (0x2e17, 0x2e17, DashPunctuation),
+    (0x2e18, 0x2e19, OtherPunctuation),
+    (0x2e1a, 0x2e1a, DashPunctuation),
+    (0x2e1b, 0x2e1b, OtherPunctuation),
+    (0x2e1c, 0x2e1c, InitialPunctuation),
+    (0x2e1d, 0x2e1d, FinalPunctuation),
+    (0x2e1e, 0x2e1f, OtherPunctuation),
+    (0x2e20, 0x2e20, InitialPunctuation),
+    (0x2e21, 0x2e21, FinalPunctuation),
+    (0x2e22, 0x2e22, OpenPunctuation),
+    (0x2e23, 0x2e23, ClosePunctuation),
+    (0x2e24, 0x2e24, OpenPunctuation),
+    (0x2e25, 0x2e25, ClosePunctuation),
+    (0x2e26, 0x2e26, OpenPunctuation),
+    (0x2e27, 0x2e27, ClosePunctuation),
+    (0x2e28, 0x2e28, OpenPunctuation),
+    (0x2e29, 0x2e29, ClosePunctuation),
+    (0x2e2a, 0x2e2e, OtherPunctuation),
+    (0x2e2f, 0x2e2f, ModifierLetter),
+    (0x2e30, 0x2e39, OtherPunctuation),
+    (0x2e3a, 0x2e3b, DashPunctuation),
+    (0x2e3c, 0x2e3f, OtherPunctuation),
+    (0x2e40, 0x2e40, DashPunctuation),
+    (0x2e41, 0x2e41, OtherPunctuation),
+    (0x2e42, 0x2e42, OpenPunctuation),
+    (0x2e43, 0x2e4f, OtherPunctuation),
+    (0x2e50, 0x2e51, OtherSymbol),
+    (0x2e52, 0x2e54, OtherPunctuation),
+    (0x2e55, 0x2e55, OpenPunctuation),
+    (0x2e56, 0x2e56, ClosePunctuation),
+    (0x2e57, 0x2e57, OpenPunctuation),
+    (0x2e58, 0x2e58, ClosePunctuation),
+    (0x2e59, 0x2e59, OpenPunctuation),
+    (0x2e5a, 0x2e5a, ClosePunctuation),
+    (0x2e5b, 0x2e5b, OpenPunctuation),
+    (0x2e5c, 0x2e5c, ClosePunctuation),
+    (0x2e5d, 0x2e5d, DashPunctuation),
+    (0x2e80, 0x2e99, OtherSymbol),
+    (0x2e9b, 0x2ef3, OtherSymbol),
+    (0x2f00, 0x2fd5, OtherSymbol),
+    (0x2ff0, 0x2ffb, OtherSymbol),
+    (0x3000, 0x3000, SpaceSeparator),
+    (0x3001, 0x3003, OtherPunctuation),
+    (0x3004, 0x3004, OtherSymbol),
+    (0x3005, 0x3005, ModifierLetter),
+    (0x3006, 0x3006, OtherLetter),
+    (0x3007, 0x3007, LetterNumber),
+    (0x3008, 0x3008, OpenPunctuation),
+    (0x3009, 0x3009, ClosePunctuation),
+    (0x300a, 0x300a, OpenPunctuation),
+    (0x300b, 0x300b, ClosePunctuation),
+    (0x300c, 0x300c, OpenPunctuation),
+    (0x300d, 0x300d, ClosePunctuation),
+    (0x300e, 0x300e, OpenPunctuation),
+    (0x300f, 0x300f, ClosePunctuation),
+    (0x3010, 0x3010, OpenPunctuation),
+    (0x3011, 0x3011, ClosePunctuation),
+    (0x3012, 0x3013, OtherSymbol),
+    (0x3014, 0x3014, OpenPunctuation),
+    (0x3015, 0x3015, ClosePunctuation),
+    (0x3016, 0x3016, OpenPunctuation),
+    (0x3017, 0x3017, ClosePunctuation),
+    (0x3018, 0x3018, OpenPunctuation),
+    (0x3019, 0x3019, ClosePunctuation),
+    (0x301a, 0x301a, OpenPunctuation),
+    (0x301b, 0x301b, ClosePunctuation),
+    (0x301c, 0x301c, DashPunctuation),
+    (0x301d, 0x301d, OpenPunctuation),
+    (0x301e, 0x301f, ClosePunctuation),
+    (0x3020, 0x3020, OtherSymbol),
+    (0x3021, 0x3029, LetterNumber),
+    (0x302a, 0x302d, NonspacingMark),
+    (0x302e, 0x302f, SpacingMark),
+    (0x3030, 0x3030, DashPunctuation),
+    (0x3031, 0x3035, ModifierLetter),
+    (0x3036, 0x3037, OtherSymbol),
+    (0x3038, 0x303a, LetterNumber),
+    (0x303b, 0x303b, ModifierLetter),
+    (0x303c, 0x303c, OtherLetter),
+    (0x303d, 0x303d, OtherPunctuation),
+    (0x303e, 0x303f, OtherSymbol),
+    (0x3041, 0x3096, OtherLetter),
+    (0x3099, 0x309a, NonspacingMark),
+    (0x309b, 0x309c, ModifierSymbol),
+    (0x309d, 0x309e, ModifierLetter),
+    (0x309f, 0x309f, OtherLetter),
+    (0x30a0, 0x30a0, DashPunctuation),
+    (0x30a1, 0x30fa, OtherLetter),
+    (0x30fb, 0x30fb, OtherPunctuation),
+    (0x30fc, 0x30fe, ModifierLetter),
+    (0x30ff, 0x30ff, OtherLetter),
+    (0x3105, 0x312f, OtherLetter),
+    (0x3131, 0x318e, OtherLetter),
+    (0x3190, 0x3191, OtherSymbol),
+    (0x3192, 0x3195, OtherNumber),
+    (0x3196, 0x319f, OtherSymbol),
+    (0x31a0, 0x31bf, OtherLetter),
+    (0x31c0, 0x31e3, OtherSymbol),
+    (0x31f0, 0x31ff, OtherLetter),
+    (0x3200, 0x321e, OtherSymbol),
+    (0x3220, 0x3229, OtherNumber),
+    (0x322a, 0x3247, OtherSymbol),
+    (0x3248, 0x324f, OtherNumber),
+    (0x3250, 0x3250, OtherSymbol),
+    (0x3251, 0x325f, OtherNumber),
+    (0x3260, 0x327f, OtherSymbol),
+    (0x3280, 0x3289, OtherNumber),
+    (0x328a, 0x32b0, OtherSymbol),
+    (0x32b1, 0x32bf, OtherNumber),
+    (0x32c0, 0x33ff, OtherSymbol),
+    (0x3400, 0x4dbf, OtherLetter),
+    (0x4dc0, 0x4dff, OtherSymbol),
+    (0x4e00, 0xa014, OtherLetter),
+    (0xa015, 0xa015, ModifierLetter),
+    (0xa016, 0xa48c, OtherLetter),
+    (0xa490, 0xa4c6, OtherSymbol),
+    (0xa4d0, 0xa4f7, OtherLetter),
+    (0xa4f8, 0xa4fd, ModifierLetter),
+    (0xa4fe, 0xa4ff, OtherPunctuation),
+    (0xa500, 0xa60b, OtherLetter),
+    (0xa60c, 0xa60c, ModifierLetter),
+    (0xa60d, 0xa60f, OtherPunctuation),
+    (0xa610, 0xa61f, OtherLetter),
+    (0xa620, 0xa629, DecimalNumber),
+    (0xa62a, 0xa62b, OtherLetter),
+    (0xa640, 0xa640, UppercaseLetter),
+    (0xa641, 0xa641, LowercaseLetter),
+    (0xa642, 0xa642, UppercaseLetter),
+    (0xa643, 0xa643, LowercaseLetter),
+    (0xa644, 0xa644, UppercaseLetter),
+    (0xa645, 0xa645, LowercaseLetter),
+    (0xa646, 0xa646, UppercaseLetter),
+    (0xa647, 0xa647, LowercaseLetter),
+    (0xa648, 0xa648, UppercaseLetter),
+    (0xa649, 0xa649, LowercaseLetter),
+    (0xa64a, 0xa64a, UppercaseLetter),
+    (0xa64b, 0xa64b, LowercaseLetter),
+    (0xa64c, 0xa64c, UppercaseLetter),
+    (0xa64d, 0xa64d, LowercaseLetter),
+    (0xa64e, 0xa64e, UppercaseLetter),
+    (0xa64f, 0xa64f, LowercaseLetter),
+    (0xa650, 0xa650, UppercaseLetter),
+    (0xa651, 0xa651, LowercaseLetter),
+    (0xa652, 0xa652, UppercaseLetter),
+    (0xa653, 0xa653, LowercaseLetter),
+    (0xa654, 0xa654, UppercaseLetter),
+    (0xa655, 0xa655, LowercaseLetter),
+    (0xa656, 0xa656, UppercaseLetter),
+    (0xa657, 0xa657, LowercaseLetter),
+    (0xa658, 0xa658, UppercaseLetter),
+    (0xa659, 0xa659, LowercaseLetter),
+    (0xa65a, 0xa65a, UppercaseLetter),
+    (0xa65b, 0xa65b, LowercaseLetter),
+    (0xa65c, 0xa65c, UppercaseLetter),
+    (0xa65d, 0xa65d, LowercaseLetter),
+    (0xa65e, 0xa65e, UppercaseLetter),
+    (0xa65f, 0xa65f, LowercaseLetter),
+    (0xa660, 0xa660, UppercaseLetter),
+    (0xa661, 0xa661, LowercaseLetter),
+    (0xa662, 0xa662, UppercaseLetter),
+    (0xa663, 0xa663, LowercaseLetter),
+    (0xa664, 0xa664, UppercaseLetter),
+    (0xa665, 0xa665, LowercaseLetter),
+    (0xa666, 0xa666, UppercaseLetter),
+    (0xa667, 0xa667, LowercaseLetter),
+    (0xa668, 0xa668, UppercaseLetter),
+    (0xa669, 0xa669, LowercaseLetter),
+    (0xa66a, 0xa66a, UppercaseLetter),
+    (0xa66b, 0xa66b, LowercaseLetter),
+    (0xa66c, 0xa66c, UppercaseLetter),
+    (0xa66d, 0xa66d, LowercaseLetter),
+    (0xa66e, 0xa66e, OtherLetter),
+    (0xa66f, 0xa66f, NonspacingMark),
+    (0xa670, 0xa672, EnclosingMark),
+    (0xa673, 0xa673, OtherPunctuation),
+    (0xa674, 0xa67d, NonspacingMark),
+    (0xa67e, 0xa67e, OtherPunctuation),
+    (0xa67f, 0xa67f, ModifierLetter),
+    (0xa680, 0xa680, UppercaseLetter),
+    (0xa681, 0xa681, LowercaseLetter),
+    (0xa682, 0xa682, UppercaseLetter),
+    (0xa683, 0xa683, LowercaseLetter),
+    (0xa684, 0xa684, UppercaseLetter),
+    (0xa685, 0xa685, LowercaseLetter),
+    (0xa686, 0xa686, UppercaseLetter),
+    (0xa687, 0xa687, LowercaseLetter),
+    (0xa688, 0xa688, UppercaseLetter),
+    (0xa689, 0xa689, LowercaseLetter),
+    (0xa68a, 0xa68a, UppercaseLetter),
+    (0xa68b, 0xa68b, LowercaseLetter),
+    (0xa68c, 0xa68c, UppercaseLetter),
+    (0xa68d, 0xa68d, LowercaseLetter),
+    (0xa68e, 0xa68e, UppercaseLetter),
+    (0xa68f, 0xa68f, LowercaseLetter),
+    (0xa690, 0xa690, UppercaseLetter),
+    (0xa691, 0xa691, LowercaseLetter),
+    (0xa692, 0xa692, UppercaseLetter),
+    (0xa693, 0xa693, LowercaseLetter),
+    (0xa694, 0xa694, UppercaseLetter),
+    (0xa695, 0xa695, LowercaseLetter),
+    (0xa696, 0xa696, UppercaseLetter),
+    (0xa697, 0xa697, LowercaseLetter),
+    (0xa698, 0xa698, UppercaseLetter),
+    (0xa699, 0xa699, LowercaseLetter),
+    (0xa69a, 0xa69a, UppercaseLetter),
+    (0xa69b, 0xa69b, LowercaseLetter),
+    (0xa69c, 0xa69d, ModifierLetter),
+    (0xa69e, 0xa69f, NonspacingMark),
+    (0xa6a0, 0xa6e5, OtherLetter),
+    (0xa6e6, 0xa6ef, LetterNumber),
+    (0xa6f0, 0xa6f1, NonspacingMark),
+    (0xa6f2, 0xa6f7, OtherPunctuation),
+    (0xa700, 0xa716, ModifierSymbol),
+    (0xa717, 0xa71f, ModifierLetter),
+    (0xa720, 0xa721, ModifierSymbol),
+    (0xa722, 0xa722, UppercaseLetter),
+    (0xa723, 0xa723, LowercaseLetter),
+    (0xa724, 0xa724, UppercaseLetter),
+    (0xa725, 0xa725, LowercaseLetter),
+    (0xa726, 0xa726, UppercaseLetter),
+    (0xa727, 0xa727, LowercaseLetter),
+    (0xa728, 0xa728, UppercaseLetter),
+    (0xa729, 0xa729, LowercaseLetter),
+    (0xa72a, 0xa72a, UppercaseLetter),
+    (0xa72b, 0xa72b, LowercaseLetter),
+    (0xa72c, 0xa72c, UppercaseLetter),
+    (0xa72d, 0xa72d, LowercaseLetter),
+    (0xa72e, 0xa72e, UppercaseLetter),
+    (0xa72f, 0xa731, LowercaseLetter),
+    (0xa732, 0xa732, UppercaseLetter),
+    (0xa733, 0xa733, LowercaseLetter),
+    (0xa734, 0xa734, UppercaseLetter),
+    (0xa735, 0xa735, LowercaseLetter),
+    (0xa736, 0xa736, UppercaseLetter),
+    (0xa737, 0xa737, LowercaseLetter),
+    (0xa738, 0xa738, UppercaseLetter),
+    (0xa739, 0xa739, LowercaseLetter),
+    (0xa73a, 0xa73a, UppercaseLetter),
+    (0xa73b, 0xa73b, LowercaseLetter),
+    (0xa73c, 0xa73c, UppercaseLetter),
+    (0xa73d, 0xa73d, LowercaseLetter),
+    (0xa73e, 0xa73e, UppercaseLetter),
+    (0xa73f, 0xa73f, LowercaseLetter),
+    (0xa740, 0xa740, UppercaseLetter),
+    (0xa741, 0xa741, LowercaseLetter),
+    (0xa742, 0xa742, UppercaseLetter),
+    (0xa743, 0xa743, LowercaseLetter),
+    (0xa744, 0xa744, UppercaseLetter),
+    (0xa745, 0xa745, LowercaseLetter),
+    (0xa746, 0xa746, UppercaseLetter),
+    (0xa747, 0xa747, LowercaseLetter),
+    (0xa748, 0xa748, UppercaseLetter),
+    (0xa749, 0xa749, LowercaseLetter),
+    (0xa74a, 0xa74a, UppercaseLetter),
+    (0xa74b, 0xa74b, LowercaseLetter),
+    (0xa74c, 0xa74c, UppercaseLetter),
+    (0xa74d, 0xa74d, LowercaseLetter),
+    (0xa74e, 0xa74e, UppercaseLetter),
+    (0xa74f, 0xa74f, LowercaseLetter),
+    (0xa750, 0xa750, UppercaseLetter),
+    (0xa751, 0xa751, LowercaseLetter),
+    (0xa752, 0xa752, UppercaseLetter),
+    (0xa753, 0xa753, LowercaseLetter),
+    (0xa754, 0xa754, UppercaseLetter),
+    (0xa755, 0xa755, LowercaseLetter),
+    (0xa756, 0xa756, UppercaseLetter),
+    (0xa757, 0xa757, LowercaseLetter),
+    (0xa758, 0xa758, UppercaseLetter),
+    (0xa759, 0xa759, LowercaseLetter),
+    (0xa75a, 0xa75a, UppercaseLetter),
+    (0xa75b, 0xa75b, LowercaseLetter),
+    (0xa75c, 0xa75c, UppercaseLetter),
+    (0xa75d, 0xa75d, LowercaseLetter),
+    (0xa75e, 0xa75e, UppercaseLetter),
+    (0xa75f, 0xa75f, LowercaseLetter),
+    (0xa760, 0xa760, UppercaseLetter),
+    (0xa761, 0xa761, LowercaseLetter),
+    (0xa762, 0xa762, UppercaseLetter),
+    (0xa763, 0xa763, LowercaseLetter),
+    (0xa764, 0xa764, UppercaseLetter),
+    (0xa765, 0xa765, LowercaseLetter),
+    (0xa766, 0xa766, UppercaseLetter),
+    (0xa767, 0xa767, LowercaseLetter),
+    (0xa768, 0xa768, UppercaseLetter),
+    (0xa769, 0xa769, LowercaseLetter),
+    (0xa76a, 0xa76a, UppercaseLetter),
+    (0xa76b, 0xa76b, LowercaseLetter),
+    (0xa76c, 0xa76c, UppercaseLetter),
+    (0xa76d, 0xa76d, LowercaseLetter),
+    (0xa76e, 0xa76e, UppercaseLetter),
+    (0xa76f, 0xa76f, LowercaseLetter),
+    (0xa770, 0xa770, ModifierLetter),
+    (0xa771, 0xa778, LowercaseLetter),
+    (0xa779, 0xa779, UppercaseLetter),
+    (0xa77a, 0xa77a, LowercaseLetter),
+    (0xa77b, 0xa77b, UppercaseLetter),
+    (0xa77c, 0xa77c, LowercaseLetter),
+    (0xa77d, 0xa77e, UppercaseLetter),
+    (0xa77f, 0xa77f, LowercaseLetter),
+    (0xa780, 0xa780, UppercaseLetter),
+    (0xa781, 0xa781, LowercaseLetter),
+    (0xa782, 0xa782, UppercaseLetter),
+    (0xa783, 0xa783, LowercaseLetter),
+    (0xa784, 0xa784, UppercaseLetter),
+    (0xa785, 0xa785, LowercaseLetter),
+    (0xa786, 0xa786, UppercaseLetter),
+    (0xa787, 0xa787, LowercaseLetter),
+    (0xa788, 0xa788, ModifierLetter),
+    (0xa789, 0xa78a, ModifierSymbol),
+    (0xa78b, 0xa78b, UppercaseLetter),
+    (0xa78c, 0xa78c, LowercaseLetter),
+    (0xa78d, 0xa78d, UppercaseLetter),
+    (0xa78e, 0xa78e, LowercaseLetter),
+    (0xa78f, 0xa78f, OtherLetter),
+    (0xa790, 0xa790, UppercaseLetter),
+    (0xa791, 0xa791, LowercaseLetter),
+    (0xa792, 0xa792, UppercaseLetter),
+    (0xa793, 0xa795, LowercaseLetter),
+    (0xa796, 0xa796, UppercaseLetter),
+    (0xa797, 0xa797, LowercaseLetter),
+    (0xa798, 0xa798, UppercaseLetter),
+    (0xa799, 0xa799, LowercaseLetter),
+    (0xa79a, 0xa79a, UppercaseLetter),
+    (0xa79b, 0xa79b, LowercaseLetter),
+    (0xa79c, 0xa79c, UppercaseLetter),
+    (0xa79d, 0xa79d, LowercaseLetter),
+    (0xa79e, 0xa79e, UppercaseLetter),
+    (0xa79f, 0xa79f, LowercaseLetter),
+    (0xa7a0, 0xa7a0, UppercaseLetter),
+    (0xa7a1, 0xa7a1, LowercaseLetter),
+    (0xa7a2, 0xa7a2, UppercaseLetter),
+    (0xa7a3, 0xa7a3, LowercaseLetter),
+    (0xa7a4, 0xa7a4, UppercaseLetter),
+    (0xa7a5, 0xa7a5, LowercaseLetter),
+    (0xa7a6, 0xa7a6, UppercaseLetter),
+    (0xa7a7, 0xa7a7, LowercaseLetter),
+    (0xa7a8, 0xa7a8, UppercaseLetter),
+    (0xa7a9, 0xa7a9, LowercaseLetter),
+    (0xa7aa, 0xa7ae, UppercaseLetter),
+    (0xa7af, 0xa7af, LowercaseLetter),
+    (0xa7b0, 0xa7b4, UppercaseLetter),
+    (0xa7b5, 0xa7b5, LowercaseLetter),
+    (0xa7b6, 0xa7b6, UppercaseLetter),
+    (0xa7b7, 0xa7b7, LowercaseLetter),
+    (0xa7b8, 0xa7b8, UppercaseLetter),
+    (0xa7b9, 0xa7b9, LowercaseLetter),
+    (0xa7ba, 0xa7ba, UppercaseLetter),
+    (0xa7bb, 0xa7bb, LowercaseLetter),
+    (0xa7bc, 0xa7bc, UppercaseLetter),
+    (0xa7bd, 0xa7bd, LowercaseLetter),
+    (0xa7be, 0xa7be, UppercaseLetter),
+    (0xa7bf, 0xa7bf, LowercaseLetter),
+    (0xa7c0, 0xa7c0, UppercaseLetter),
+    (0xa7c1, 0xa7c1, LowercaseLetter),
+    (0xa7c2, 0xa7c2, UppercaseLetter),
+    (0xa7c3, 0xa7c3, LowercaseLetter),
+    (0xa7c4, 0xa7c7, UppercaseLetter),
+    (0xa7c8, 0xa7c8, LowercaseLetter),
+    (0xa7c9, 0xa7c9, UppercaseLetter),
+    (0xa7ca, 0xa7ca, LowercaseLetter),
+    (0xa7d0, 0xa7d0, UppercaseLetter),
+    (0xa7d1, 0xa7d1, LowercaseLetter),
+    (0xa7d3, 0xa7d3, LowercaseLetter),
+    (0xa7d5, 0xa7d5, LowercaseLetter),
+    (0xa7d6, 0xa7d6, UppercaseLetter),
+    (0xa7d7, 0xa7d7, LowercaseLetter),
+    (0xa7d8, 0xa7d8, UppercaseLetter),
+    (0xa7d9, 0xa7d9, LowercaseLetter),
+    (0xa7f2, 0xa7f4, ModifierLetter),
+    (0xa7f5, 0xa7f5, UppercaseLetter),
+    (0xa7f6, 0xa7f6, LowercaseLetter),
+    (0xa7f7, 0xa7f7, OtherLetter),
+    (0xa7f8, 0xa7f9, ModifierLetter),
+    (0xa7fa, 0xa7fa, LowercaseLetter),
+    (0xa7fb, 0xa801, OtherLetter),
+    (0xa802, 0xa802, NonspacingMark),
+    (0xa803, 0xa805, OtherLetter),
+    (0xa806, 0xa806, NonspacingMark),
+    (0xa807, 0xa80a, OtherLetter),
+    (0xa80b, 0xa80b, NonspacingMark),
+    (0xa80c, 0xa822, OtherLetter),
+    (0xa823, 0xa824, SpacingMark),
+    (0xa825, 0xa826, NonspacingMark),
+    (0xa827, 0xa827, SpacingMark),
+    (0xa828, 0xa82b, OtherSymbol),
+    (0xa82c, 0xa82c, NonspacingMark),
+    (0xa830, 0xa835, OtherNumber),
+    (0xa836, 0xa837, OtherSymbol),
+    (0xa838, 0xa838, CurrencySymbol),
+    (0xa839, 0xa839, OtherSymbol),
+    (0xa840, 0xa873, OtherLetter),
+    (0xa874, 0xa877, OtherPunctuation),
+    (0xa880, 0xa881, SpacingMark),
+    (0xa882, 0xa8b3, OtherLetter),
+    (0xa8b4, 0xa8c3, SpacingMark),
+    (0xa8c4, 0xa8c5, NonspacingMark),
+    (0xa8ce, 0xa8cf, OtherPunctuation),
+    (0xa8d0, 0xa8d9, DecimalNumber),
+    (0xa8e0, 0xa8f1, NonspacingMark),
+    (0xa8f2, 0xa8f7, OtherLetter),
+    (0xa8f8, 0xa8fa, OtherPunctuation),
+    (0xa8fb, 0xa8fb, OtherLetter),
+    (0xa8fc, 0xa8fc, OtherPunctuation),
+    (0xa8fd, 0xa8fe, OtherLetter),
+    (0xa8ff, 0xa8ff, NonspacingMark),
+    (0xa900, 0xa909, DecimalNumber),
+    (0xa90a, 0xa925, OtherLetter),
+    (0xa926, 0xa92d, NonspacingMark),
+    (0xa92e, 0xa92f, OtherPunctuation),
+    (0xa930, 0xa946, OtherLetter),
+    (0xa947, 0xa951, NonspacingMark),
+    (0xa952, 0xa953, SpacingMark),
+    (0xa95f, 0xa95f, OtherPunctuation),
+    (0xa960, 0xa97c, OtherLetter),
+    (0xa980, 0xa982, NonspacingMark),
+    (0xa983, 0xa983, SpacingMark),
+    (0xa984, 0xa9b2, OtherLetter),
+    (0xa9b3, 0xa9b3, NonspacingMark),
+    (0xa9b4, 0xa9b5, SpacingMark),
+    (0xa9b6, 0xa9b9, NonspacingMark),
+    (0xa9ba, 0xa9bb, SpacingMark),
+    (0xa9bc, 0xa9bd, NonspacingMark),
+    (0xa9be, 0xa9c0, SpacingMark),
+    (0xa9c1, 0xa9cd, OtherPunctuation),
+    (0xa9cf, 0xa9cf, ModifierLetter),
+    (0xa9d0, 0xa9d9, DecimalNumber),
+    (0xa9de, 0xa9df, OtherPunctuation),
+    (0xa9e0, 0xa9e4, OtherLetter),
+    (0xa9e5, 0xa9e5, NonspacingMark),
+    (0xa9e6, 0xa9e6, ModifierLetter),
+    (0xa9e7, 0xa9ef, OtherLetter),
+    (0xa9f0, 0xa9f9, DecimalNumber),
+    (0xa9fa, 0xa9fe, OtherLetter),
+    (0xaa00, 0xaa28, OtherLetter),
+    (0xaa29, 0xaa2e, NonspacingMark),
+    (0xaa2f, 0xaa30, SpacingMark),
+    (0xaa31, 0xaa32, NonspacingMark),
+    (0xaa33, 0xaa34, SpacingMark),
+    (0xaa35, 0xaa36, NonspacingMark),
+    (0xaa40, 0xaa42, OtherLetter),
+    (0xaa43, 0xaa43, NonspacingMark),
+    (0xaa44, 0xaa4b, OtherLetter),
+    (0xaa4c, 0xaa4c, NonspacingMark),
+    (0xaa4d, 0xaa4d, SpacingMark),
+    (0xaa50, 0xaa59, DecimalNumber),
+    (0xaa5c, 0xaa5f, OtherPunctuation),
+    (0xaa60, 0xaa6f, OtherLetter),
+    (0xaa70, 0xaa70, ModifierLetter),
+    (0xaa71, 0xaa76, OtherLetter),
+    (0xaa77, 0xaa79, OtherSymbol),
+    (0xaa7a, 0xaa7a, OtherLetter),
+    (0xaa7b, 0xaa7b, SpacingMark),
+    (0xaa7c, 0xaa7c, NonspacingMark),
+    (0xaa7d, 0xaa7d, SpacingMark),
+    (0xaa7e, 0xaaaf, OtherLetter),
+    (0xaab0, 0xaab0, NonspacingMark),
+    (0xaab1, 0xaab1, OtherLetter),
+    (0xaab2, 0xaab4, NonspacingMark),
+    (0xaab5, 0xaab6, OtherLetter),
+    (0xaab7, 0xaab8, NonspacingMark),
+    (0xaab9, 0xaabd, OtherLetter),
+    (0xaabe, 0xaabf, NonspacingMark),
+    (0xaac0, 0xaac0, OtherLetter),
+    (0xaac1, 0xaac1, NonspacingMark),
+    (0xaac2, 0xaac2, OtherLetter),
+    (0xaadb, 0xaadc, OtherLetter),
+    (0xaadd, 0xaadd, ModifierLetter),
+    (0xaade, 0xaadf, OtherPunctuation),
+    (0xaae0, 0xaaea, OtherLetter),
+    (0xaaeb, 0xaaeb, SpacingMark),
+    (0xaaec, 0xaaed, NonspacingMark),
+    (0xaaee, 0xaaef, SpacingMark),
+    (0xaaf0, 0xaaf1, OtherPunctuation),
+    (0xaaf2, 0xaaf2, OtherLetter),
+    (0xaaf3, 0xaaf4, ModifierLetter),
+    (0xaaf5, 0xaaf5, SpacingMark),
+    (0xaaf6, 0xaaf6, NonspacingMark),
+    (0xab01, 0xab06, OtherLetter),
+    (0xab09, 0xab0e, OtherLetter),
+    (0xab11, 0xab16, OtherLetter),
+    (0xab20, 0xab26, OtherLetter),
+    (0xab28, 0xab2e, OtherLetter),
+    (0xab30, 0xab5a, LowercaseLetter),
+    (0xab5b, 0xab5b, ModifierSymbol),
+    (0xab5c, 0xab5f, ModifierLetter),
+    (0xab60, 0xab68, LowercaseLetter),
+    (0xab69, 0xab69, ModifierLetter),
+    (0xab6a, 0xab6b, ModifierSymbol),
+    (0xab70, 0xabbf, LowercaseLetter),
+    (0xabc0, 0xabe2, OtherLetter),
+    (0xabe3, 0xabe4, SpacingMark),
+    (0xabe5, 0xabe5, NonspacingMark),
+    (0xabe6, 0xabe7, SpacingMark),
+    (0xabe8, 0xabe8, NonspacingMark),
+    (0xabe9, 0xabea, SpacingMark),
+    (0xabeb, 0xabeb, OtherPunctuation),
+    (0xabec, 0xabec, SpacingMark),
+    (0xabed, 0xabed, NonspacingMark),
+    (0xabf0, 0xabf9, DecimalNumber),
+    (0xac00, 0xd7a3, OtherLetter),
+    (0xd7b0, 0xd7c6, OtherLetter),
+    (0xd7cb, 0xd7fb, OtherLetter),
+    (0xe000, 0xf8ff, PrivateUse),
+    (0xf900, 0xfa6d, OtherLetter),
+    (0xfa70, 0xfad9, OtherLetter),
+    (0xfb00, 0xfb06, LowercaseLetter),
+    (0xfb13, 0xfb17, LowercaseLetter),
+    (0xfb1d, 0xfb1d, OtherLetter),
+    (0xfb1e, 0xfb1e, NonspacingMark),
+    (0xfb1f, 0xfb28, OtherLetter),
+    (0xfb29, 0xfb29, MathSymbol),
+    (0xfb2a, 0xfb36, OtherLetter),
+    (0xfb38, 0xfb3c, OtherLetter),
+    (0xfb3e, 0xfb3e, OtherLetter),
+    (0xfb40, 0xfb41, OtherLetter),
+    (0xfb43, 0xfb44, OtherLetter),
+    (0xfb46, 0xfbb1, OtherLetter),
+    (0xfbb2, 0xfbc2, ModifierSymbol),
+    (0xfbd3, 0xfd3d, OtherLetter),
+    (0xfd3e, 0xfd3e, ClosePunctuation),
+    (0xfd3f, 0xfd3f, OpenPunctuation),
+    (0xfd40, 0xfd4f, OtherSymbol),
+    (0xfd50, 0xfd8f, OtherLetter),
+    (0xfd92, 0xfdc7, OtherLetter),
+    (0xfdcf, 0xfdcf, OtherSymbol),
+    (0xfdf0, 0xfdfb, OtherLetter),
+    (0xfdfc, 0xfdfc, CurrencySymbol),
+    (0xfdfd, 0xfdff, OtherSymbol),
+    (0xfe00, 0xfe0f, NonspacingMark),
+    (0xfe10, 0xfe16, OtherPunctuation),
+    (0xfe17, 0xfe17, OpenPunctuation),
+    (0xfe18, 0xfe18, ClosePunctuation),
+    (0xfe19, 0xfe19, OtherPunctuation),
+    (0xfe20, 0xfe2f, NonspacingMark),
+    (0xfe30, 0xfe30, OtherPunctuation),
+    (0xfe31, 0xfe32, DashPunctuation),
+    (0xfe33, 0xfe34, ConnectorPunctuation),
+    (0xfe35, 0xfe35, OpenPunctuation),
+    (0xfe36, 0xfe36, ClosePunctuation),
+    (0xfe37, 0xfe37, OpenPunctuation),
+    (0xfe38, 0xfe38, ClosePunctuation),
+    (0xfe39, 0xfe39, OpenPunctuation),
+    (0xfe3a, 0xfe3a, ClosePunctuation),
+    (0xfe3b, 0xfe3b, OpenPunctuation),
+    (0xfe3c, 0xfe3c, ClosePunctuation),
+    (0xfe3d, 0xfe3d, OpenPunctuation),
+    (0xfe3e, 0xfe3e, ClosePunctuation),
+    (0xfe3f, 0xfe3f, OpenPunctuation),
+    (0xfe40, 0xfe40, ClosePunctuation),
+    (0xfe41, 0xfe41, OpenPunctuation),
+    (0xfe42, 0xfe42, ClosePunctuation),
+    (0xfe43, 0xfe43, OpenPunctuation),
+    (0xfe44, 0xfe44, ClosePunctuation),
+    (0xfe45, 0xfe46, OtherPunctuation),
+    (0xfe47, 0xfe47, OpenPunctuation),
+    (0xfe48, 0xfe48, ClosePunctuation),
+    (0xfe49, 0xfe4c, OtherPunctuation),
+    (0xfe4d, 0xfe4f, ConnectorPunctuation),
+    (0xfe50, 0xfe52, OtherPunctuation),
+    (0xfe54, 0xfe57, OtherPunctuation),
+    (0xfe58, 0xfe58, DashPunctuation),
+    (0xfe59, 0xfe59, OpenPunctuation),
+    (0xfe5a, 0xfe5a, ClosePunctuation),
+    (0xfe5b, 0xfe5b, OpenPunctuation),
+    (0xfe5c, 0xfe5c, ClosePunctuation),
+    (0xfe5d, 0xfe5d, OpenPunctuation),
+    (0xfe5e, 0xfe5e, ClosePunctuation),
+    (0xfe5f, 0xfe61, OtherPunctuation),
+    (0xfe62, 0xfe62, MathSymbol),
+    (0xfe63, 0xfe63, DashPunctuation),
+    (0xfe64, 0xfe66, MathSymbol),
+    (0xfe68, 0xfe68, OtherPunctuation),
+    (0xfe69, 0xfe69, CurrencySymbol),
+    (0xfe6a, 0xfe6b, OtherPunctuation),
+    (0xfe70, 0xfe74, OtherLetter),
+    (0xfe76, 0xfefc, OtherLetter),
+    (0xfeff, 0xfeff, Format),
+    (0xff01, 0xff03, OtherPunctuation),
+    (0xff04, 0xff04, CurrencySymbol),
+    (0xff05, 0xff07, OtherPunctuation),
+    (0xff08, 0xff08, OpenPunctuation),
+    (0xff09, 0xff09, ClosePunctuation),
+    (0xff0a, 0xff0a, OtherPunctuation),
+    (0xff0b, 0xff0b, MathSymbol),
+    (0xff0c, 0xff0c, OtherPunctuation),
+    (0xff0d, 0xff0d, DashPunctuation),
+    (0xff0e, 0xff0f, OtherPunctuation),
+    (0xff10, 0xff19, DecimalNumber),
+    (0xff1a, 0xff1b, OtherPunctuation),
+    (0xff1c, 0xff1e, MathSymbol),
+    (0xff1f, 0xff20, OtherPunctuation),
+    (0xff21, 0xff3a, UppercaseLetter),
+    (0xff3b, 0xff3b, OpenPunctuation),
+    (0xff3c, 0xff3c, OtherPunctuation),
+    (0xff3d, 0xff3d, ClosePunctuation),
+    (0xff3e, 0xff3e, ModifierSymbol),
+    (0xff3f, 0xff3f, ConnectorPunctuation),
+    (0xff40, 0xff40, ModifierSymbol),
+    (0xff41, 0xff5a, LowercaseLetter),
+    (0xff5b, 0xff5b, OpenPunctuation),
+    (0xff5c, 0xff5c, MathSymbol),
+    (0xff5d, 0xff5d, ClosePunctuation),
+    (0xff5e, 0xff5e, MathSymbol),
+    (0xff5f, 0xff5f, OpenPunctuation),
+    (0xff60, 0xff60, ClosePunctuation),
+    (0xff61, 0xff61, OtherPunctuation),
+    (0xff62, 0xff62, OpenPunctuation),
+    (0xff63, 0xff63, ClosePunctuation),
+    (0xff64, 0xff65, OtherPunctuation),
+    (0xff66, 0xff6f, OtherLetter),
+    (0xff70, 0xff70, ModifierLetter),
+    (0xff71, 0xff9d, OtherLetter),
+    (0xff9e, 0xff9f, ModifierLetter),
+    (0xffa0, 0xffbe, OtherLetter),
+    (0xffc2, 0xffc7, OtherLetter),
+    (0xffca, 0xffcf, OtherLetter),
+    (0xffd2, 0xffd7, OtherLetter),
+    (0xffda, 0xffdc, OtherLetter),
+    (0xffe0, 0xffe1, CurrencySymbol),
+    (0xffe2, 0xffe2, MathSymbol),
+    (0xffe3, 0xffe3, ModifierSymbol),
+    (0xffe4, 0xffe4, OtherSymbol),
+    (0xffe5, 0xffe6, CurrencySymbol),
+    (0xffe8, 0xffe8, OtherSymbol),
+    (0xffe9, 0xffec, MathSymbol),
+    (0xffed, 0xffee, OtherSymbol),
+    (0xfff9, 0xfffb, Format),
+    (0xfffc, 0xfffd, OtherSymbol),
+    (0x10000, 0x1000b, OtherLetter),
+    (0x1000d, 0x10026, OtherLetter),
+    (0x10028, 0x1003a, OtherLetter),
+    (0x1003c, 0x1003d, OtherLetter),
+    (0x1003f, 0x1004d, OtherLetter),
+    (0x10050, 0x1005d, OtherLetter),
+    (0x10080, 0x100fa, OtherLetter),
+    (0x10100, 0x10102, OtherPunctuation),
+    (0x10107, 0x10133, OtherNumber),
+    (0x10137, 0x1013f, OtherSymbol),
+    (0x10140, 0x10174, LetterNumber),
+    (0x10175, 0x10178, OtherNumber),
+    (0x10179, 0x10189, OtherSymbol),
+    (0x1018a, 0x1018b, OtherNumber),
+    (0x1018c, 0x1018e, OtherSymbol),
+    (0x10190, 0x1019c, OtherSymbol),
+    (0x101a0, 0x101a0, OtherSymbol),
+    (0x101d0, 0x101fc, OtherSymbol),
+    (0x101fd, 0x101fd, NonspacingMark),
+    (0x10280, 0x1029c, OtherLetter),
+    (0x102a0, 0x102d0, OtherLetter),
+    (0x102e0, 0x102e0, NonspacingMark),
+    (0x102e1, 0x102fb, OtherNumber),
+    (0x10300, 0x1031f, OtherLetter),
+    (0x10320, 0x10323, OtherNumber),
+    (0x1032d, 0x10340, OtherLetter),
+    (0x10341, 0x10341, LetterNumber),
+    (0x10342, 0x10349, OtherLetter),
+    (0x1034a, 0x1034a, LetterNumber),
+    (0x10350, 0x10375, OtherLetter),
+    (0x10376, 0x1037a, NonspacingMark),
+    (0x10380, 0x1039d, OtherLetter),
+    (0x1039f, 0x1039f, OtherPunctuation),
+    (0x103a0, 0x103c3, OtherLetter),
+    (0x103c8, 0x103cf, OtherLetter),
+    (0x103d0, 0x103d0, OtherPunctuation),
+    (0x103d1, 0x103d5, LetterNumber),
+    (0x10400, 0x10427, UppercaseLetter),
+    (0x10428, 0x1044f, LowercaseLetter),
+    (0x10450, 0x1049d, OtherLetter),
+    (0x104a0, 0x104a9, DecimalNumber),
+    (0x104b0, 0x104d3, UppercaseLetter),
+    (0x104d8, 0x104fb, LowercaseLetter),
+    (0x10500, 0x10527, OtherLetter),
+    (0x10530, 0x10563, OtherLetter),
+    (0x1056f, 0x1056f, OtherPunctuation),
+    (0x10570, 0x1057a, UppercaseLetter),
+    (0x1057c, 0x1058a, UppercaseLetter),
+    (0x1058c, 0x10592, UppercaseLetter),
+    (0x10594, 0x10595, UppercaseLetter),
+    (0x10597, 0x105a1, LowercaseLetter),
+    (0x105a3, 0x105b1, LowercaseLetter),
+    (0x105b3, 0x105b9, LowercaseLetter),
+    (0x105bb, 0x105bc, LowercaseLetter),
+    (0x10600, 0x10736, OtherLetter),
+    (0x10740, 0x10755, OtherLetter),
+    (0x10760, 0x10767, OtherLetter),
+    (0x10780, 0x10785, ModifierLetter),
+    (0x10787, 0x107b0, ModifierLetter),
+    (0x107b2, 0x107ba, ModifierLetter),
+    (0x10800, 0x10805, OtherLetter),
+    (0x10808, 0x10808, OtherLetter),
+    (0x1080a, 0x10835, OtherLetter),
+    (0x10837, 0x10838, OtherLetter),
+    (0x1083c, 0x1083c, OtherLetter),
+    (0x1083f, 0x10855, OtherLetter),
+    (0x10857, 0x10857, OtherPunctuation),
+    (0x10858, 0x1085f, OtherNumber),
+    (0x10860, 0x10876, OtherLetter),
+    (0x10877, 0x10878, OtherSymbol),
+    (0x10879, 0x1087f, OtherNumber),
+    (0x10880, 0x1089e, OtherLetter),
+    (0x108a7, 0x108af, OtherNumber),
+    (0x108e0, 0x108f2, OtherLetter),
+    (0x108f4, 0x108f5, OtherLetter),
+    (0x108fb, 0x108ff, OtherNumber),
+    (0x10900, 0x10915, OtherLetter),
+    (0x10916, 0x1091b, OtherNumber),
+    (0x1091f, 0x1091f, OtherPunctuation),
+    (0x10920, 0x10939, OtherLetter),
+    (0x1093f, 0x1093f, OtherPunctuation),
+    (0x10980, 0x109b7, OtherLetter),
+    (0x109bc, 0x109bd, OtherNumber),
+    (0x109be, 0x109bf, OtherLetter),
+    (0x109c0, 0x109cf, OtherNumber),
+    (0x109d2, 0x109ff, OtherNumber),
+    (0x10a00, 0x10a00, OtherLetter),
+    (0x10a01, 0x10a03, NonspacingMark),
+    (0x10a05, 0x10a06, NonspacingMark),
+    (0x10a0c, 0x10a0f, NonspacingMark),
+    (0x10a10, 0x10a13, OtherLetter),
+    (0x10a15, 0x10a17, OtherLetter),
+    (0x10a19, 0x10a35, OtherLetter),
+    (0x10a38, 0x10a3a, NonspacingMark),
+    (0x10a3f, 0x10a3f, NonspacingMark),
+    (0x10a40, 0x10a48, OtherNumber),
+    (0x10a50, 0x10a58, OtherPunctuation),
+    (0x10a60, 0x10a7c, OtherLetter),
+    (0x10a7d, 0x10a7e, OtherNumber),
+    (0x10a7f, 0x10a7f, OtherPunctuation),
+    (0x10a80, 0x10a9c, OtherLetter),
+    (0x10a9d, 0x10a9f, OtherNumber),
+    (0x10ac0, 0x10ac7, OtherLetter),
+    (0x10ac8, 0x10ac8, OtherSymbol),
+    (0x10ac9, 0x10ae4, OtherLetter),
+    (0x10ae5, 0x10ae6, NonspacingMark),
+    (0x10aeb, 0x10aef, OtherNumber),
+    (0x10af0, 0x10af6, OtherPunctuation),
+    (0x10b00, 0x10b35, OtherLetter),
+    (0x10b39, 0x10b3f, OtherPunctuation),
+    (0x10b40, 0x10b55, OtherLetter),
+    (0x10b58, 0x10b5f, OtherNumber),
+    (0x10b60, 0x10b72, OtherLetter),
+    (0x10b78, 0x10b7f, OtherNumber),
+    (0x10b80, 0x10b91, OtherLetter),
+    (0x10b99, 0x10b9c, OtherPunctuation),
+    (0x10ba9, 0x10baf, OtherNumber),
+    (0x10c00, 0x10c48, OtherLetter),
+    (0x10c80, 0x10cb2, UppercaseLetter),
+    (0x10cc0, 0x10cf2, LowercaseLetter),
+    (0x10cfa, 0x10cff, OtherNumber),
+    (0x10d00, 0x10d23, OtherLetter),
+    (0x10d24, 0x10d27, NonspacingMark),
+    (0x10d30, 0x10d39, DecimalNumber),
+    (0x10e60, 0x10e7e, OtherNumber),
+    (0x10e80, 0x10ea9, OtherLetter),
+    (0x10eab, 0x10eac, NonspacingMark),
+    (0x10ead, 0x10ead, DashPunctuation),
+    (0x10eb0, 0x10eb1, OtherLetter),
+    (0x10f00, 0x10f1c, OtherLetter),
+    (0x10f1d, 0x10f26, OtherNumber),
+    (0x10f27, 0x10f27, OtherLetter),
+    (0x10f30, 0x10f45, OtherLetter),
+    (0x10f46, 0x10f50, NonspacingMark),
+    (0x10f51, 0x10f54, OtherNumber),
+    (0x10f55, 0x10f59, OtherPunctuation),
+    (0x10f70, 0x10f81, OtherLetter),
+    (0x10f82, 0x10f85, NonspacingMark),
+    (0x10f86, 0x10f89, OtherPunctuation),
+    (0x10fb0, 0x10fc4, OtherLetter),
+    (0x10fc5, 0x10fcb, OtherNumber),
+    (0x10fe0, 0x10ff6, OtherLetter),
+    (0x11000, 0x11000, SpacingMark),
+    (0x11001, 0x11001, NonspacingMark),
+    (0x11002, 0x11002, SpacingMark),
+    (0x11003, 0x11037, OtherLetter),
+    (0x11038, 0x11046, NonspacingMark),
+    (0x11047, 0x1104d, OtherPunctuation),
+    (0x11052, 0x11065, OtherNumber),
+    (0x11066, 0x1106f, DecimalNumber),
+    (0x11070, 0x11070, NonspacingMark),
+    (0x11071, 0x11072, OtherLetter),
+    (0x11073, 0x11074, NonspacingMark),
+    (0x11075, 0x11075, OtherLetter),
+    (0x1107f, 0x11081, NonspacingMark),
+    (0x11082, 0x11082, SpacingMark),
+    (0x11083, 0x110af, OtherLetter),
+    (0x110b0, 0x110b2, SpacingMark),
+    (0x110b3, 0x110b6, NonspacingMark),
+    (0x110b7, 0x110b8, SpacingMark),
+    (0x110b9, 0x110ba, NonspacingMark),
+    (0x110bb, 0x110bc, OtherPunctuation),
+    (0x110bd, 0x110bd, Format),
+    (0x110be, 0x110c1, OtherPunctuation),
+    (0x110c2, 0x110c2, NonspacingMark),
+    (0x110cd, 0x110cd, Format),
+    (0x110d0, 0x110e8, OtherLetter),
+    (0x110f0, 0x110f9, DecimalNumber),
+    (0x11100, 0x11102, NonspacingMark),
+    (0x11103, 0x11126, OtherLetter),
+    (0x11127, 0x1112b, NonspacingMark),
+    (0x1112c, 0x1112c, SpacingMark),
+    (0x1112d, 0x11134, NonspacingMark),
+    (0x11136, 0x1113f, DecimalNumber),
+    (0x11140, 0x11143, OtherPunctuation),
+    (0x11144, 0x11144, OtherLetter),
+    (0x11145, 0x11146, SpacingMark),
+    (0x11147, 0x11147, OtherLetter),
+    (0x11150, 0x11172, OtherLetter),
+    (0x11173, 0x11173, NonspacingMark),
+    (0x11174, 0x11175, OtherPunctuation),
+    (0x11176, 0x11176, OtherLetter),
+    (0x11180, 0x11181, NonspacingMark),
+    (0x11182, 0x11182, SpacingMark),
+    (0x11183, 0x111b2, OtherLetter),
+    (0x111b3, 0x111b5, SpacingMark),
+    (0x111b6, 0x111be, NonspacingMark),
+    (0x111bf, 0x111c0, SpacingMark),
+    (0x111c1, 0x111c4, OtherLetter),
+    (0x111c5, 0x111c8, OtherPunctuation),
+    (0x111c9, 0x111cc, NonspacingMark),
+    (0x111cd, 0x111cd, OtherPunctuation),
+    (0x111ce, 0x111ce, SpacingMark),
+    (0x111cf, 0x111cf, NonspacingMark),
+    (0x111d0, 0x111d9, DecimalNumber),
+    (0x111da, 0x111da, OtherLetter),
+    (0x111db, 0x111db, OtherPunctuation),
+    (0x111dc, 0x111dc, OtherLetter),
+    (0x111dd, 0x111df, OtherPunctuation),
+    (0x111e1, 0x111f4, OtherNumber),
+    (0x11200, 0x11211, OtherLetter),
+    (0x11213, 0x1122b, OtherLetter),
+    (0x1122c, 0x1122e, SpacingMark),
+    (0x1122f, 0x11231, NonspacingMark),
+    (0x11232, 0x11233, SpacingMark),
+    (0x11234, 0x11234, NonspacingMark),
+    (0x11235, 0x11235, SpacingMark),
+    (0x11236, 0x11237, NonspacingMark),
+    (0x11238, 0x1123d, OtherPunctuation),
+    (0x1123e, 0x1123e, NonspacingMark),
+    (0x11280, 0x11286, OtherLetter),
+    (0x11288, 0x11288, OtherLetter),
+    (0x1128a, 0x1128d, OtherLetter),
+    (0x1128f, 0x1129d, OtherLetter),
+    (0x1129f, 0x112a8, OtherLetter),
+    (0x112a9, 0x112a9, OtherPunctuation),
+    (0x112b0, 0x112de, OtherLetter),
+    (0x112df, 0x112df, NonspacingMark),
+    (0x112e0, 0x112e2, SpacingMark),
+    (0x112e3, 0x112ea, NonspacingMark),
+    (0x112f0, 0x112f9, DecimalNumber),
+    (0x11300, 0x11301, NonspacingMark),
+    (0x11302, 0x11303, SpacingMark),
+    (0x11305, 0x1130c, OtherLetter),
+    (0x1130f, 0x11310, OtherLetter),
+    (0x11313, 0x11328, OtherLetter),
+    (0x1132a, 0x11330, OtherLetter),
+    (0x11332, 0x11333, OtherLetter),
+    (0x11335, 0x11339, OtherLetter),
+    (0x1133b, 0x1133c, NonspacingMark),
+    (0x1133d, 0x1133d, OtherLetter),
+    (0x1133e, 0x1133f, SpacingMark),
+    (0x11340, 0x11340, NonspacingMark),
+    (0x11341, 0x11344, SpacingMark),
+    (0x11347, 0x11348, SpacingMark),
+    (0x1134b, 0x1134d, SpacingMark),
+    (0x11350, 0x11350, OtherLetter),
+    (0x11357, 0x11357, SpacingMark),
+    (0x1135d, 0x11361, OtherLetter),
+    (0x11362, 0x11363, SpacingMark),
+    (0x11366, 0x1136c, NonspacingMark),
+    (0x11370, 0x11374, NonspacingMark),
+    (0x11400, 0x11434, OtherLetter),
+    (0x11435, 0x11437, SpacingMark),
+    (0x11438, 0x1143f, NonspacingMark),
+    (0x11440, 0x11441, SpacingMark),
+    (0x11442, 0x11444, NonspacingMark),
+    (0x11445, 0x11445, SpacingMark),
+    (0x11446, 0x11446, NonspacingMark),
+    (0x11447, 0x1144a, OtherLetter),
+    (0x1144b, 0x1144f, OtherPunctuation),
+    (0x11450, 0x11459, DecimalNumber),
+    (0x1145a, 0x1145b, OtherPunctuation),
+    (0x1145d, 0x1145d, OtherPunctuation),
+    (0x1145e, 0x1145e, NonspacingMark),
+    (0x1145f, 0x11461, OtherLetter),
+    (0x11480, 0x114af, OtherLetter),
+    (0x114b0, 0x114b2, SpacingMark),
+    (0x114b3, 0x114b8, NonspacingMark),
+    (0x114b9, 0x114b9, SpacingMark),
+    (0x114ba, 0x114ba, NonspacingMark),
+    (0x114bb, 0x114be, SpacingMark),
+    (0x114bf, 0x114c0, NonspacingMark),
+    (0x114c1, 0x114c1, SpacingMark),
+    (0x114c2, 0x114c3, NonspacingMark),
+    (0x114c4, 0x114c5, OtherLetter),
+    (0x114c6, 0x114c6, OtherPunctuation),
+    (0x114c7, 0x114c7, OtherLetter),
+    (0x114d0, 0x114d9, DecimalNumber),
+    (0x11580, 0x115ae, OtherLetter),
+    (0x115af, 0x115b1, SpacingMark),
+    (0x115b2, 0x115b5, NonspacingMark),
+    (0x115b8, 0x115bb, SpacingMark),
+    (0x115bc, 0x115bd, NonspacingMark),
+    (0x115be, 0x115be, SpacingMark),
+    (0x115bf, 0x115c0, NonspacingMark),
+    (0x115c1, 0x115d7, OtherPunctuation),
+    (0x115d8, 0x115db, OtherLetter),
+    (0x115dc, 0x115dd, NonspacingMark),
+    (0x11600, 0x1162f, OtherLetter),
+    (0x11630, 0x11632, SpacingMark),
+    (0x11633, 0x1163a, NonspacingMark),
+    (0x1163b, 0x1163c, SpacingMark),
+    (0x1163d, 0x1163d, NonspacingMark),
+    (0x1163e, 0x1163e, SpacingMark),
+    (0x1163f, 0x11640, NonspacingMark),
+    (0x11641, 0x11643, OtherPunctuation),
+    (0x11644, 0x11644, OtherLetter),
+    (0x11650, 0x11659, DecimalNumber),
+    (0x11660, 0x1166c, OtherPunctuation),
+    (0x11680, 0x116aa, OtherLetter),
+    (0x116ab, 0x116ab, NonspacingMark),
+    (0x116ac, 0x116ac, SpacingMark),
+    (0x116ad, 0x116ad, NonspacingMark),
+    (0x116ae, 0x116af, SpacingMark),
+    (0x116b0, 0x116b5, NonspacingMark),
+    (0x116b6, 0x116b6, SpacingMark),
+    (0x116b7, 0x116b7, NonspacingMark),
+    (0x116b8, 0x116b8, OtherLetter),
+    (0x116b9, 0x116b9, OtherPunctuation),
+    (0x116c0, 0x116c9, DecimalNumber),
+    (0x11700, 0x1171a, OtherLetter),
+    (0x1171d, 0x1171f, NonspacingMark),
+    (0x11720, 0x11721, SpacingMark),
+    (0x11722, 0x11725, NonspacingMark),
+    (0x11726, 0x11726, SpacingMark),
+    (0x11727, 0x1172b, NonspacingMark),
+    (0x11730, 0x11739, DecimalNumber),
+    (0x1173a, 0x1173b, OtherNumber),
+    (0x1173c, 0x1173e, OtherPunctuation),
+    (0x1173f, 0x1173f, OtherSymbol),
+    (0x11740, 0x11746, OtherLetter),
+    (0x11800, 0x1182b, OtherLetter),
+    (0x1182c, 0x1182e, SpacingMark),
+    (0x1182f, 0x11837, NonspacingMark),
+    (0x11838, 0x11838, SpacingMark),
+    (0x11839, 0x1183a, NonspacingMark),
+    (0x1183b, 0x1183b, OtherPunctuation),
+    (0x118a0, 0x118bf, UppercaseLetter),
+    (0x118c0, 0x118df, LowercaseLetter),
+    (0x118e0, 0x118e9, DecimalNumber),
+    (0x118ea, 0x118f2, OtherNumber),
+    (0x118ff, 0x11906, OtherLetter),
+    (0x11909, 0x11909, OtherLetter),
+    (0x1190c, 0x11913, OtherLetter),
+    (0x11915, 0x11916, OtherLetter),
+    (0x11918, 0x1192f, OtherLetter),
+    (0x11930, 0x11935, SpacingMark),
+    (0x11937, 0x11938, SpacingMark),
+    (0x1193b, 0x1193c, NonspacingMark),
+    (0x1193d, 0x1193d, SpacingMark),
+    (0x1193e, 0x1193e, NonspacingMark),
+    (0x1193f, 0x1193f, OtherLetter),
+    (0x11940, 0x11940, SpacingMark),
+    (0x11941, 0x11941, OtherLetter),
+    (0x11942, 0x11942, SpacingMark),
+    (0x11943, 0x11943, NonspacingMark),
+    (0x11944, 0x11946, OtherPunctuation),
+    (0x11950, 0x11959, DecimalNumber),
+    (0x119a0, 0x119a7, OtherLetter),
+    (0x119aa, 0x119d0, OtherLetter),
+    (0x119d1, 0x119d3, SpacingMark),
+    (0x119d4, 0x119d7, NonspacingMark),
+    (0x119da, 0x119db, NonspacingMark),
+    (0x119dc, 0x119df, SpacingMark),
+    (0x119e0, 0x119e0, NonspacingMark),
+    (0x119e1, 0x119e1, OtherLetter),
+    (0x119e2, 0x119e2, OtherPunctuation),
+    (0x119e3, 0x119e3, OtherLetter),
+    (0x119e4, 0x119e4, SpacingMark),
+    (0x11a00, 0x11a00, OtherLetter),
+    (0x11a01, 0x11a0a, NonspacingMark),
+    (0x11a0b, 0x11a32, OtherLetter),
+    (0x11a33, 0x11a38, NonspacingMark),
+    (0x11a39, 0x11a39, SpacingMark),
+    (0x11a3a, 0x11a3a, OtherLetter),
+    (0x11a3b, 0x11a3e, NonspacingMark),
+    (0x11a3f, 0x11a46, OtherPunctuation),
+    (0x11a47, 0x11a47, NonspacingMark),
+    (0x11a50, 0x11a50, OtherLetter),
+    (0x11a51, 0x11a56, NonspacingMark),
+    (0x11a57, 0x11a58, SpacingMark),
+    (0x11a59, 0x11a5b, NonspacingMark),
+    (0x11a5c, 0x11a89, OtherLetter),
+    (0x11a8a, 0x11a96, NonspacingMark),
+    (0x11a97, 0x11a97, SpacingMark),
+    (0x11a98, 0x11a99, NonspacingMark),
+    (0x11a9a, 0x11a9c, OtherPunctuation),
+    (0x11a9d, 0x11a9d, OtherLetter),
+    (0x11a9e, 0x11aa2, OtherPunctuation),
+    (0x11ab0, 0x11af8, OtherLetter),
+    (0x11c00, 0x11c08, OtherLetter),
+    (0x11c0a, 0x11c2e, OtherLetter),
+    (0x11c2f, 0x11c2f, SpacingMark),
+    (0x11c30, 0x11c36, NonspacingMark),
+    (0x11c38, 0x11c3d, NonspacingMark),
+    (0x11c3e, 0x11c3e, SpacingMark),
+    (0x11c3f, 0x11c3f, NonspacingMark),
+    (0x11c40, 0x11c40, OtherLetter),
+    (0x11c41, 0x11c45, OtherPunctuation),
+    (0x11c50, 0x11c59, DecimalNumber),
+    (0x11c5a, 0x11c6c, OtherNumber),
+    (0x11c70, 0x11c71, OtherPunctuation),
+    (0x11c72, 0x11c8f, OtherLetter),
+    (0x11c92, 0x11ca7, NonspacingMark),
+    (0x11ca9, 0x11ca9, SpacingMark),
+    (0x11caa, 0x11cb0, NonspacingMark),
+    (0x11cb1, 0x11cb1, SpacingMark),
+    (0x11cb2, 0x11cb3, NonspacingMark),
+    (0x11cb4, 0x11cb4, SpacingMark),
+    (0x11cb5, 0x11cb6, NonspacingMark),
+    (0x11d00, 0x11d06, OtherLetter),
+    (0x11d08, 0x11d09, OtherLetter),
+    (0x11d0b, 0x11d30, OtherLetter),
+    (0x11d31, 0x11d36, NonspacingMark),
+    (0x11d3a, 0x11d3a, NonspacingMark),
+    (0x11d3c, 0x11d3d, NonspacingMark),
+    (0x11d3f, 0x11d45, NonspacingMark),
+    (0x11d46, 0x11d46, OtherLetter),
+    (0x11d47, 0x11d47, NonspacingMark),
+    (0x11d50, 0x11d59, DecimalNumber),
+    (0x11d60, 0x11d65, OtherLetter),
+    (0x11d67, 0x11d68, OtherLetter),
+    (0x11d6a, 0x11d89, OtherLetter),
+    (0x11d8a, 0x11d8e, SpacingMark),
+    (0x11d90, 0x11d91, NonspacingMark),
+    (0x11d93, 0x11d94, SpacingMark),
+    (0x11d95, 0x11d95, NonspacingMark),
+    (0x11d96, 0x11d96, SpacingMark),
+    (0x11d97, 0x11d97, NonspacingMark),
+    (0x11d98, 0x11d98, OtherLetter),
+    (0x11da0, 0x11da9, DecimalNumber),
+    (0x11ee0, 0x11ef2, OtherLetter),
+    (0x11ef3, 0x11ef4, NonspacingMark),
+    (0x11ef5, 0x11ef6, SpacingMark),
+    (0x11ef7, 0x11ef8, OtherPunctuation),
+    (0x11fb0, 0x11fb0, OtherLetter),
+    (0x11fc0, 0x11fd4, OtherNumber),
+    (0x11fd5, 0x11fdc, OtherSymbol),
+    (0x11fdd, 0x11fe0, CurrencySymbol),
+    (0x11fe1, 0x11ff1, OtherSymbol),
+    (0x11fff, 0x11fff, OtherPunctuation),
+    (0x12000, 0x12399, OtherLetter),
+    (0x12400, 0x1246e, LetterNumber),
+    (0x12470, 0x12474, OtherPunctuation),
+    (0x12480, 0x12543, OtherLetter),
+    (0x12f90, 0x12ff0, OtherLetter),
+    (0x12ff1, 0x12ff2, OtherPunctuation),
+    (0x13000, 0x1342e, OtherLetter),
+    (0x13430, 0x13438, Format),
+    (0x14400, 0x14646, OtherLetter),
+    (0x16800, 0x16a38, OtherLetter),
+    (0x16a40, 0x16a5e, OtherLetter),
+    (0x16a60, 0x16a69, DecimalNumber),
+    (0x16a6e, 0x16a6f, OtherPunctuation),
+    (0x16a70, 0x16abe, OtherLetter),
+    (0x16ac0, 0x16ac9, DecimalNumber),
+    (0x16ad0, 0x16aed, OtherLetter),
+    (0x16af0, 0x16af4, NonspacingMark),
+    (0x16af5, 0x16af5, OtherPunctuation),
+    (0x16b00, 0x16b2f, OtherLetter),
+    (0x16b30, 0x16b36, NonspacingMark),
+    (0x16b37, 0x16b3b, OtherPunctuation),
+    (0x16b3c, 0x16b3f, OtherSymbol),
+    (0x16b40, 0x16b43, ModifierLetter),
+    (0x16b44, 0x16b44, OtherPunctuation),
+    (0x16b45, 0x16b45, OtherSymbol),
+    (0x16b50, 0x16b59, DecimalNumber),
+    (0x16b5b, 0x16b61, OtherNumber),
+    (0x16b63, 0x16b77, OtherLetter),
+    (0x16b7d, 0x16b8f, OtherLetter),
+    (0x16e40, 0x16e5f, UppercaseLetter),
+    (0x16e60, 0x16e7f, LowercaseLetter),
+    (0x16e80, 0x16e96, OtherNumber),
+    (0x16e97, 0x16e9a, OtherPunctuation),
+    (0x16f00, 0x16f4a, OtherLetter),
+    (0x16f4f, 0x16f4f, NonspacingMark),
+    (0x16f50, 0x16f50, OtherLetter),
+    (0x16f51, 0x16f87, SpacingMark),
+    (0x16f8f, 0x16f92, NonspacingMark),
+    (0x16f93, 0x16f9f, ModifierLetter),
+    (0x16fe0, 0x16fe1, ModifierLetter),
+    (0x16fe2, 0x16fe2, OtherPunctuation),
+    (0x16fe3, 0x16fe3, ModifierLetter),
+    (0x16fe4, 0x16fe4, NonspacingMark),
+    (0x16ff0, 0x16ff1, SpacingMark),
+    (0x17000, 0x187f7, OtherLetter),
+    (0x18800, 0x18cd5, OtherLetter),
+    (0x18d00, 0x18d08, OtherLetter),
+    (0x1aff0, 0x1aff3, ModifierLetter),
+    (0x1aff5, 0x1affb, ModifierLetter),
+    (0x1affd, 0x1affe, ModifierLetter),
+    (0x1b000, 0x1b122, OtherLetter),
+    (0x1b150, 0x1b152, OtherLetter),
+    (0x1b164, 0x1b167, OtherLetter),
+    (0x1b170, 0x1b2fb, OtherLetter),
+    (0x1bc00, 0x1bc6a, OtherLetter),
+    (0x1bc70, 0x1bc7c, OtherLetter),
+    (0x1bc80, 0x1bc88, OtherLetter),
+    (0x1bc90, 0x1bc99, OtherLetter),
+    (0x1bc9c, 0x1bc9c, OtherSymbol),
+    (0x1bc9d, 0x1bc9e, NonspacingMark),
+    (0x1bc9f, 0x1bc9f, OtherPunctuation),
+    (0x1bca0, 0x1bca3, Format),
+    (0x1cf00, 0x1cf2d, NonspacingMark),
+    (0x1cf30, 0x1cf46, NonspacingMark),
+    (0x1cf50, 0x1cfc3, OtherSymbol),
+    (0x1d000, 0x1d0f5, OtherSymbol),
+    (0x1d100, 0x1d126, OtherSymbol),
+    (0x1d129, 0x1d164, OtherSymbol),
+    (0x1d165, 0x1d166, SpacingMark),
+    (0x1d167, 0x1d169, NonspacingMark),
+    (0x1d16a, 0x1d16c, OtherSymbol),
+    (0x1d16d, 0x1d172, SpacingMark),
+    (0x1d173, 0x1d17a, Format),
+    (0x1d17b, 0x1d182, NonspacingMark),
+    (0x1d183, 0x1d184, OtherSymbol),
+    (0x1d185, 0x1d18b, NonspacingMark),
+    (0x1d18c, 0x1d1a9, OtherSymbol),
+    (0x1d1aa, 0x1d1ad, NonspacingMark),
+    (0x1d1ae, 0x1d1ea, OtherSymbol),
+    (0x1d200, 0x1d241, OtherSymbol),
+    (0x1d242, 0x1d244, NonspacingMark),
+    (0x1d245, 0x1d245, OtherSymbol),
+    (0x1d2e0, 0x1d2f3, OtherNumber),
+    (0x1d300, 0x1d356, OtherSymbol),
+    (0x1d360, 0x1d378, OtherNumber),
+    (0x1d400, 0x1d419, UppercaseLetter),
+    (0x1d41a, 0x1d433, LowercaseLetter),
+    (0x1d434, 0x1d44d, UppercaseLetter),
+    (0x1d44e, 0x1d454, LowercaseLetter),
+    (0x1d456, 0x1d467, LowercaseLetter),
+    (0x1d468, 0x1d481, UppercaseLetter),
+    (0x1d482, 0x1d49b, LowercaseLetter),
+    (0x1d49c, 0x1d49c, UppercaseLetter),
+    (0x1d49e, 0x1d49f, UppercaseLetter),
+    (0x1d4a2, 0x1d4a2, UppercaseLetter),
+    (0x1d4a5, 0x1d4a6, UppercaseLetter),
+    (0x1d4a9, 0x1d4ac, UppercaseLetter),
+    (0x1d4ae, 0x1d4b5, UppercaseLetter),
+    (0x1d4b6, 0x1d4b9, LowercaseLetter),
+    (0x1d4bb, 0x1d4bb, LowercaseLetter),
+    (0x1d4bd, 0x1d4c3, LowercaseLetter),
+    (0x1d4c5, 0x1d4cf, LowercaseLetter),
+    (0x1d4d0, 0x1d4e9, UppercaseLetter),
+    (0x1d4ea, 0x1d503, LowercaseLetter),
+    (0x1d504, 0x1d505, UppercaseLetter),
+    (0x1d507, 0x1d50a, UppercaseLetter),
+    (0x1d50d, 0x1d514, UppercaseLetter),
+    (0x1d516, 0x1d51c, UppercaseLetter),
+    (0x1d51e, 0x1d537, LowercaseLetter),
+    (0x1d538, 0x1d539, UppercaseLetter),
+    (0x1d53b, 0x1d53e, UppercaseLetter),
+    (0x1d540, 0x1d544, UppercaseLetter),
+    (0x1d546, 0x1d546, UppercaseLetter),
+    (0x1d54a, 0x1d550, UppercaseLetter),
+    (0x1d552, 0x1d56b, LowercaseLetter),
+    (0x1d56c, 0x1d585, UppercaseLetter),
+    (0x1d586, 0x1d59f, LowercaseLetter),
+    (0x1d5a0, 0x1d5b9, UppercaseLetter),
+    (0x1d5ba, 0x1d5d3, LowercaseLetter),
+    (0x1d5d4, 0x1d5ed, UppercaseLetter),
+    (0x1d5ee, 0x1d607, LowercaseLetter),
+    (0x1d608, 0x1d621, UppercaseLetter),
+    (0x1d622, 0x1d63b, LowercaseLetter),
+    (0x1d63c, 0x1d655, UppercaseLetter),
+    (0x1d656, 0x1d66f, LowercaseLetter),
+    (0x1d670, 0x1d689, UppercaseLetter),
+    (0x1d68a, 0x1d6a5, LowercaseLetter),
+    (0x1d6a8, 0x1d6c0, UppercaseLetter),
+    (0x1d6c1, 0x1d6c1, MathSymbol),
+    (0x1d6c2, 0x1d6da, LowercaseLetter),
+    (0x1d6db, 0x1d6db, MathSymbol),
+    (0x1d6dc, 0x1d6e1, LowercaseLetter),
+    (0x1d6e2, 0x1d6fa, UppercaseLetter),
+    (0x1d6fb, 0x1d6fb, MathSymbol),
+    (0x1d6fc, 0x1d714, LowercaseLetter),
+    (0x1d715, 0x1d715, MathSymbol),
+    (0x1d716, 0x1d71b, LowercaseLetter),
+    (0x1d71c, 0x1d734, UppercaseLetter),
+    (0x1d735, 0x1d735, MathSymbol),
+    (0x1d736, 0x1d74e, LowercaseLetter),
+    (0x1d74f, 0x1d74f, MathSymbol),
+    (0x1d750, 0x1d755, LowercaseLetter),
+    (0x1d756, 0x1d76e, UppercaseLetter),
+    (0x1d76f, 0x1d76f, MathSymbol),
+    (0x1d770, 0x1d788, LowercaseLetter),
+    (0x1d789, 0x1d789, MathSymbol),
+    (0x1d78a, 0x1d78f, LowercaseLetter),
+    (0x1d790, 0x1d7a8, UppercaseLetter),
+    (0x1d7a9, 0x1d7a9, MathSymbol),
+    (0x1d7aa, 0x1d7c2, LowercaseLetter),
+    (0x1d7c3, 0x1d7c3, MathSymbol),
+    (0x1d7c4, 0x1d7c9, LowercaseLetter),
+    (0x1d7ca, 0x1d7ca, UppercaseLetter),
+    (0x1d7cb, 0x1d7cb, LowercaseLetter),
+    (0x1d7ce, 0x1d7ff, DecimalNumber),
+    (0x1d800, 0x1d9ff, OtherSymbol),
+    (0x1da00, 0x1da36, NonspacingMark),
+    (0x1da37, 0x1da3a, OtherSymbol),
+    (0x1da3b, 0x1da6c, NonspacingMark),
+    (0x1da6d, 0x1da74, OtherSymbol),
+    (0x1da75, 0x1da75, NonspacingMark),
+    (0x1da76, 0x1da83, OtherSymbol),
+    (0x1da84, 0x1da84, NonspacingMark),
+    (0x1da85, 0x1da86, OtherSymbol),
+    (0x1da87, 0x1da8b, OtherPunctuation),
+    (0x1da9b, 0x1da9f, NonspacingMark),
+    (0x1daa1, 0x1daaf, NonspacingMark),
+    (0x1df00, 0x1df09, LowercaseLetter),
+    (0x1df0a, 0x1df0a, OtherLetter),
+    (0x1df0b, 0x1df1e, LowercaseLetter),
+    (0x1e000, 0x1e006, NonspacingMark),
+    (0x1e008, 0x1e018, NonspacingMark),
+    (0x1e01b, 0x1e021, NonspacingMark),
+    (0x1e023, 0x1e024, NonspacingMark),
+    (0x1e026, 0x1e02a, NonspacingMark),
+    (0x1e100, 0x1e12c, OtherLetter),
+    (0x1e130, 0x1e136, NonspacingMark),
+    (0x1e137, 0x1e13d, ModifierLetter),
+    (0x1e140, 0x1e149, DecimalNumber),
+    (0x1e14e, 0x1e14e, OtherLetter),
+    (0x1e14f, 0x1e14f, OtherSymbol),
+    (0x1e290, 0x1e2ad, OtherLetter),
+    (0x1e2ae, 0x1e2ae, NonspacingMark),
+    (0x1e2c0, 0x1e2eb, OtherLetter),
+    (0x1e2ec, 0x1e2ef, NonspacingMark),
+    (0x1e2f0, 0x1e2f9, DecimalNumber),
+    (0x1e2ff, 0x1e2ff, CurrencySymbol),
+    (0x1e7e0, 0x1e7e6, OtherLetter),
+    (0x1e7e8, 0x1e7eb, OtherLetter),
+    (0x1e7ed, 0x1e7ee, OtherLetter),
+    (0x1e7f0, 0x1e7fe, OtherLetter),
+    (0x1e800, 0x1e8c4, OtherLetter),
+    (0x1e8c7, 0x1e8cf, OtherNumber),
+    (0x1e8d0, 0x1e8d6, NonspacingMark),
+    (0x1e900, 0x1e921, UppercaseLetter),
+    (0x1e922, 0x1e943, LowercaseLetter),
+    (0x1e944, 0x1e94a, NonspacingMark),
+    (0x1e94b, 0x1e94b, ModifierLetter),
+    (0x1e950, 0x1e959, DecimalNumber),
+    (0x1e95e, 0x1e95f, OtherPunctuation),
+    (0x1ec71, 0x1ecab, OtherNumber),
+    (0x1ecac, 0x1ecac, OtherSymbol),
+    (0x1ecad, 0x1ecaf, OtherNumber),
+    (0x1ecb0, 0x1ecb0, CurrencySymbol),
+    (0x1ecb1, 0x1ecb4, OtherNumber),
+    (0x1ed01, 0x1ed2d, OtherNumber),
+    (0x1ed2e, 0x1ed2e, OtherSymbol),
+    (0x1ed2f, 0x1ed3d, OtherNumber),
+    (0x1ee00, 0x1ee03, OtherLetter),
+    (0x1ee05, 0x1ee1f, OtherLetter),
+    (0x1ee21, 0x1ee22, OtherLetter),
+    (0x1ee24, 0x1ee24, OtherLetter),
+    (0x1ee27, 0x1ee27, OtherLetter),
+    (0x1ee29, 0x1ee32, OtherLetter),
+    (0x1ee34, 0x1ee37, OtherLetter),
+    (0x1ee39, 0x1ee39, OtherLetter),
+    (0x1ee3b, 0x1ee3b, OtherLetter),
+    (0x1ee42, 0x1ee42, OtherLetter),
+    (0x1ee47, 0x1ee47, OtherLetter),
+    (0x1ee49, 0x1ee49, OtherLetter),
+    (0x1ee4b, 0x1ee4b, OtherLetter),
+    (0x1ee4d, 0x1ee4f, OtherLetter),
+    (0x1ee51, 0x1ee52, OtherLetter),
+    (0x1ee54, 0x1ee54, OtherLetter),
+    (0x1ee57, 0x1ee57, OtherLetter),
+    (0x1ee59, 0x1ee59, OtherLetter),
+    (0x1ee5b, 0x1ee5b, OtherLetter),
+    (0x1ee5d, 0x1ee5d, OtherLetter),
+    (0x1ee5f, 0x1ee5f, OtherLetter),
+    (0x1ee61, 0x1ee62, OtherLetter),
+    (0x1ee64, 0x1ee64, OtherLetter),
+    (0x1ee67, 0x1ee6a, OtherLetter),
+    (0x1ee6c, 0x1ee72, OtherLetter),
+    (0x1ee74, 0x1ee77, OtherLetter),
+    (0x1ee79, 0x1ee7c, OtherLetter),
+    (0x1ee7e, 0x1ee7e, OtherLetter),
+    (0x1ee80, 0x1ee89, OtherLetter),
+    (0x1ee8b, 0x1ee9b, OtherLetter),
+    (0x1eea1, 0x1eea3, OtherLetter),
+    (0x1eea5, 0x1eea9, OtherLetter),
+    (0x1eeab, 0x1eebb, OtherLetter),
+    (0x1eef0, 0x1eef1, MathSymbol),
+    (0x1f000, 0x1f02b, OtherSymbol),
+    (0x1f030, 0x1f093, OtherSymbol),
+    (0x1f0a0, 0x1f0ae, OtherSymbol),
+    (0x1f0b1, 0x1f0bf, OtherSymbol),
+    (0x1f0c1, 0x1f0cf, OtherSymbol),
+    (0x1f0d1, 0x1f0f5, OtherSymbol),
+    (0x1f100, 0x1f10c, OtherNumber),
+    (0x1f10d, 0x1f1ad, OtherSymbol),
+    (0x1f1e6, 0x1f202, OtherSymbol),
+    (0x1f210, 0x1f23b, OtherSymbol),
+    (0x1f240, 0x1f248, OtherSymbol),
+    (0x1f250, 0x1f251, OtherSymbol),
+    (0x1f260, 0x1f265, OtherSymbol),
+    (0x1f300, 0x1f3fa, OtherSymbol),
+    (0x1f3fb, 0x1f3ff, ModifierSymbol),
+    (0x1f400, 0x1f6d7, OtherSymbol),
+    (0x1f6dd, 0x1f6ec, OtherSymbol),
+    (0x1f6f0, 0x1f6fc, OtherSymbol),
+    (0x1f700, 0x1f773, OtherSymbol),
+    (0x1f780, 0x1f7d8, OtherSymbol),
+    (0x1f7e0, 0x1f7eb, OtherSymbol),
+    (0x1f7f0, 0x1f7f0, OtherSymbol),
+    (0x1f800, 0x1f80b, OtherSymbol),
+    (0x1f810, 0x1f847, OtherSymbol),
+    (0x1f850, 0x1f859, OtherSymbol),
+    (0x1f860, 0x1f887, OtherSymbol),
+    (0x1f890, 0x1f8ad, OtherSymbol),
+    (0x1f8b0, 0x1f8b1, OtherSymbol),
+    (0x1f900, 0x1fa53, OtherSymbol),
+    (0x1fa60, 0x1fa6d, OtherSymbol),
+    (0x1fa70, 0x1fa74, OtherSymbol),
+    (0x1fa78, 0x1fa7c, OtherSymbol),
+    (0x1fa80, 0x1fa86, OtherSymbol),
+    (0x1fa90, 0x1faac, OtherSymbol),
+    (0x1fab0, 0x1faba, OtherSymbol),
+    (0x1fac0, 0x1fac5, OtherSymbol),
+    (0x1fad0, 0x1fad9, OtherSymbol),
+    (0x1fae0, 0x1fae7, OtherSymbol),
+    (0x1faf0, 0x1faf6, OtherSymbol),
+    (0x1fb00, 0x1fb92, OtherSymbol),
+    (0x1fb94, 0x1fbca, OtherSymbol),
+    (0x1fbf0, 0x1fbf9, DecimalNumber),
+    (0x20000, 0x2a6df, OtherLetter),
+    (0x2a700, 0x2b738, OtherLetter),
+    (0x2b740, 0x2b81d, OtherLetter),
+    (0x2b820, 0x2cea1, OtherLetter),
+    (0x2ceb0, 0x2ebe0, OtherLetter),
+    (0x2f800, 0x2fa1d, OtherLetter),
+    (0x30000, 0x3134a, OtherLetter),
+    (0xe0001, 0xe0001, Format),
+    (0xe0020, 0xe007f, Format),
+    (0xe0100, 0xe01ef, NonspacingMark),
+    (0xf0000, 0xffffd, PrivateUse),
+    (0x100000, 0x10fffd, PrivateUse),
+];
@@ -0,0 +1,8 @@
+#![cfg(feature = "unstable-prelude-2021-v1")]
+
+#[test]
+fn parser_fn() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/parser_fn/pass-*.rs");
+    cases.compile_fail("tests/ui/parser_fn/fail-*.rs");
+}
@@ -0,0 +1,10 @@
+use lavan::parser_fn;
+
+pub struct Nothing;
+
+#[parser_fn]
+fn nothing(self: &Nothing, count: usize) -> Option<char> {
+    None
+}
+
+fn main() {}
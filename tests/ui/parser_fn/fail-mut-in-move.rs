@@ -0,0 +1,10 @@
+use lavan::parser_fn;
+
+pub struct Nothing;
+
+#[parser_fn(mut in move)]
+fn nothing(self: &Nothing) -> Option<char> {
+    None
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use lavan::parser_fn;
+
+pub struct Nothing;
+
+#[parser_fn]
+fn nothing(self: &Nothing) {}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use lavan::parser_fn;
+
+#[parser_fn]
+fn nothing() -> Option<char> {
+    None
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use lavan::parser_fn;
+
+pub struct Nothing;
+
+#[parser_fn]
+fn nothing<LEFT, RIGHT>(self: &Nothing) -> Option<char> {
+    None
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use lavan::parser_fn;
+
+pub struct Nothing;
+
+#[parser_fn(mut in ref)]
+fn nothing(self: &Nothing) -> Option<char> {
+    None
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use lavan::parser_fn;
+
+pub struct Nothing;
+
+impl Nothing {
+    #[parser_fn]
+    fn nothing(&self) -> Option<char> {
+        None
+    }
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+// only the macro is imported: the parser traits are in scope within the body
+use lavan::parser_fn;
+
+pub struct Pair<Par>(Par);
+
+// parsing twice requires moving the parser into a mutable binding first
+#[parser_fn(mut in move)]
+fn pair<par>(mut self: &Pair<par>) -> Option<(char, char)>
+where
+    par::Output: Into<Option<char>>,
+{
+    let first = parse![self.0].into()?;
+    let second = parse![self.0].into()?;
+    Some((first, second))
+}
+
+pub struct Byte(u8);
+
+#[parser_fn]
+fn byte<'a>(self: &Byte, input: std::slice::Iter<'a, u8>) -> bool {
+    let state = input.savestate();
+    if input.next() == Some(&self.0) {
+        return true;
+    }
+    input.backtrack(state);
+    false
+}
+
+fn main() {
+    use lavan::prelude::*;
+
+    let pair = Pair(any());
+    assert_eq!(pair.parse(&mut "ab".chars()), Some(('a', 'b')));
+    assert!(Byte(1).parse(&mut [1, 2].iter()));
+}